@@ -0,0 +1,234 @@
+//! Benchmarks comparing `ArcStr` (and `Substr`) against `Arc<str>`, `String`,
+//! and `compact_str::CompactString`.
+//!
+//! Run with `cargo bench`, or `cargo bench -- <filter>` to run a subset (for
+//! example, `cargo bench -- clone`).
+use arcstr::{ArcStr, Substr};
+use compact_str::CompactString;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+const SIZES: &[usize] = &[4, 16, 64, 1024];
+
+fn input(len: usize) -> String {
+    "abcdefghijklmnopqrstuvwxyz0123456789"
+        .chars()
+        .cycle()
+        .take(len)
+        .collect()
+}
+
+fn hash_one<T: Hash + ?Sized>(v: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    v.hash(&mut h);
+    h.finish()
+}
+
+// Runs `$body` as a benchmark for each of the types, with `$v` being a value of
+// that type created from `$s` (a `&str`).
+macro_rules! bench_each {
+    ($group:expr, $size:expr, $s:expr, |$v:ident| $body:expr) => {{
+        let s: &str = $s;
+        let $v = ArcStr::from(s);
+        $group.bench_with_input(BenchmarkId::new("ArcStr", $size), &$v, |b, $v| {
+            b.iter(|| $body)
+        });
+        let $v: Arc<str> = Arc::from(s);
+        $group.bench_with_input(BenchmarkId::new("Arc<str>", $size), &$v, |b, $v| {
+            b.iter(|| $body)
+        });
+        let $v = String::from(s);
+        $group.bench_with_input(BenchmarkId::new("String", $size), &$v, |b, $v| {
+            b.iter(|| $body)
+        });
+        let $v = CompactString::from(s);
+        $group.bench_with_input(BenchmarkId::new("CompactString", $size), &$v, |b, $v| {
+            b.iter(|| $body)
+        });
+    }};
+}
+
+fn create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create");
+    for &size in SIZES {
+        let s = input(size);
+        let s = s.as_str();
+        group.bench_function(BenchmarkId::new("ArcStr", size), |b| {
+            b.iter(|| ArcStr::from(black_box(s)))
+        });
+        group.bench_function(BenchmarkId::new("Arc<str>", size), |b| {
+            b.iter(|| Arc::<str>::from(black_box(s)))
+        });
+        group.bench_function(BenchmarkId::new("String", size), |b| {
+            b.iter(|| String::from(black_box(s)))
+        });
+        group.bench_function(BenchmarkId::new("CompactString", size), |b| {
+            b.iter(|| CompactString::from(black_box(s)))
+        });
+    }
+    group.finish();
+}
+
+// Lots of short strings alive at once, created and dropped in batches, like
+// the keys of a parsed JSON object. This is what `pool-alloc` is for.
+fn create_drop_churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_drop_churn");
+    let keys: Vec<String> = (0..1000).map(|i| format!("key_{}", i)).collect();
+    group.bench_function("ArcStr", |b| {
+        b.iter(|| {
+            let v: Vec<ArcStr> = keys.iter().map(|k| ArcStr::from(k.as_str())).collect();
+            black_box(v)
+        })
+    });
+    group.bench_function("Arc<str>", |b| {
+        b.iter(|| {
+            let v: Vec<Arc<str>> = keys.iter().map(|k| Arc::from(k.as_str())).collect();
+            black_box(v)
+        })
+    });
+    group.finish();
+}
+
+fn clone_drop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_drop");
+    for &size in SIZES {
+        bench_each!(group, size, &input(size), |v| drop(black_box(v.clone())));
+    }
+    // Literals don't touch the reference count at all.
+    let lit = arcstr::literal!("a static string literal");
+    group.bench_function("ArcStr literal", |b| {
+        b.iter(|| drop(black_box(lit.clone())))
+    });
+    group.finish();
+}
+
+fn eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eq_same_content");
+    for &size in SIZES {
+        let other = input(size);
+        bench_each!(group, size, &input(size), |v| black_box(&**v)
+            == black_box(&*other));
+    }
+    group.finish();
+
+    // Comparing a string to a clone of itself. `ArcStr` can tell these are equal
+    // from the pointer, without looking at the data.
+    let mut group = c.benchmark_group("eq_same_pointer");
+    for &size in SIZES {
+        let s = input(size);
+        let a = ArcStr::from(&s);
+        let a2 = a.clone();
+        group.bench_function(BenchmarkId::new("ArcStr", size), |b| {
+            b.iter(|| black_box(&a) == black_box(&a2))
+        });
+        let a: Arc<str> = Arc::from(&*s);
+        let a2 = a.clone();
+        group.bench_function(BenchmarkId::new("Arc<str>", size), |b| {
+            b.iter(|| black_box(&a) == black_box(&a2))
+        });
+    }
+    group.finish();
+}
+
+fn hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    for &size in SIZES {
+        bench_each!(group, size, &input(size), |v| hash_one(black_box(v)));
+    }
+    group.finish();
+}
+
+fn substr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("substr_from_range");
+    for &size in SIZES {
+        let a = ArcStr::from(input(size));
+        let range = 1..size - 1;
+        group.bench_function(BenchmarkId::new("Substr", size), |b| {
+            b.iter(|| a.substr(black_box(range.clone())))
+        });
+        // The closest equivalents for the other types copy the data.
+        let s: Arc<str> = Arc::from(&*a);
+        group.bench_function(BenchmarkId::new("Arc<str>", size), |b| {
+            b.iter(|| Arc::<str>::from(&s[black_box(range.clone())]))
+        });
+        group.bench_function(BenchmarkId::new("String", size), |b| {
+            b.iter(|| String::from(&a[black_box(range.clone())]))
+        });
+        group.bench_function(BenchmarkId::new("CompactString", size), |b| {
+            b.iter(|| CompactString::from(&a[black_box(range.clone())]))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("substr_split");
+    let text = (0..100)
+        .map(|i| format!("word{}", i))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let whole = Substr::from(ArcStr::from(&text));
+    group.bench_function("Substr::split", |b| {
+        b.iter(|| black_box(&whole).split(' ').count())
+    });
+    group.bench_function("str::split", |b| {
+        b.iter(|| black_box(text.as_str()).split(' ').count())
+    });
+    group.bench_function("str::split to String", |b| {
+        b.iter(|| {
+            black_box(text.as_str())
+                .split(' ')
+                .map(String::from)
+                .count()
+        })
+    });
+    group.finish();
+}
+
+// Looking up a key which is the same `ArcStr` that was inserted, in a
+// `LazyArcStrMap` (which checks pointers first) and a `HashMap` (which always
+// hashes the contents).
+#[cfg(feature = "map")]
+fn map_lookup(c: &mut Criterion) {
+    use arcstr::LazyArcStrMap;
+    use std::collections::HashMap;
+    let mut group = c.benchmark_group("map_lookup");
+    for &(n, len) in &[(8, 16), (8, 256), (64, 16), (64, 256)] {
+        let keys: Vec<ArcStr> = (0..n)
+            .map(|i| ArcStr::from(format!("{}{}", "x".repeat(len), i)))
+            .collect();
+        let lazy: LazyArcStrMap<usize> = keys.iter().cloned().zip(0..).collect();
+        let hash: HashMap<ArcStr, usize> = keys.iter().cloned().zip(0..).collect();
+        let id = format!("{} keys of {} bytes", n, len);
+        group.bench_function(BenchmarkId::new("LazyArcStrMap", &id), |b| {
+            b.iter(|| {
+                keys.iter()
+                    .map(|k| black_box(&lazy).get(k).unwrap())
+                    .sum::<usize>()
+            })
+        });
+        group.bench_function(BenchmarkId::new("HashMap", &id), |b| {
+            b.iter(|| {
+                keys.iter()
+                    .map(|k| black_box(&hash).get(k).unwrap())
+                    .sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "map"))]
+fn map_lookup(_: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    create,
+    create_drop_churn,
+    clone_drop,
+    eq,
+    hash,
+    map_lookup,
+    substr
+);
+criterion_main!(benches);
@@ -0,0 +1,112 @@
+//! Runs an arbitrary sequence of operations on `ArcStr`s and `Arc<str>`s with
+//! the same contents, checking that the comparison, hashing, and formatting
+//! results always match.
+#![no_main]
+use arbitrary::Arbitrary;
+use arcstr::ArcStr;
+use libfuzzer_sys::fuzz_target;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Create(String),
+    /// Uses one of a few literals, so that static `ArcStr`s get compared too.
+    CreateStatic(u8),
+    Clone(u8),
+    Drop(u8),
+    Compare(u8, u8),
+    CompareStr(u8, String),
+    Hash(u8),
+    Fmt(u8),
+}
+
+static LITERALS: [ArcStr; 4] = [
+    arcstr::literal!(""),
+    arcstr::literal!("a"),
+    arcstr::literal!("abc"),
+    arcstr::literal!("\u{1F980} \0 \"quoted\" \n"),
+];
+
+fn hash_of<T: Hash + ?Sized>(v: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    v.hash(&mut h);
+    h.finish()
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut pairs: Vec<(ArcStr, Arc<str>)> = vec![];
+    // Picks one of `pairs`, if there are any.
+    let pick = |pairs: &[(ArcStr, Arc<str>)], i: u8| {
+        if pairs.is_empty() {
+            None
+        } else {
+            Some(i as usize % pairs.len())
+        }
+    };
+    for op in ops {
+        match op {
+            Op::Create(s) => pairs.push((ArcStr::from(&s[..]), Arc::from(&s[..]))),
+            Op::CreateStatic(i) => {
+                let lit = LITERALS[i as usize % LITERALS.len()].clone();
+                let arc = Arc::from(lit.as_str());
+                pairs.push((lit, arc));
+            }
+            Op::Clone(i) => {
+                if let Some(i) = pick(&pairs, i) {
+                    let (a, b) = &pairs[i];
+                    let pair = (a.clone(), b.clone());
+                    pairs.push(pair);
+                }
+            }
+            Op::Drop(i) => {
+                if let Some(i) = pick(&pairs, i) {
+                    pairs.swap_remove(i);
+                }
+            }
+            Op::Compare(i, j) => {
+                if let (Some(i), Some(j)) = (pick(&pairs, i), pick(&pairs, j)) {
+                    let ((a0, b0), (a1, b1)) = (&pairs[i], &pairs[j]);
+                    assert_eq!(a0 == a1, b0 == b1);
+                    assert_eq!(a0 != a1, b0 != b1);
+                    assert_eq!(a0.partial_cmp(a1), b0.partial_cmp(b1));
+                    assert_eq!(a0.cmp(a1), b0.cmp(b1));
+                    assert_eq!(a0 < a1, b0 < b1);
+                    assert_eq!(a0 >= a1, b0 >= b1);
+                }
+            }
+            Op::CompareStr(i, s) => {
+                if let Some(i) = pick(&pairs, i) {
+                    let (a, b) = &pairs[i];
+                    assert_eq!(*a == *s, **b == *s);
+                    assert_eq!(a == s.as_str(), &**b == s.as_str());
+                    assert_eq!(a.as_str().cmp(&s), (**b).cmp(&s[..]));
+                }
+            }
+            Op::Hash(i) => {
+                if let Some(i) = pick(&pairs, i) {
+                    let (a, b) = &pairs[i];
+                    assert_eq!(hash_of(a), hash_of(b));
+                    // Which is also the hash of the `str`, which is what makes
+                    // `Borrow<str>` work.
+                    assert_eq!(hash_of(a), hash_of(a.as_str()));
+                }
+            }
+            Op::Fmt(i) => {
+                if let Some(i) = pick(&pairs, i) {
+                    let (a, b) = &pairs[i];
+                    assert_eq!(format!("{:?}", a), format!("{:?}", b));
+                    assert_eq!(format!("{}", a), format!("{}", b));
+                    assert_eq!(
+                        format!("{:>8.3}|{:<5}", a, a),
+                        format!("{:>8.3}|{:<5}", b, b)
+                    );
+                }
+            }
+        }
+    }
+    for (a, b) in &pairs {
+        assert_eq!(a.as_str(), &**b);
+    }
+});
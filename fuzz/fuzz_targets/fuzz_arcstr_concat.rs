@@ -0,0 +1,17 @@
+//! Concatenates up to 8 arbitrary strings, checking the result against
+//! `[String]::join`.
+#![no_main]
+use arcstr::ArcStr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, Vec<&str>)| {
+    let (sep, mut parts) = input;
+    parts.truncate(8);
+    let joined = parts.join(sep);
+    let s = ArcStr::concat_with_separator(sep, &parts);
+    assert_eq!(s, joined);
+    assert_eq!(s.len(), joined.len());
+    let arcs: Vec<ArcStr> = parts.iter().map(|p| ArcStr::from(*p)).collect();
+    assert_eq!(ArcStr::concat_with_separator(sep, &arcs), joined);
+    assert_eq!(ArcStr::concat_with_separator("", &parts), parts.concat());
+});
@@ -0,0 +1,38 @@
+//! Creates an `ArcStr` from arbitrary bytes, clones it a bunch, and drops the
+//! clones, checking the contents and refcount along the way. Leaks are caught
+//! by libFuzzer's leak detection.
+#![no_main]
+use arcstr::ArcStr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u8, &[u8])| {
+    let (clones, bytes) = input;
+    let text = match core::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let s = ArcStr::from(text);
+    assert_eq!(s, text);
+    assert_eq!(s.len(), text.len());
+    // Empty strings don't allocate, so they have no count.
+    let base = ArcStr::strong_count(&s);
+    assert_eq!(base, if text.is_empty() { None } else { Some(1) });
+
+    let mut all = vec![];
+    for i in 0..clones as usize {
+        all.push(s.clone());
+        assert_eq!(ArcStr::strong_count(&s), base.map(|_| i + 2));
+    }
+    for c in &all {
+        assert!(ArcStr::ptr_eq(c, &s));
+        assert_eq!(c, text);
+    }
+    drop(all);
+    assert_eq!(ArcStr::strong_count(&s), base);
+
+    // Round trip through a raw pointer.
+    let raw = ArcStr::into_raw(s);
+    let s = unsafe { ArcStr::from_raw(raw) };
+    assert_eq!(s, text);
+    assert_eq!(ArcStr::strong_count(&s), base);
+});
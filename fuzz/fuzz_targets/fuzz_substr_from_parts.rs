@@ -0,0 +1,42 @@
+//! Takes substrings of an arbitrary string at arbitrary offsets, checking that
+//! `ArcStr::substr` and `try_substr_from` agree with slicing the `str`.
+#![no_main]
+use arcstr::ArcStr;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, Vec<(usize, usize)>)| {
+    let (text, ranges) = input;
+    let parent = ArcStr::from(text);
+    // Same contents, different allocation (unless it's empty).
+    let other = String::from(text);
+    for (start, end) in ranges {
+        let (start, end) = (start % (text.len() + 2), end % (text.len() + 2));
+        let slice = match parent.get(start..end) {
+            Some(slice) => slice,
+            None => {
+                assert!(text.get(start..end).is_none());
+                continue;
+            }
+        };
+        let sub = parent.substr(start..end);
+        assert_eq!(sub, slice);
+        assert_eq!(sub.range(), start..end);
+        assert!(ArcStr::ptr_eq(sub.parent(), &parent));
+
+        let from = parent.try_substr_from(slice).unwrap();
+        let nested = sub.try_substr_from(slice).unwrap();
+        assert_eq!(from, sub);
+        assert_eq!(nested, sub);
+        if slice.is_empty() {
+            // Empty strings always succeed, without referencing `parent`.
+            assert!(parent.try_substr_from(&other[start..end]).is_some());
+            continue;
+        }
+        assert_eq!(from.range(), start..end);
+        // A substring of a substring is still relative to the first parent.
+        assert_eq!(nested.range(), start..end);
+        // Not from `parent`, so it's rejected.
+        assert!(parent.try_substr_from(&other[start..end]).is_none());
+        assert!(sub.try_substr_from(&other[start..end]).is_none());
+    }
+});
@@ -0,0 +1,357 @@
+use crate::arc_str::StaticArcStrInner;
+use crate::ArcStr;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// `feature = "bytes-type"`: An [`ArcStr`] for arbitrary bytes.
+///
+/// This is a cheaply cloneable, immutable, reference-counted byte string, and
+/// can be used in place of `Arc<[u8]>`. It uses the same representation as
+/// `ArcStr` (so it's a single pointer, and static `ArcBytes` created with
+/// [`arcstr::literal_bytes!`](crate::literal_bytes) need no heap allocation
+/// or reference counting), but doesn't require that its contents are UTF-8.
+///
+/// Since the representation is the same, an `ArcStr` can be converted into an
+/// `ArcBytes` without copying.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcBytes, ArcStr};
+/// let b = ArcBytes::from(&b"\xff\x00abc"[..]);
+/// assert_eq!(b.len(), 5);
+/// assert_eq!(b.as_bytes(), b"\xff\x00abc");
+///
+/// const LIT: ArcBytes = arcstr::literal_bytes!(b"\xfe\xff");
+/// assert_eq!(ArcBytes::as_static(&LIT), Some(&b"\xfe\xff"[..]));
+///
+/// let s = ArcStr::from("text");
+/// let b = ArcBytes::from(s.clone());
+/// assert_eq!(b.as_ptr(), s.as_ptr());
+/// ```
+// Note: the `ArcStr` here may hold arbitrary bytes, so it must never be used
+// as a `str`, or be given out to users. It's only used for its allocation and
+// reference counting (and the methods of it which don't look at the contents).
+#[derive(Clone)]
+#[repr(transparent)]
+pub struct ArcBytes(ArcStr);
+
+impl ArcBytes {
+    /// Construct a new empty `ArcBytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// assert!(ArcBytes::new().is_empty());
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Self(ArcStr::new())
+    }
+
+    /// Extract a byte slice containing our data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// let b = ArcBytes::from(&[1u8, 2, 3][..]);
+    /// assert_eq!(b.as_bytes(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    /// Returns the length of this `ArcBytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// assert_eq!(ArcBytes::from(&b"foo"[..]).len(), 3);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if this `ArcBytes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// assert!(!ArcBytes::from(&b"foo"[..]).is_empty());
+    /// assert!(ArcBytes::new().is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the two `ArcBytes` point to the same allocation. See
+    /// [`ArcStr::ptr_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// let a = ArcBytes::from(&b"foo"[..]);
+    /// assert!(ArcBytes::ptr_eq(&a, &a.clone()));
+    /// assert!(!ArcBytes::ptr_eq(&a, &ArcBytes::from(&b"foo"[..])));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(lhs: &Self, rhs: &Self) -> bool {
+        ArcStr::ptr_eq(&lhs.0, &rhs.0)
+    }
+
+    /// Returns the number of references that exist to this `ArcBytes`, or
+    /// `None` if it is static. See [`ArcStr::strong_count`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// let a = ArcBytes::from(&b"foo"[..]);
+    /// let b = a.clone();
+    /// assert_eq!(ArcBytes::strong_count(&a), Some(2));
+    /// assert_eq!(ArcBytes::strong_count(&arcstr::literal_bytes!(b"foo")), None);
+    /// ```
+    #[inline]
+    pub fn strong_count(this: &Self) -> Option<usize> {
+        ArcStr::strong_count(&this.0)
+    }
+
+    /// Returns true if `this` is a "static" `ArcBytes`, for example, one
+    /// created by [`arcstr::literal_bytes!`](crate::literal_bytes), or from a
+    /// static `ArcStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// assert!(ArcBytes::is_static(&arcstr::literal_bytes!(b"abc")));
+    /// assert!(!ArcBytes::is_static(&ArcBytes::from(&b"abc"[..])));
+    /// ```
+    #[inline]
+    pub fn is_static(this: &Self) -> bool {
+        ArcStr::is_static(&this.0)
+    }
+
+    /// Returns the data as a `&'static [u8]` if `this` is static, and `None`
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcBytes;
+    /// let b = arcstr::literal_bytes!(b"abc");
+    /// assert_eq!(ArcBytes::as_static(&b), Some(&b"abc"[..]));
+    /// assert_eq!(ArcBytes::as_static(&ArcBytes::from(&b"abc"[..])), None);
+    /// ```
+    #[inline]
+    pub fn as_static(this: &Self) -> Option<&'static [u8]> {
+        if Self::is_static(this) {
+            // Static data lives forever.
+            Some(unsafe { &*(this.as_bytes() as *const [u8]) })
+        } else {
+            None
+        }
+    }
+
+    // Not public API. Exists so the `arcstr::literal_bytes` macro can call it.
+    #[inline]
+    #[doc(hidden)]
+    pub const unsafe fn _private_new_from_static_data<B>(
+        ptr: &'static StaticArcStrInner<B>,
+    ) -> Self {
+        Self(ArcStr::_private_new_from_static_data(ptr))
+    }
+}
+
+impl core::ops::Deref for ArcBytes {
+    type Target = [u8];
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Default for ArcBytes {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&[u8]> for ArcBytes {
+    #[inline]
+    fn from(b: &[u8]) -> Self {
+        // Safety: the inner `ArcStr` is never used as a `str`.
+        Self(unsafe { ArcStr::alloc_raw_bytes(b) })
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for ArcBytes {
+    #[inline]
+    fn from(b: &[u8; N]) -> Self {
+        Self::from(&b[..])
+    }
+}
+
+impl From<Vec<u8>> for ArcBytes {
+    #[inline]
+    fn from(b: Vec<u8>) -> Self {
+        Self::from(&b[..])
+    }
+}
+
+impl From<&Vec<u8>> for ArcBytes {
+    #[inline]
+    fn from(b: &Vec<u8>) -> Self {
+        Self::from(&b[..])
+    }
+}
+
+impl From<Box<[u8]>> for ArcBytes {
+    #[inline]
+    fn from(b: Box<[u8]>) -> Self {
+        Self::from(&b[..])
+    }
+}
+
+impl<'a> From<Cow<'a, [u8]>> for ArcBytes {
+    #[inline]
+    fn from(b: Cow<'a, [u8]>) -> Self {
+        Self::from(&*b)
+    }
+}
+
+impl From<&str> for ArcBytes {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::from(s.as_bytes())
+    }
+}
+
+/// Doesn't copy the string data: the result shares the same allocation.
+impl From<ArcStr> for ArcBytes {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        Self(s)
+    }
+}
+
+/// Doesn't copy the string data: the result shares the same allocation.
+impl From<&ArcStr> for ArcBytes {
+    #[inline]
+    fn from(s: &ArcStr) -> Self {
+        Self(s.clone())
+    }
+}
+
+impl From<&ArcBytes> for ArcBytes {
+    #[inline]
+    fn from(b: &ArcBytes) -> Self {
+        b.clone()
+    }
+}
+
+impl From<ArcBytes> for Vec<u8> {
+    #[inline]
+    fn from(b: ArcBytes) -> Self {
+        b.as_bytes().to_vec()
+    }
+}
+
+impl core::fmt::Debug for ArcBytes {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_bytes(), f)
+    }
+}
+
+impl PartialEq for ArcBytes {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        ArcBytes::ptr_eq(self, o) || PartialEq::eq(self.as_bytes(), o.as_bytes())
+    }
+}
+
+impl Eq for ArcBytes {}
+
+macro_rules! impl_peq {
+    (@one $a:ty, $b:ty) => {
+        #[allow(clippy::extra_unused_lifetimes)]
+        impl<'a> PartialEq<$b> for $a {
+            #[inline]
+            fn eq(&self, s: &$b) -> bool {
+                PartialEq::eq(&self[..], &s[..])
+            }
+        }
+    };
+    ($(($a:ty, $b:ty),)+) => {$(
+        impl_peq!(@one $a, $b);
+        impl_peq!(@one $b, $a);
+    )+};
+}
+
+impl_peq! {
+    (ArcBytes, [u8]),
+    (ArcBytes, &'a [u8]),
+    (ArcBytes, Vec<u8>),
+    (ArcBytes, Cow<'a, [u8]>),
+    (ArcBytes, Box<[u8]>),
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ArcBytes {
+    #[inline]
+    fn eq(&self, s: &[u8; N]) -> bool {
+        self.as_bytes() == s
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for ArcBytes {
+    #[inline]
+    fn eq(&self, s: &&[u8; N]) -> bool {
+        self.as_bytes() == *s
+    }
+}
+
+impl PartialOrd for ArcBytes {
+    #[inline]
+    fn partial_cmp(&self, s: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(s))
+    }
+}
+
+impl Ord for ArcBytes {
+    #[inline]
+    fn cmp(&self, s: &Self) -> core::cmp::Ordering {
+        self.as_bytes().cmp(s.as_bytes())
+    }
+}
+
+impl core::hash::Hash for ArcBytes {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.as_bytes().hash(h)
+    }
+}
+
+impl AsRef<[u8]> for ArcBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl core::borrow::Borrow<[u8]> for ArcBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
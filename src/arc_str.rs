@@ -7,18 +7,29 @@
     clippy::redundant_slicing,
 )]
 use core::alloc::Layout;
+#[cfg(not(all(loom, test)))]
+use core::hint::spin_loop;
 use core::mem::{align_of, size_of, MaybeUninit};
 use core::ptr::NonNull;
 #[cfg(not(all(loom, test)))]
 pub(crate) use core::sync::atomic::{AtomicUsize, Ordering};
 #[cfg(all(loom, test))]
+use loom::hint::spin_loop;
+#[cfg(all(loom, test))]
 pub(crate) use loom::sync::atomic::{AtomicUsize, Ordering};
 
+// The allocator behind `ThinInner`. These have the same API.
+#[cfg(feature = "pool-alloc")]
+use crate::pool as heap;
+#[cfg(not(feature = "pool-alloc"))]
+use alloc::alloc as heap;
+
 #[cfg(feature = "substr")]
 use crate::Substr;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// A better atomically-reference counted string type.
 ///
@@ -47,13 +58,16 @@ use alloc::string::String;
 ///   want to keep the data structure lightweight or need to do some FFI stuff
 ///   with it.
 ///
-/// - `ArcStr` is totally immutable. No need to lose sleep because you're afraid
-///   of code which thinks it has a right to mutate your `Arc`s just because it
-///   holds the only reference...
+/// - `ArcStr` is immutable while it's shared. The only way to change the
+///   contents is [`ArcStr::try_reuse`], which overwrites them in place only if
+///   it's given the sole reference to a non-static, non-leaked string of the
+///   same length (and otherwise allocates a new one), so nobody else can
+///   observe the change.
 ///
-/// - Lower reference counting operations are lower overhead because we don't
-///   support `Weak` references. This can be a drawback for some use cases, but
-///   improves performance for the common case of no-weak-refs.
+/// - Weak references are supported (see [`ArcStr::downgrade`] and
+///   [`WeakArcStr`]), but are kept off the fast path: `clone` is unaffected,
+///   and `drop` only touches the weak count when dropping the last strong
+///   reference.
 ///
 /// ## What does "zero-cost literals" mean?
 ///
@@ -68,7 +82,13 @@ use alloc::string::String;
 ///
 /// (Additionally, it's almost certain that in the future we'll be able to
 /// reduce the synchronization required for atomic instructions. This is due to
-/// our guarantee of immutability and lack of support for `Weak`.)
+/// shared `ArcStr`s never being modified.)
+///
+/// If you don't need to share strings across threads at all, the `rc-str`
+/// feature provides `RcStr`, which works the same way but uses a non-atomic
+/// reference count. (There's intentionally no feature which makes `ArcStr`
+/// itself non-atomic: cargo features must be additive, and removing `Send` and
+/// `Sync` from `ArcStr` would break other crates using it in the same build.)
 ///
 /// # Usage
 ///
@@ -119,7 +139,6 @@ use alloc::string::String;
 /// let test_but_loud = ArcStr::from("TEST");
 /// assert!(test_str.eq_ignore_ascii_case(&test_but_loud));
 /// ```
-
 #[repr(transparent)]
 pub struct ArcStr(NonNull<ThinInner>);
 
@@ -297,6 +316,32 @@ impl ArcStr {
         }
     }
 
+    /// Allocates an `ArcStr` holding a copy of `bytes`, which need not be
+    /// UTF-8.
+    ///
+    /// Safety: Unless `bytes` is valid UTF-8, the result must never be used as
+    /// a `str` (or be handed to anyone who might). This only exists for
+    /// `ArcBytes`, which uses an `ArcStr` for its allocation and refcounting.
+    #[cfg(feature = "bytes-type")]
+    pub(crate) unsafe fn alloc_raw_bytes(bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            return Self::new();
+        }
+        let copy = |buf: &mut [MaybeUninit<u8>]| {
+            debug_assert_eq!(buf.len(), bytes.len());
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                buf.as_mut_ptr().cast::<u8>(),
+                bytes.len(),
+            );
+        };
+        match ThinInner::try_allocate_with(bytes.len(), false, AllocInit::Uninit, copy) {
+            Ok(inner) => Self(inner),
+            Err(None) => alloc_overflow(),
+            Err(Some(layout)) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+
     /// Extract a string slice containing our data.
     ///
     /// Note: This is an equivalent to our `Deref` implementation, but can be
@@ -348,11 +393,116 @@ impl ArcStr {
         self.len() == 0
     }
 
+    /// Returns the number of `char`s (Unicode scalar values) in this `ArcStr`.
+    ///
+    /// This is equivalent to `s.chars().count()`, but is easier to find, and
+    /// may be optimized in the future.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("héllo 🙀");
+    /// assert_eq!(s.len(), 11);
+    /// assert_eq!(s.char_count(), 7);
+    /// ```
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        char_count(self)
+    }
+
+    /// Returns an iterator over the byte indices of every `char` boundary in
+    /// this string, in order.
+    ///
+    /// This includes both `0` and `self.len()`, so a string with `n` chars has
+    /// `n + 1` boundaries (and the empty string has one: `0`). Every yielded
+    /// index is one for which [`str::is_char_boundary`] returns true, so any
+    /// two of them can be used as the bounds of a range to slice `self` with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("aé🙀");
+    /// let bounds: Vec<usize> = s.char_boundary_indices().collect();
+    /// assert_eq!(bounds, [0, 1, 3, 7]);
+    /// // Find the byte offset of the 2nd char boundary.
+    /// assert_eq!(s.char_boundary_indices().nth(2), Some(3));
+    /// ```
+    #[inline]
+    pub fn char_boundary_indices(&self) -> ArcStrCharBoundaries<'_> {
+        ArcStrCharBoundaries::new(self.as_str())
+    }
+
+    /// Returns this string encoded as UTF-16, in a new `Vec<u16>`.
+    ///
+    /// This is equivalent to `s.encode_utf16().collect::<Vec<u16>>()`. See
+    /// [`ArcStr::encode_utf16_to_buf`] to reuse an existing buffer instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("hi 🙀");
+    /// assert_eq!(s.encode_utf16_to_vec(), [0x68, 0x69, 0x20, 0xd83d, 0xde40]);
+    /// ```
+    #[inline]
+    pub fn encode_utf16_to_vec(&self) -> Vec<u16> {
+        self.encode_utf16().collect()
+    }
+
+    /// Appends this string, encoded as UTF-16, to the end of `buf`, and returns
+    /// the part of `buf` that was appended.
+    ///
+    /// This is useful for callers who want to reuse an allocation across
+    /// several strings (for example, when repeatedly calling an API that takes
+    /// UTF-16).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let mut buf = vec![];
+    /// for s in [ArcStr::from("ab"), ArcStr::from("🙀")] {
+    ///     buf.clear();
+    ///     let encoded = s.encode_utf16_to_buf(&mut buf);
+    ///     assert_eq!(encoded.len(), 2);
+    /// }
+    /// let mut buf = vec![0x20];
+    /// assert_eq!(ArcStr::from("a").encode_utf16_to_buf(&mut buf), [0x61]);
+    /// assert_eq!(buf, [0x20, 0x61]);
+    /// ```
+    #[inline]
+    pub fn encode_utf16_to_buf<'a>(&self, buf: &'a mut Vec<u16>) -> &'a [u16] {
+        encode_utf16_to_buf(self, buf)
+    }
+
+    /// Returns the `char`s of this string as UTF-32 code points, in a new
+    /// `Vec<u32>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("hi 🙀");
+    /// assert_eq!(s.encode_utf32_to_vec(), [0x68, 0x69, 0x20, 0x1f640]);
+    /// ```
+    #[inline]
+    pub fn encode_utf32_to_vec(&self) -> Vec<u32> {
+        self.chars().map(u32::from).collect()
+    }
+
     /// Convert us to a `std::string::String`.
     ///
     /// This is provided as an inherent method to avoid needing to route through
     /// the `Display` machinery, but is equivalent to `ToString::to_string`.
     ///
+    /// This always copies the data, even if `self` is the only reference to
+    /// it. For the same reasons `From<String>` can't reuse the `String`'s
+    /// buffer, a `String` can't take over ours: the data isn't at the start of
+    /// the allocation (the header is), and the allocation has a different
+    /// layout than a `String`'s would.
+    ///
     /// # Examples
     ///
     /// ```
@@ -388,6 +538,77 @@ impl ArcStr {
         }
     }
 
+    /// Returns the range of addresses our data occupies, as with
+    /// [`slice::as_ptr_range`].
+    ///
+    /// This is useful for checking whether a `&str` (say, one produced by a
+    /// parser from our contents) points into us. [`ArcStr::contains_ptr`] and
+    /// [`ArcStr::ptr_offset`] do the common cases of this, and
+    /// [`ArcStr::try_substr_from`] turns such a `&str` into a [`Substr`].
+    ///
+    /// The pointers are only valid while this `ArcStr` (or a clone of it) is
+    /// alive. Comparing them doesn't require that, though.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("abc");
+    /// let r = s.ptr_range();
+    /// assert_eq!(r.start, s.as_ptr());
+    /// assert_eq!(r.end as usize - r.start as usize, 3);
+    /// ```
+    #[inline]
+    pub fn ptr_range(&self) -> core::ops::Range<*const u8> {
+        self.as_bytes().as_ptr_range()
+    }
+
+    /// Returns true if `ptr` points to one of our bytes.
+    ///
+    /// This only compares addresses, so it's safe to call with any pointer
+    /// (including a dangling one). Note that a pointer just past our end, like
+    /// the one from an empty `&str` at the end of our data, isn't considered
+    /// to be inside us (so an empty `ArcStr` contains no pointers).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("hello world");
+    /// let word = &s[6..];
+    /// assert!(s.contains_ptr(word.as_ptr()));
+    /// assert!(!s.contains_ptr(s[11..].as_ptr()));
+    /// assert!(!s.contains_ptr("world".as_ptr()));
+    /// ```
+    #[inline]
+    pub fn contains_ptr(&self, ptr: *const u8) -> bool {
+        self.ptr_range().contains(&ptr)
+    }
+
+    /// Returns the offset of `ptr` from the start of our data, if it points to
+    /// one of our bytes (see [`ArcStr::contains_ptr`]), or `None` otherwise.
+    ///
+    /// The offset isn't necessarily on a char boundary, since `ptr` can be
+    /// anything. As with `contains_ptr`, this only compares addresses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("key = value");
+    /// let value = s.split(" = ").nth(1).unwrap();
+    /// assert_eq!(s.ptr_offset(value.as_ptr()), Some(6));
+    /// assert_eq!(s.ptr_offset("value".as_ptr()), None);
+    /// ```
+    #[inline]
+    pub fn ptr_offset(&self, ptr: *const u8) -> Option<usize> {
+        if self.contains_ptr(ptr) {
+            Some(ptr as usize - self.as_ptr() as usize)
+        } else {
+            None
+        }
+    }
+
     /// Return the raw pointer this `ArcStr` wraps, for advanced use cases.
     ///
     /// Note that in addition to the `NonNull` constraint expressed in the type
@@ -466,6 +687,32 @@ impl ArcStr {
         core::ptr::eq(lhs.0.as_ptr(), rhs.0.as_ptr())
     }
 
+    /// Returns true if `needle` occurs somewhere in `haystack`.
+    ///
+    /// This is the same as `haystack.contains(needle.as_str())`, except that
+    /// if the two share an allocation (see [`ArcStr::ptr_eq`]) we know the
+    /// answer without looking at the string data at all. That's common when
+    /// strings are cloned around rather than recreated, for example, when
+    /// they've been deduplicated.
+    ///
+    /// As with [`str::contains`], an empty `needle` is contained in every
+    /// `haystack`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let text = ArcStr::from("needle in a haystack");
+    /// assert!(ArcStr::contains_arcstr(&text, &text.clone()));
+    /// assert!(ArcStr::contains_arcstr(&text, &ArcStr::from("in a")));
+    /// assert!(!ArcStr::contains_arcstr(&text, &ArcStr::from("pin")));
+    /// assert!(ArcStr::contains_arcstr(&text, &ArcStr::new()));
+    /// ```
+    #[inline]
+    pub fn contains_arcstr(haystack: &Self, needle: &Self) -> bool {
+        Self::ptr_eq(haystack, needle) || haystack.as_str().contains(needle.as_str())
+    }
+
     /// Returns the number of references that exist to this `ArcStr`. If this is
     /// a static `ArcStr` (For example, one from
     /// [`arcstr::literal!`][crate::literal]), returns `None`.
@@ -510,7 +757,12 @@ impl ArcStr {
     /// ```
     #[inline]
     pub fn strong_count(this: &Self) -> Option<usize> {
-        let cf = Self::load_count_flag(this, Ordering::Acquire)?;
+        // `Relaxed` is fine: the result is only a snapshot which may be stale
+        // by the time it's returned (as documented above), and nothing is read
+        // or written based on it, so there's nothing for it to synchronize
+        // with. Code which needs the count to be exact (like `try_reuse`) uses
+        // `is_unique` instead.
+        let cf = Self::load_count_flag(this, Ordering::Relaxed)?;
         if cf.flag_part() {
             None
         } else {
@@ -537,6 +789,129 @@ impl ArcStr {
         }
     }
 
+    /// Returns an `ArcStr` containing `new_content`, reusing the allocation of
+    /// `this` if possible.
+    ///
+    /// If `this` is the only reference to its (non-static) string data, and
+    /// `new_content` has the same length as `this`, then the bytes are
+    /// overwritten in place and `this` is returned. Otherwise, this is
+    /// equivalent to `ArcStr::from(new_content)`.
+    ///
+    /// This is useful when repeatedly producing strings of the same length
+    /// (for example, fixed-width formatting of numbers or timestamps), where
+    /// the previous result is no longer shared with anyone else.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("12:00");
+    /// let p = s.as_ptr();
+    /// // We're the only owner, so the allocation is reused.
+    /// let s = ArcStr::try_reuse(s, "12:01");
+    /// assert_eq!(s, "12:01");
+    /// assert_eq!(s.as_ptr(), p);
+    ///
+    /// // Not unique, so `s` is left alone and we get a new string.
+    /// let t = ArcStr::try_reuse(s.clone(), "12:02");
+    /// assert_eq!((s.as_str(), t.as_str()), ("12:01", "12:02"));
+    ///
+    /// // The length differs, so we get a new string.
+    /// let u = ArcStr::try_reuse(t, "noon");
+    /// assert_eq!(u, "noon");
+    /// ```
+    pub fn try_reuse(this: Self, new_content: &str) -> Self {
+        // See `is_unique` for why this is enough to ensure nobody else can be
+        // reading the data. Static strings (including ones which became static
+        // via `leak`) are never unique.
+        if this.len() != new_content.len() || !Self::is_unique(&this) {
+            return Self::from(new_content);
+        }
+        unsafe {
+            let data = this.0.as_ptr().cast::<u8>().add(OFFSET_DATA);
+            // `new_content` can't point into our data (that would require
+            // another reference), but `copy` is just as cheap as
+            // `copy_nonoverlapping` here, so don't rely on that.
+            core::ptr::copy(new_content.as_ptr(), data, new_content.len());
+        }
+        this
+    }
+
+    /// Returns true if `this` is the only reference (strong or weak) to its
+    /// non-static data, in which case nobody else can observe it.
+    ///
+    /// This follows what `Arc::is_unique` does in the stdlib: we "lock" the
+    /// weak count by swapping it from 1 (the implicit weak reference held by
+    /// the strong ones) to `usize::MAX`, so that no `WeakArcStr` can exist or
+    /// be created, and only then check the strong count. Checking the two
+    /// separately would race with a `WeakArcStr` being upgraded and then
+    /// dropped in between.
+    fn is_unique(this: &Self) -> bool {
+        if Self::has_static_lenflag(this) {
+            return false;
+        }
+        let weak = unsafe { &*core::ptr::addr_of!((*this.0.as_ptr()).weak_count) };
+        if weak
+            .compare_exchange(1, WEAK_LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        // The `Acquire` here synchronizes with the `Release` decrement in
+        // `drop` for every other strong reference.
+        let count = unsafe { Self::load_count_flag_raw(this, Ordering::Acquire) };
+        weak.store(1, Ordering::Release);
+        count == PackedFlagUint::FALSE_ONE
+    }
+
+    /// Creates a [`WeakArcStr`] pointing to the same string as `this`.
+    ///
+    /// The `WeakArcStr` doesn't keep the string alive, but can be upgraded
+    /// back into an `ArcStr` with [`WeakArcStr::upgrade`] for as long as some
+    /// other `ArcStr` does.
+    ///
+    /// Static `ArcStr`s (for example, ones from
+    /// [`arcstr::literal!`][crate::literal]) are never deallocated, so a
+    /// `WeakArcStr` to one of them can always be upgraded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("foobar");
+    /// let weak = ArcStr::downgrade(&s);
+    /// assert_eq!(weak.upgrade().unwrap(), "foobar");
+    /// drop(s);
+    /// assert!(weak.upgrade().is_none());
+    ///
+    /// let lit = ArcStr::downgrade(&arcstr::literal!("static"));
+    /// assert_eq!(lit.upgrade().unwrap(), "static");
+    /// ```
+    pub fn downgrade(this: &Self) -> WeakArcStr {
+        if !Self::has_static_lenflag(this) {
+            let weak = unsafe { &*core::ptr::addr_of!((*this.0.as_ptr()).weak_count) };
+            let mut cur = weak.load(Ordering::Relaxed);
+            loop {
+                // Locked by `is_unique` on another thread. This can only last
+                // a moment, since we hold a strong ref, so it won't succeed.
+                if cur == WEAK_LOCKED {
+                    spin_loop();
+                    cur = weak.load(Ordering::Relaxed);
+                    continue;
+                }
+                if cur > WEAK_MAX {
+                    weak_overflow();
+                }
+                match weak.compare_exchange_weak(cur, cur + 1, Ordering::Acquire, Ordering::Relaxed)
+                {
+                    Ok(_) => break,
+                    Err(old) => cur = old,
+                }
+            }
+        }
+        WeakArcStr(this.0)
+    }
+
     /// Convert the `ArcStr` into a "static" `ArcStr`, even if it was originally
     /// created from runtime values. The `&'static str` is returned.
     ///
@@ -576,7 +951,7 @@ impl ArcStr {
         if is_static_count.flag_part() {
             return unsafe { Self::to_static_unchecked(self) };
         }
-        unsafe { Self::become_static(self, is_static_count.uint_part() == 1) };
+        unsafe { Self::become_static(self, Self::is_unique(self)) };
         debug_assert!(Self::is_static(self));
         unsafe { Self::to_static_unchecked(self) }
     }
@@ -716,6 +1091,47 @@ impl ArcStr {
         Self(NonNull::new_unchecked(ptr as *const _ as *mut ThinInner))
     }
 
+    /// `feature = "verify"` Checks that the internal state of `this` is
+    /// consistent, and panics with a description of the problem if it isn't.
+    ///
+    /// This should never panic, unless something (such as buggy `unsafe` code)
+    /// has corrupted the string. It's intended as a debugging aid, and it isn't
+    /// cheap: among other things, it validates that the data is UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// ArcStr::verify_invariants(&ArcStr::from("abc"));
+    /// ArcStr::verify_invariants(&arcstr::literal!("abc"));
+    /// ```
+    #[cfg(feature = "verify")]
+    pub fn verify_invariants(this: &Self) {
+        let len_flag = this.get_inner_len_flag();
+        assert!(
+            len_flag.uint_part() <= isize::MAX as usize,
+            "ArcStr invariant violated: length {} is larger than isize::MAX",
+            len_flag.uint_part(),
+        );
+        // Static strings may live in read-only memory, and nothing about their
+        // counts can change anyway, so only check dynamic ones.
+        if !len_flag.flag_part() {
+            let count_flag = unsafe { Self::load_count_flag_raw(this, Ordering::Acquire) };
+            assert!(
+                count_flag.flag_part() || count_flag.uint_part() != 0,
+                "ArcStr invariant violated: a dynamic ArcStr has a strong count of zero",
+            );
+            let weak = unsafe { (*this.0.as_ptr()).weak_count.load(Ordering::Acquire) };
+            assert!(
+                weak != 0,
+                "ArcStr invariant violated: the weak count of a live ArcStr is zero",
+            );
+        }
+        if let Err(e) = core::str::from_utf8(this.as_bytes()) {
+            panic!("ArcStr invariant violated: data is not UTF-8 ({})", e);
+        }
+    }
+
     /// `feature = "substr"` Returns a substr of `self` over the given range.
     ///
     /// # Examples
@@ -784,7 +1200,7 @@ impl ArcStr {
         let substr_start = substr.as_ptr() as usize;
         let substr_end = substr_start + substr.len();
         if substr_start < self_start || substr_end > self_end {
-            out_of_range(self, &substr);
+            out_of_range(self, substr);
         }
 
         let index = substr_start - self_start;
@@ -840,7 +1256,11 @@ impl ArcStr {
 
         let index = substr_start - self_start;
         let end = index + substr.len();
-        debug_assert!(self.get(index..end).is_some());
+        // A `&str` should always start and end on char boundaries, but one
+        // which was built with unsafe code might not.
+        if !self.is_char_boundary(index) || !self.is_char_boundary(end) {
+            return None;
+        }
         Some(self.substr(index..end))
     }
 
@@ -898,6 +1318,153 @@ impl ArcStr {
         self.substr_from(f(self.as_str()))
     }
 
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// [`Substr`]s of `self`, where each part includes the separator that ends
+    /// it.
+    ///
+    /// This behaves the same as [`str::split_inclusive`]. See
+    /// [`Substr::split_inclusive_as_substrs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("line 1\nline 2\n");
+    /// let lines: Vec<Substr> = text.split_inclusive_as_substrs("\n").collect();
+    /// assert_eq!(lines, ["line 1\n", "line 2\n"]);
+    /// ```
+    #[cfg(feature = "substr")]
+    #[inline]
+    pub fn split_inclusive_as_substrs<'a>(&self, sep: &'a str) -> crate::SubstrSplitInclusive<'a> {
+        Substr::full(self.clone()).split_inclusive_as_substrs(sep)
+    }
+
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// [`Substr`]s of `self`, splitting at most `n - 1` times.
+    ///
+    /// This behaves the same as [`str::splitn`]. See
+    /// [`Substr::splitn_as_substrs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("key=value=extra");
+    /// let parts: Vec<Substr> = text.splitn_as_substrs(2, "=").collect();
+    /// assert_eq!(parts, ["key", "value=extra"]);
+    /// assert!(ArcStr::ptr_eq(parts[1].parent(), &text));
+    /// ```
+    #[cfg(feature = "substr")]
+    #[inline]
+    pub fn splitn_as_substrs<'a>(&self, n: usize, sep: &'a str) -> crate::SubstrSplitN<'a> {
+        Substr::full(self.clone()).splitn_as_substrs(n, sep)
+    }
+
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// [`Substr`]s of `self`, starting from the end of the string and
+    /// splitting at most `n - 1` times.
+    ///
+    /// This behaves the same as [`str::rsplitn`]. See
+    /// [`Substr::rsplitn_as_substrs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("a.b.c");
+    /// let parts: Vec<Substr> = text.rsplitn_as_substrs(2, ".").collect();
+    /// assert_eq!(parts, ["c", "a.b"]);
+    /// ```
+    #[cfg(feature = "substr")]
+    #[inline]
+    pub fn rsplitn_as_substrs<'a>(&self, n: usize, sep: &'a str) -> crate::SubstrRSplitN<'a> {
+        Substr::full(self.clone()).rsplitn_as_substrs(n, sep)
+    }
+
+    /// `feature = "regex"` Returns the leftmost-first match of `re` in this
+    /// string, as a [`Substr`] of `self`.
+    ///
+    /// No string data is copied: the result shares `self` as its parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arcstr::ArcStr;
+    /// let re = regex::Regex::new("[0-9]+").unwrap();
+    /// let text = ArcStr::from("abc 123 456");
+    /// let m = text.find_regex(&re).unwrap();
+    /// assert_eq!(m, "123");
+    /// assert_eq!(m.range(), 4..7);
+    /// assert!(ArcStr::ptr_eq(m.parent(), &text));
+    /// assert_eq!(ArcStr::from("abc").find_regex(&re), None);
+    /// ```
+    #[cfg(all(feature = "regex", feature = "substr"))]
+    pub fn find_regex(&self, re: &regex::Regex) -> Option<Substr> {
+        re.find(self).map(|m| self.substr(m.range()))
+    }
+
+    /// `feature = "regex"` Returns an iterator over all successive
+    /// non-overlapping matches of `re` in this string, as [`Substr`]s of
+    /// `self`.
+    ///
+    /// This uses the same semantics as [`regex::Regex::find_iter`] (in
+    /// particular with regard to empty matches).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arcstr::{ArcStr, Substr};
+    /// let re = regex::Regex::new("[0-9]+").unwrap();
+    /// let text = ArcStr::from("1, 22, 333");
+    /// let nums: Vec<Substr> = text.find_all_regex(&re).collect();
+    /// assert_eq!(nums, ["1", "22", "333"]);
+    /// assert_eq!(nums[1].range(), 3..5);
+    /// assert!(nums.iter().all(|n| ArcStr::ptr_eq(n.parent(), &text)));
+    /// ```
+    #[cfg(all(feature = "regex", feature = "substr"))]
+    pub fn find_all_regex<'a, 'r: 'a>(
+        &'a self,
+        re: &'r regex::Regex,
+    ) -> impl Iterator<Item = Substr> + 'a {
+        re.find_iter(self).map(move |m| self.substr(m.range()))
+    }
+
+    /// `feature = "regex"` Returns the capture groups of the leftmost-first
+    /// match of `re` in this string, as [`Substr`]s of `self`.
+    ///
+    /// The result has one entry per capture group in `re` (including the
+    /// implicit group 0 for the whole match), which is `None` if that group
+    /// did not participate in the match. Returns `None` if there's no match at
+    /// all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arcstr::ArcStr;
+    /// let re = regex::Regex::new("([a-z]+)=([0-9]+)?").unwrap();
+    /// let text = ArcStr::from("x: key=42");
+    /// let caps = text.captures_as_substrs(&re).unwrap();
+    /// assert_eq!(caps.len(), 3);
+    /// assert_eq!(caps[0].as_deref(), Some("key=42"));
+    /// assert_eq!(caps[1].as_ref().unwrap().range(), 3..6);
+    /// assert_eq!(caps[2].as_deref(), Some("42"));
+    ///
+    /// let caps = ArcStr::from("key=").captures_as_substrs(&re).unwrap();
+    /// assert_eq!(caps[2], None);
+    /// ```
+    #[cfg(all(feature = "regex", feature = "substr"))]
+    pub fn captures_as_substrs(
+        &self,
+        re: &regex::Regex,
+    ) -> Option<alloc::vec::Vec<Option<Substr>>> {
+        let caps = re.captures(self)?;
+        Some(
+            caps.iter()
+                .map(|m| m.map(|m| self.substr(m.range())))
+                .collect(),
+        )
+    }
+
     /// Creates an `ArcStr` by repeating the source string `n` times
     ///
     /// # Errors
@@ -968,29 +1535,379 @@ impl ArcStr {
     pub fn repeat(source: &str, n: usize) -> Self {
         Self::try_repeat(source, n).expect("capacity overflow")
     }
-}
 
-#[cold]
-#[inline(never)]
-#[cfg(feature = "substr")]
-fn out_of_range(arc: &ArcStr, substr: &&str) -> ! {
-    let arc_start = arc.as_ptr();
-    let arc_end = arc_start.wrapping_add(arc.len());
-    let substr_start = substr.as_ptr();
-    let substr_end = substr_start.wrapping_add(substr.len());
-    panic!(
-        "ArcStr over ({:p}..{:p}) does not contain substr over ({:p}..{:p})",
-        arc_start, arc_end, substr_start, substr_end,
-    );
-}
+    /// Returns a copy of this string where each character is mapped to its
+    /// ASCII upper case equivalent.
+    ///
+    /// ASCII letters 'a' to 'z' are mapped to 'A' to 'Z', but non-ASCII
+    /// characters are unchanged. This is the same as
+    /// [`str::to_ascii_uppercase`], but produces an `ArcStr` directly, rather
+    /// than going through a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("Grüße, Jürgen ❤");
+    /// assert_eq!(s.to_ascii_uppercase(), "GRüßE, JüRGEN ❤");
+    /// ```
+    #[inline]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        ascii_case_mapped(self, <[u8]>::make_ascii_uppercase)
+    }
 
-impl Clone for ArcStr {
+    /// Returns a copy of this string where each character is mapped to its
+    /// ASCII lower case equivalent.
+    ///
+    /// ASCII letters 'A' to 'Z' are mapped to 'a' to 'z', but non-ASCII
+    /// characters are unchanged. This is the same as
+    /// [`str::to_ascii_lowercase`], but produces an `ArcStr` directly, rather
+    /// than going through a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("Grüße, Jürgen ❤");
+    /// assert_eq!(s.to_ascii_lowercase(), "grüße, jürgen ❤");
+    /// ```
     #[inline]
-    fn clone(&self) -> Self {
-        if !Self::is_static(self) {
-            // From libstd's impl:
-            //
-            // > Using a relaxed ordering is alright here, as knowledge of the
+    pub fn to_ascii_lowercase(&self) -> Self {
+        ascii_case_mapped(self, <[u8]>::make_ascii_lowercase)
+    }
+
+    /// Returns a copy of this string where the first ASCII letter is mapped to
+    /// upper case, and every ASCII letter after it is mapped to lower case.
+    ///
+    /// Anything before the first ASCII letter (such as digits, punctuation or
+    /// non-ASCII characters) is left alone, as are non-ASCII characters
+    /// everywhere. This is meant for simple cases like capitalizing a label
+    /// for display, and doesn't attempt Unicode title casing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::from("hELLO wORLD").to_ascii_titlecase(), "Hello world");
+    /// assert_eq!(ArcStr::from("3 little PIGS").to_ascii_titlecase(), "3 Little pigs");
+    /// assert_eq!(ArcStr::from("élan VITAL").to_ascii_titlecase(), "éLan vital");
+    /// ```
+    #[inline]
+    pub fn to_ascii_titlecase(&self) -> Self {
+        ascii_case_mapped(self, make_ascii_titlecase)
+    }
+
+    /// Convert a vector of bytes into an `ArcStr`, if it holds valid UTF-8.
+    ///
+    /// On failure, the returned error can give the vector back (via
+    /// [`FromUtf8Error::into_bytes`](alloc::string::FromUtf8Error::into_bytes)),
+    /// just like with [`String::from_utf8`]. Unlike [`TryFrom<Vec<u8>>`], this
+    /// never loses the input.
+    ///
+    /// Note that the bytes are always copied into a new allocation, even if
+    /// `v.len() == v.capacity()`. See the comment on `From<String>` for why
+    /// the vector's buffer can't be reused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from_utf8_owned(b"abc".to_vec()).unwrap();
+    /// assert_eq!(s, "abc");
+    ///
+    /// let err = ArcStr::from_utf8_owned(b"ab\xff".to_vec()).unwrap_err();
+    /// assert_eq!(err.utf8_error().valid_up_to(), 2);
+    /// assert_eq!(err.into_bytes(), b"ab\xff");
+    /// ```
+    #[inline]
+    pub fn from_utf8_owned(v: Vec<u8>) -> Result<Self, alloc::string::FromUtf8Error> {
+        String::from_utf8(v).map(|s| Self::from(s.as_str()))
+    }
+
+    /// Create an `ArcStr` from the [`Display`](core::fmt::Display)
+    /// representation of `value`.
+    ///
+    /// This is the same as `arcstr::format!("{}", value)` (or
+    /// `ArcStr::from(value.to_string())`), but is easier to find, and can be
+    /// passed to things like `Iterator::map` directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::from_display(&123), "123");
+    /// assert_eq!(ArcStr::from_display(&1.5f32), "1.5");
+    /// let v: Vec<ArcStr> = [1, 2, 3].iter().map(ArcStr::from_display).collect();
+    /// assert_eq!(v, ["1", "2", "3"]);
+    /// ```
+    #[inline]
+    pub fn from_display<T: core::fmt::Display + ?Sized>(value: &T) -> Self {
+        crate::format!("{}", value)
+    }
+
+    /// Create an `ArcStr` from the [`Debug`](core::fmt::Debug) representation
+    /// of `value`.
+    ///
+    /// This is the same as `arcstr::format!("{:?}", value)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::from_debug(&"hi\n"), r#""hi\n""#);
+    /// assert_eq!(ArcStr::from_debug(&Some(1)), "Some(1)");
+    /// ```
+    #[inline]
+    pub fn from_debug<T: core::fmt::Debug + ?Sized>(value: &T) -> Self {
+        crate::format!("{:?}", value)
+    }
+
+    /// Concatenate `parts`, with `sep` between each of them.
+    ///
+    /// This is like `parts.join(sep)` for slices of strings, except that it
+    /// works for a slice of anything that implements `AsRef<str>`, and builds
+    /// the result directly in a single `ArcStr` allocation.
+    ///
+    /// An empty `parts` produces an empty string, and a single part is copied
+    /// without any separator.
+    ///
+    /// See also [`arcstr::join!`](crate::join), which does this at compile
+    /// time for literals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parts = [ArcStr::from("a"), ArcStr::from("b"), ArcStr::from("c")];
+    /// assert_eq!(ArcStr::concat_with_separator(", ", &parts), "a, b, c");
+    /// assert_eq!(ArcStr::concat_with_separator("+", &["x"]), "x");
+    /// assert_eq!(ArcStr::concat_with_separator::<&str>("+", &[]), "");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length overflows a `usize`, or if the `AsRef` impl
+    /// of the parts doesn't return the same string each time it's called.
+    pub fn concat_with_separator<S: AsRef<str>>(sep: &str, parts: &[S]) -> Self {
+        if parts.is_empty() {
+            return Self::new();
+        }
+        let len = parts
+            .iter()
+            .try_fold(0usize, |n, p| n.checked_add(p.as_ref().len()))
+            .and_then(|n| sep.len().checked_mul(parts.len() - 1)?.checked_add(n))
+            .expect("capacity overflow");
+        if len == 0 {
+            return Self::new();
+        }
+        unsafe {
+            Self::init_with_unchecked(len, |buf| {
+                let mut pos = 0;
+                let mut push = |s: &str| {
+                    // Slicing checks the bounds, in case `AsRef` lied to us.
+                    let dst = &mut buf[pos..pos + s.len()];
+                    core::ptr::copy_nonoverlapping(s.as_ptr(), dst.as_mut_ptr().cast(), s.len());
+                    pos += s.len();
+                };
+                push(parts[0].as_ref());
+                for p in &parts[1..] {
+                    push(sep);
+                    push(p.as_ref());
+                }
+                // Don't hand out a partially initialized string.
+                assert_eq!(pos, len, "`AsRef<str>` returned inconsistent results");
+            })
+        }
+    }
+
+    /// Concatenate the [`Display`](core::fmt::Display) representations of
+    /// each of `parts`, with `sep` between each of them.
+    ///
+    /// An empty `parts` produces an empty string, and a single part is
+    /// formatted without any separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::join_display(", ", [1, 2, 3]), "1, 2, 3");
+    /// assert_eq!(ArcStr::join_display(" ", ['a'].iter()), "a");
+    /// assert_eq!(ArcStr::join_display("-", Vec::<u8>::new()), "");
+    /// ```
+    pub fn join_display<T, I>(sep: &str, parts: I) -> Self
+    where
+        T: core::fmt::Display,
+        I: IntoIterator<Item = T>,
+    {
+        use core::fmt::Write;
+        let mut out = String::new();
+        for (i, p) in parts.into_iter().enumerate() {
+            if i != 0 {
+                out.push_str(sep);
+            }
+            write!(out, "{}", p).expect("a Display implementation returned an error unexpectedly");
+        }
+        Self::from(out)
+    }
+
+    /// Builds an `ArcStr` from `iter`, allocating room for `byte_hint` bytes up
+    /// front.
+    ///
+    /// This is for cases where you know (or have a good guess at) the final
+    /// length in bytes, which an iterator's `size_hint` usually can't tell you.
+    /// The contents are written directly into the `ArcStr`'s allocation:
+    ///
+    /// - If `byte_hint` is exactly right, this makes a single allocation, and
+    ///   nothing is copied afterwards.
+    /// - If it's too small, the allocation grows by doubling (like a `Vec`'s
+    ///   does), so it's never more than twice the size actually needed.
+    /// - If it's too large (or growing overshot), the allocation is shrunk to
+    ///   fit at the end. If it's so large that it can't be allocated at all,
+    ///   it's ignored, and we start growing from nothing instead.
+    ///
+    /// In all cases, the result holds exactly the characters from `iter`. A
+    /// `byte_hint` of zero is fine, and just means we start growing right away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let src = "a-b-c";
+    /// // Replacing each `-` with `+` doesn't change the length.
+    /// let s = ArcStr::from_chars_iter_with_hint(
+    ///     src.chars().map(|c| if c == '-' { '+' } else { c }),
+    ///     src.len(),
+    /// );
+    /// assert_eq!(s, "a+b+c");
+    /// // Hints which are wrong only cost performance.
+    /// assert_eq!(ArcStr::from_chars_iter_with_hint("héllo".chars(), 1), "héllo");
+    /// assert_eq!(ArcStr::from_chars_iter_with_hint("héllo".chars(), 100), "héllo");
+    /// assert_eq!(ArcStr::from_chars_iter_with_hint("".chars(), 100), "");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the contents overflows, or if allocation fails
+    /// while growing. An oversized `byte_hint` alone never causes either.
+    pub fn from_chars_iter_with_hint(iter: impl Iterator<Item = char>, byte_hint: usize) -> Self {
+        let mut buf = CharsBuf::new();
+        if byte_hint != 0 {
+            // If we can't get that much up front, the hint is presumably wrong,
+            // so just start from nothing and grow.
+            let _ = buf.try_resize(byte_hint);
+        }
+        for c in iter {
+            buf.push(c);
+        }
+        buf.finish()
+    }
+
+    /// `feature = "std"`: Returns the value of the environment variable `key`
+    /// as an `ArcStr`.
+    ///
+    /// This is the same as `std::env::var(key).map(ArcStr::from)`, and fails
+    /// in the same cases: if the variable isn't set, or if its value isn't
+    /// valid Unicode.
+    ///
+    /// Each call reads the environment again and returns a newly allocated
+    /// string (unless the value is empty), since the variable may have changed
+    /// since the last call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// std::env::set_var("ARCSTR_DOCTEST_FROM_ENV", "hello");
+    /// assert_eq!(ArcStr::from_env("ARCSTR_DOCTEST_FROM_ENV").unwrap(), "hello");
+    /// assert_eq!(
+    ///     ArcStr::from_env("ARCSTR_DOCTEST_UNSET"),
+    ///     Err(std::env::VarError::NotPresent),
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_env(key: &str) -> Result<Self, std::env::VarError> {
+        std::env::var(key).map(Self::from)
+    }
+
+    /// `feature = "std"`: Returns the value of the environment variable `key`
+    /// as an `ArcStr`, or `default` if it isn't set or isn't valid Unicode.
+    ///
+    /// See [`ArcStr::from_env`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// std::env::set_var("ARCSTR_DOCTEST_FROM_ENV_OR", "set");
+    /// let default = arcstr::literal!("default");
+    /// assert_eq!(ArcStr::from_env_or("ARCSTR_DOCTEST_FROM_ENV_OR", default.clone()), "set");
+    /// assert_eq!(ArcStr::from_env_or("ARCSTR_DOCTEST_UNSET", default), "default");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_env_or(key: &str, default: Self) -> Self {
+        Self::from_env(key).unwrap_or(default)
+    }
+
+    /// `feature = "std"`: Write the contents of this string to `writer`.
+    ///
+    /// This is the same as `writer.write_all(self.as_bytes())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let mut out = Vec::new();
+    /// ArcStr::from("hello").write_to(&mut out).unwrap();
+    /// assert_eq!(out, b"hello");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_to<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+
+    /// Write the contents of this string to the [`core::fmt::Write`] `writer`.
+    ///
+    /// This is the same as `writer.write_str(self)`, and is available without
+    /// the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let mut out = String::from("> ");
+    /// ArcStr::from("hello").write_to_fmt(&mut out).unwrap();
+    /// assert_eq!(out, "> hello");
+    /// ```
+    #[inline]
+    pub fn write_to_fmt<W: core::fmt::Write + ?Sized>(&self, writer: &mut W) -> core::fmt::Result {
+        writer.write_str(self)
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[cfg(feature = "substr")]
+fn out_of_range(arc: &ArcStr, substr: &str) -> ! {
+    let arc_start = arc.as_ptr();
+    let arc_end = arc_start.wrapping_add(arc.len());
+    let substr_start = substr.as_ptr();
+    let substr_end = substr_start.wrapping_add(substr.len());
+    panic!(
+        "ArcStr over ({:p}..{:p}) does not contain substr over ({:p}..{:p})",
+        arc_start, arc_end, substr_start, substr_end,
+    );
+}
+
+impl Clone for ArcStr {
+    #[inline]
+    fn clone(&self) -> Self {
+        if !Self::is_static(self) {
+            // From libstd's impl:
+            //
+            // > Using a relaxed ordering is alright here, as knowledge of the
             // > original reference prevents other threads from erroneously deleting
             // > the object.
             //
@@ -1002,8 +1919,9 @@ impl Clone for ArcStr {
                     .fetch_add(step, Ordering::Relaxed)
             });
             // Protect against aggressive leaking of Arcs causing us to
-            // overflow. Technically, we could probably transition it to static
-            // here, but I haven't thought it through.
+            // overflow. Rather than aborting (which we can't do reliably
+            // without `std`), set the leaked flag, after which `clone` and
+            // `drop` no longer touch the count, and the string is never freed.
             if n.uint_part() > RC_MAX && !n.flag_part() {
                 let val = PackedFlagUint::new_raw(true, 0).encoded_value();
                 unsafe {
@@ -1011,7 +1929,6 @@ impl Clone for ArcStr {
                         .count_flag
                         .fetch_or(val, Ordering::Release)
                 };
-                // abort();
             }
         }
         Self(self.0)
@@ -1035,11 +1952,264 @@ impl Drop for ArcStr {
             // Note: `enc == PackedFlagUint::FALSE_ONE`
             if enc == PackedFlagUint::FALSE_ONE {
                 let _ = (*this).count_flag.load(Ordering::Acquire);
-                ThinInner::destroy_cold(this)
+                // Release the implicit weak reference held by the strong ones.
+                ThinInner::release_weak(this)
             }
         }
     }
 }
+
+/// A weak reference to the string data of an [`ArcStr`], created by
+/// [`ArcStr::downgrade`].
+///
+/// This doesn't keep the string alive, but can be turned back into an `ArcStr`
+/// using [`WeakArcStr::upgrade`], as long as the string hasn't been dropped.
+/// Note that while the string data is dropped along with the last `ArcStr`, the
+/// (small) allocation holding it is not freed until all `WeakArcStr`s pointing
+/// to it are dropped too.
+///
+/// This is the `ArcStr` equivalent of [`alloc::sync::Weak`].
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, WeakArcStr};
+/// let s = ArcStr::from("cached");
+/// let weak: WeakArcStr = ArcStr::downgrade(&s);
+/// assert_eq!(weak.strong_count(), Some(1));
+///
+/// let upgraded = weak.upgrade().unwrap();
+/// assert!(ArcStr::ptr_eq(&s, &upgraded));
+/// assert_eq!(weak.strong_count(), Some(2));
+///
+/// drop((s, upgraded));
+/// assert_eq!(weak.strong_count(), Some(0));
+/// assert_eq!(weak.upgrade(), None);
+/// ```
+#[repr(transparent)]
+pub struct WeakArcStr(NonNull<ThinInner>);
+
+unsafe impl Sync for WeakArcStr {}
+unsafe impl Send for WeakArcStr {}
+
+impl WeakArcStr {
+    /// Attempts to upgrade to an [`ArcStr`], returning `None` if every
+    /// `ArcStr` has already been dropped.
+    ///
+    /// This always succeeds for a `WeakArcStr` created from a static `ArcStr`
+    /// (including one which became static via [`ArcStr::leak`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("foo");
+    /// let weak = ArcStr::downgrade(&s);
+    /// assert_eq!(weak.upgrade(), Some(s.clone()));
+    /// drop(s);
+    /// assert_eq!(weak.upgrade(), None);
+    /// ```
+    pub fn upgrade(&self) -> Option<ArcStr> {
+        if self.has_static_lenflag() {
+            return Some(ArcStr(self.0));
+        }
+        let count_flag = unsafe { &*core::ptr::addr_of!((*self.0.as_ptr()).count_flag) };
+        let mut cur = count_flag.load(Ordering::Relaxed);
+        loop {
+            let n = PackedFlagUint::from_encoded(cur);
+            if n.flag_part() {
+                // Leaked, so it'll never be freed, and `clone`/`drop` won't
+                // touch the count anymore.
+                return Some(ArcStr(self.0));
+            }
+            if n.uint_part() == 0 {
+                return None;
+            }
+            let next = cur + PackedFlagUint::FALSE_ONE.encoded_value();
+            match count_flag.compare_exchange_weak(cur, next, Ordering::Acquire, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(old) => cur = old,
+            }
+        }
+        // Same overflow protection as `ArcStr::clone`.
+        if PackedFlagUint::from_encoded(cur).uint_part() > RC_MAX {
+            let val = PackedFlagUint::new_raw(true, 0).encoded_value();
+            count_flag.fetch_or(val, Ordering::Release);
+        }
+        Some(ArcStr(self.0))
+    }
+
+    /// Returns the number of [`ArcStr`]s pointing to this string, or `None` if
+    /// it is static. See [`ArcStr::strong_count`].
+    ///
+    /// Unlike with `ArcStr::strong_count`, this may return `Some(0)`, if every
+    /// `ArcStr` has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("foo");
+    /// let weak = ArcStr::downgrade(&s);
+    /// assert_eq!(weak.strong_count(), Some(1));
+    /// drop(s);
+    /// assert_eq!(weak.strong_count(), Some(0));
+    ///
+    /// let weak = ArcStr::downgrade(&arcstr::literal!("foo"));
+    /// assert_eq!(weak.strong_count(), None);
+    /// ```
+    pub fn strong_count(&self) -> Option<usize> {
+        if self.has_static_lenflag() {
+            return None;
+        }
+        // `Relaxed` for the same reason as in `ArcStr::strong_count`. (Our weak
+        // reference keeps the allocation alive, so the load itself is fine.)
+        let cf = PackedFlagUint::from_encoded(unsafe {
+            (*self.0.as_ptr()).count_flag.load(Ordering::Relaxed)
+        });
+        if cf.flag_part() {
+            None
+        } else {
+            Some(cf.uint_part())
+        }
+    }
+
+    /// Returns true if the two `WeakArcStr`s point to the same string (even if
+    /// it has already been dropped).
+    ///
+    /// This is like [`ArcStr::ptr_eq`], and has the same caveats about static
+    /// strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("foo");
+    /// let w1 = ArcStr::downgrade(&s);
+    /// let w2 = ArcStr::downgrade(&s.clone());
+    /// let w3 = ArcStr::downgrade(&ArcStr::from("foo"));
+    /// assert!(w1.ptr_eq(&w2));
+    /// assert!(!w1.ptr_eq(&w3));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.0.as_ptr(), other.0.as_ptr())
+    }
+
+    /// The contents of the string, which remain readable even once every
+    /// `ArcStr` has been dropped, since they're only freed along with the
+    /// allocation (and `try_reuse` can't write to them while we exist).
+    #[cfg(feature = "intern")]
+    pub(crate) fn contents(&self) -> &str {
+        let p = self.0.as_ptr();
+        unsafe {
+            let len = ThinInner::get_len_flag(p).uint_part();
+            let data = p.cast::<u8>().add(OFFSET_DATA);
+            core::str::from_utf8_unchecked(core::slice::from_raw_parts(data, len))
+        }
+    }
+
+    #[inline]
+    fn has_static_lenflag(&self) -> bool {
+        // The allocation (and so the length) sticks around for as long as we
+        // do, even if the string is dropped.
+        unsafe { ThinInner::get_len_flag(self.0.as_ptr()).flag_part() }
+    }
+}
+
+impl Clone for WeakArcStr {
+    #[inline]
+    fn clone(&self) -> Self {
+        if !self.has_static_lenflag() {
+            // We hold a weak reference, so the count can't be locked (or hit
+            // zero), and `Relaxed` is fine for the same reason as in
+            // `ArcStr::clone`.
+            let weak = unsafe { &*core::ptr::addr_of!((*self.0.as_ptr()).weak_count) };
+            if weak.fetch_add(1, Ordering::Relaxed) > WEAK_MAX {
+                weak.fetch_sub(1, Ordering::Relaxed);
+                weak_overflow();
+            }
+        }
+        Self(self.0)
+    }
+}
+
+impl Drop for WeakArcStr {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.has_static_lenflag() {
+            unsafe { ThinInner::release_weak(self.0.as_ptr()) }
+        }
+    }
+}
+
+impl core::fmt::Debug for WeakArcStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("(WeakArcStr)")
+    }
+}
+
+/// An iterator over the `char` boundaries of an [`ArcStr`] or [`Substr`].
+///
+/// Created by [`ArcStr::char_boundary_indices`] and
+/// [`Substr::char_boundary_indices`]. Yields byte indices, starting with `0`
+/// and ending with the length of the string.
+#[derive(Clone, Debug)]
+pub struct ArcStrCharBoundaries<'a> {
+    inner: core::str::CharIndices<'a>,
+    len: usize,
+    // Whether we've yielded `len` yet (from either end).
+    yielded_end: bool,
+}
+
+impl<'a> ArcStrCharBoundaries<'a> {
+    #[inline]
+    pub(crate) fn new(s: &'a str) -> Self {
+        Self {
+            inner: s.char_indices(),
+            len: s.len(),
+            yielded_end: false,
+        }
+    }
+}
+
+impl Iterator for ArcStrCharBoundaries<'_> {
+    type Item = usize;
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if let Some((i, _)) = self.inner.next() {
+            Some(i)
+        } else if !self.yielded_end {
+            self.yielded_end = true;
+            Some(self.len)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = (!self.yielded_end) as usize;
+        let (lo, hi) = self.inner.size_hint();
+        (lo + extra, hi.map(|h| h + extra))
+    }
+}
+
+impl DoubleEndedIterator for ArcStrCharBoundaries<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        if !self.yielded_end {
+            self.yielded_end = true;
+            Some(self.len)
+        } else {
+            self.inner.next_back().map(|(i, _)| i)
+        }
+    }
+}
+
+impl core::iter::FusedIterator for ArcStrCharBoundaries<'_> {}
+
 // Caveat on the `static`/`strong` fields: "is_static" indicates if we're
 // located in static data (as with empty string). is_static being false meanse
 // we are a normal arc-ed string.
@@ -1079,12 +2249,23 @@ struct ThinInner {
     // length wouldn't be acceptable even if compilers were really good.
     len_flag: PackedFlagUint,
     count_flag: AtomicUsize,
+    // The number of `WeakArcStr`s, plus one for as long as any strong
+    // reference exists (as with `Arc` in the stdlib). The allocation is freed
+    // when this reaches zero. Never accessed for static strings.
+    weak_count: AtomicUsize,
     data: [u8; 0],
 }
 
 const OFFSET_LENFLAGS: usize = 0;
 const OFFSET_COUNTFLAGS: usize = size_of::<PackedFlagUint>();
-const OFFSET_DATA: usize = OFFSET_COUNTFLAGS + size_of::<AtomicUsize>();
+const OFFSET_WEAKCOUNT: usize = OFFSET_COUNTFLAGS + size_of::<AtomicUsize>();
+const OFFSET_DATA: usize = OFFSET_WEAKCOUNT + size_of::<AtomicUsize>();
+
+/// Value of `weak_count` while `ArcStr::is_unique` is checking the strong count.
+const WEAK_LOCKED: usize = usize::MAX;
+/// We refuse to create more weak references than this, so that we never
+/// overflow into `WEAK_LOCKED` (or wrap around).
+const WEAK_MAX: usize = isize::MAX as usize;
 
 // Not public API, exists for macros.
 #[repr(C, align(8))]
@@ -1092,6 +2273,7 @@ const OFFSET_DATA: usize = OFFSET_COUNTFLAGS + size_of::<AtomicUsize>();
 pub struct StaticArcStrInner<Buf> {
     pub len_flag: usize,
     pub count_flag: usize,
+    pub weak_count: usize,
     pub data: Buf,
 }
 
@@ -1108,14 +2290,29 @@ impl<Buf> StaticArcStrInner<Buf> {
     }
 }
 
-const _: [(); size_of::<StaticArcStrInner<[u8; 0]>>()] = [(); 2 * size_of::<usize>()];
+// `ArcStr` being exactly one (non-null) pointer is part of its public
+// contract (see the type docs, and `into_raw`/`from_raw`), so in particular it
+// has no room to store short strings inline.
+const _: [(); size_of::<ArcStr>()] = [(); size_of::<usize>()];
+const _: [(); size_of::<Option<ArcStr>>()] = [(); size_of::<usize>()];
+
+// Rounds up to our alignment of 8, which matters on 32-bit targets.
+//
+// Only used in array lengths, which older compilers (including our MSRV) don't
+// count as a use.
+#[allow(dead_code)]
+const fn round_up_8(n: usize) -> usize {
+    (n + 7) & !7
+}
+
+const _: [(); size_of::<StaticArcStrInner<[u8; 0]>>()] = [(); round_up_8(3 * size_of::<usize>())];
 const _: [(); align_of::<StaticArcStrInner<[u8; 0]>>()] = [(); 8];
 
 const _: [(); size_of::<StaticArcStrInner<[u8; 2 * size_of::<usize>()]>>()] =
-    [(); 4 * size_of::<usize>()];
+    [(); round_up_8(5 * size_of::<usize>())];
 const _: [(); align_of::<StaticArcStrInner<[u8; 2 * size_of::<usize>()]>>()] = [(); 8];
 
-const _: [(); size_of::<ThinInner>()] = [(); 2 * size_of::<usize>()];
+const _: [(); size_of::<ThinInner>()] = [(); round_up_8(3 * size_of::<usize>())];
 const _: [(); align_of::<ThinInner>()] = [(); 8];
 
 const _: [(); align_of::<AtomicUsize>()] = [(); align_of::<usize>()];
@@ -1127,7 +2324,7 @@ const _: [(); size_of::<PackedFlagUint>()] = [(); size_of::<usize>()];
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(transparent)]
-struct PackedFlagUint(usize);
+pub(crate) struct PackedFlagUint(usize);
 impl PackedFlagUint {
     const UINT_PART_MAX: usize = (1 << (usize::BITS - 1)) - 1;
     /// Encodes `false` as the flag and `1` as the uint. Used for a few things,
@@ -1135,7 +2332,7 @@ impl PackedFlagUint {
     const FALSE_ONE: Self = Self::new_raw(false, 1);
 
     #[inline]
-    const fn new(flag_part: bool, uint_part: usize) -> Option<Self> {
+    pub(crate) const fn new(flag_part: bool, uint_part: usize) -> Option<Self> {
         if uint_part > Self::UINT_PART_MAX {
             None
         } else {
@@ -1144,17 +2341,17 @@ impl PackedFlagUint {
     }
 
     #[inline(always)]
-    const fn new_raw(flag_part: bool, uint_part: usize) -> Self {
+    pub(crate) const fn new_raw(flag_part: bool, uint_part: usize) -> Self {
         Self(flag_part as usize | (uint_part << 1))
     }
 
     #[inline(always)]
-    const fn uint_part(self) -> usize {
+    pub(crate) const fn uint_part(self) -> usize {
         self.0 >> 1
     }
 
     #[inline(always)]
-    const fn flag_part(self) -> bool {
+    pub(crate) const fn flag_part(self) -> bool {
         (self.0 & 1) != 0
     }
 
@@ -1164,7 +2361,7 @@ impl PackedFlagUint {
     }
 
     #[inline(always)]
-    const fn encoded_value(self) -> usize {
+    pub(crate) const fn encoded_value(self) -> usize {
         self.0
     }
 
@@ -1212,8 +2409,8 @@ impl ThinInner {
         debug_assert!(Layout::from_size_align(capacity + OFFSET_DATA, ALIGN).is_ok());
         let layout = unsafe { Layout::from_size_align_unchecked(capacity + OFFSET_DATA, ALIGN) };
         let ptr = match init_how {
-            AllocInit::Uninit => unsafe { alloc::alloc::alloc(layout) as *mut ThinInner },
-            AllocInit::Zero => unsafe { alloc::alloc::alloc_zeroed(layout) as *mut ThinInner },
+            AllocInit::Uninit => unsafe { heap::alloc(layout) as *mut ThinInner },
+            AllocInit::Zero => unsafe { heap::alloc_zeroed(layout) as *mut ThinInner },
         };
         if ptr.is_null() {
             return Err(Some(layout));
@@ -1232,6 +2429,7 @@ impl ThinInner {
             let initial_count_flag = PackedFlagUint::new_raw(initially_static, 1);
             let count_flag: AtomicUsize = AtomicUsize::new(initial_count_flag.encoded_value());
             core::ptr::addr_of_mut!((*ptr).count_flag).write(count_flag);
+            core::ptr::addr_of_mut!((*ptr).weak_count).write(AtomicUsize::new(1));
 
             debug_assert_eq!(
                 (ptr as *const u8).wrapping_add(OFFSET_DATA),
@@ -1288,22 +2486,150 @@ impl ThinInner {
     }
 
     #[inline]
-    unsafe fn get_len_flag(p: *const ThinInner) -> PackedFlagUint {
-        debug_assert_eq!(OFFSET_LENFLAGS, 0);
-        *p.cast()
+    unsafe fn get_len_flag(p: *const ThinInner) -> PackedFlagUint {
+        debug_assert_eq!(OFFSET_LENFLAGS, 0);
+        *p.cast()
+    }
+
+    /// Safety: `p` must be a non-static `ThinInner`, and the caller must own
+    /// one of the references counted in `weak_count`.
+    #[inline]
+    unsafe fn release_weak(p: *mut ThinInner) {
+        if (*p).weak_count.fetch_sub(1, Ordering::Release) == 1 {
+            let _ = (*p).weak_count.load(Ordering::Acquire);
+            Self::destroy_cold(p)
+        }
+    }
+
+    #[cold]
+    unsafe fn destroy_cold(p: *mut ThinInner) {
+        let lf = Self::get_len_flag(p);
+        let (is_static, len) = (lf.flag_part(), lf.uint_part());
+        debug_assert!(!is_static);
+        let layout = {
+            let size = len + OFFSET_DATA;
+            let align = align_of::<ThinInner>();
+            Layout::from_size_align_unchecked(size, align)
+        };
+        heap::dealloc(p as *mut _, layout);
+    }
+}
+
+/// A partially-written `ThinInner` with room for `cap` bytes, of which the
+/// first `len` are initialized. Used by [`ArcStr::from_chars_iter_with_hint`].
+///
+/// The header's `len_flag` holds `cap` until we're finished, so that the
+/// allocation's layout can be recovered from it as usual. The buffer is freed
+/// if we're dropped before then (say, if the iterator panics).
+struct CharsBuf {
+    ptr: Option<NonNull<ThinInner>>,
+    cap: usize,
+    len: usize,
+}
+
+impl CharsBuf {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            ptr: None,
+            cap: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn layout(cap: usize) -> Layout {
+        // Safety: `try_allocate_maybe_uninit` checked this for a `cap` at least
+        // this large, and we never grow without checking it again.
+        unsafe { Layout::from_size_align_unchecked(cap + OFFSET_DATA, align_of::<ThinInner>()) }
+    }
+
+    /// Changes our capacity to `new_cap`, which must be at least `self.len`
+    /// and nonzero.
+    fn resize(&mut self, new_cap: usize) {
+        match self.try_resize(new_cap) {
+            Ok(()) => {}
+            Err(None) => alloc_overflow(),
+            Err(Some(layout)) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// Same as `resize`, but returns the error from `try_allocate_maybe_uninit`
+    /// on failure, in which case we're unchanged.
+    fn try_resize(&mut self, new_cap: usize) -> Result<(), Option<Layout>> {
+        debug_assert!(new_cap >= self.len && new_cap != 0);
+        let ptr = match self.ptr {
+            None => ThinInner::try_allocate_maybe_uninit(new_cap, false, AllocInit::Uninit)?,
+            Some(old) => {
+                if new_cap >= (isize::MAX as usize) - (OFFSET_DATA + align_of::<ThinInner>()) {
+                    return Err(None);
+                }
+                // Safety: `old` was allocated with `Self::layout(self.cap)`, and
+                // `new_cap` passed the same overflow check as in
+                // `try_allocate_maybe_uninit`. If this fails, `old` is left
+                // alone.
+                let p = unsafe {
+                    heap::realloc(
+                        old.as_ptr().cast(),
+                        Self::layout(self.cap),
+                        Self::layout(new_cap).size(),
+                    )
+                };
+                NonNull::new(p.cast::<ThinInner>()).ok_or(Some(Self::layout(new_cap)))?
+            }
+        };
+        // Safety: `ptr` is an allocation we own, with a header.
+        unsafe {
+            core::ptr::addr_of_mut!((*ptr.as_ptr()).len_flag)
+                .write(PackedFlagUint::new_raw(false, new_cap));
+        }
+        self.ptr = Some(ptr);
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    #[inline]
+    fn push(&mut self, c: char) {
+        let mut tmp = [0u8; 4];
+        let bytes = c.encode_utf8(&mut tmp).as_bytes();
+        let needed = self
+            .len
+            .checked_add(bytes.len())
+            .unwrap_or_else(|| alloc_overflow());
+        if needed > self.cap {
+            self.resize(needed.max(self.cap.saturating_mul(2)));
+        }
+        if let Some(ptr) = self.ptr {
+            // Safety: we just made sure there's room for `bytes` after `len`.
+            unsafe {
+                let dst = ThinInner::data_ptr(ptr).add(self.len);
+                core::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+            }
+        }
+        self.len = needed;
     }
 
-    #[cold]
-    unsafe fn destroy_cold(p: *mut ThinInner) {
-        let lf = Self::get_len_flag(p);
-        let (is_static, len) = (lf.flag_part(), lf.uint_part());
-        debug_assert!(!is_static);
-        let layout = {
-            let size = len + OFFSET_DATA;
-            let align = align_of::<ThinInner>();
-            Layout::from_size_align_unchecked(size, align)
-        };
-        alloc::alloc::dealloc(p as *mut _, layout);
+    fn finish(mut self) -> ArcStr {
+        if self.len == 0 {
+            return ArcStr::new();
+        }
+        if self.len != self.cap {
+            self.resize(self.len);
+        }
+        // Safety: `resize` ran at least once (since `len != 0`), so we have an
+        // allocation, and it's exactly `len` bytes of UTF-8 with the right
+        // header. Taking it leaves nothing for our `Drop` to free.
+        let ptr = self.ptr.take().unwrap();
+        ArcStr(ptr)
+    }
+}
+
+impl Drop for CharsBuf {
+    fn drop(&mut self) {
+        if let Some(ptr) = self.ptr {
+            // Safety: it's our allocation, and nobody else has seen it.
+            unsafe { heap::dealloc(ptr.as_ptr().cast(), Self::layout(self.cap)) }
+        }
     }
 }
 
@@ -1319,6 +2645,12 @@ fn alloc_overflow() -> ! {
     panic!("overflow during Layout computation")
 }
 
+#[inline(never)]
+#[cold]
+fn weak_overflow() -> ! {
+    panic!("too many `WeakArcStr`s")
+}
+
 impl From<&str> for ArcStr {
     #[inline]
     fn from(s: &str) -> Self {
@@ -1348,6 +2680,11 @@ impl Default for ArcStr {
 impl From<String> for ArcStr {
     #[inline]
     fn from(v: String) -> Self {
+        // Note: This copies even when `v.capacity() == v.len()`. Adopting the
+        // `String`'s buffer isn't possible, since we need room for the header
+        // in front of the data, and our allocation has a larger alignment than
+        // `String`'s (which `realloc` can't change). Freeing the buffer with
+        // any layout other than the one it was allocated with is UB.
         v.as_str().into()
     }
 }
@@ -1433,6 +2770,167 @@ impl From<&ArcStr> for ArcStr {
     }
 }
 
+/// Collects into a `String` first (which reserves based on the iterator's
+/// `size_hint`), and copies the result into an `ArcStr`.
+///
+/// # Examples
+///
+/// ```
+/// # use arcstr::ArcStr;
+/// let s: ArcStr = "abc".chars().map(|c| c.to_ascii_uppercase()).collect();
+/// assert_eq!(s, "ABC");
+/// ```
+impl core::iter::FromIterator<char> for ArcStr {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let buf: String = iter.into_iter().collect();
+        Self::from(buf.as_str())
+    }
+}
+
+impl TryFrom<&[u8]> for ArcStr {
+    type Error = core::str::Utf8Error;
+    /// Copies `bytes` into a new `ArcStr` if they're valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::try_from(&b"abc"[..]).unwrap(), "abc");
+    /// assert!(ArcStr::try_from(&b"\xff"[..]).is_err());
+    /// ```
+    #[inline]
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        core::str::from_utf8(bytes).map(Self::from)
+    }
+}
+
+impl TryFrom<Vec<u8>> for ArcStr {
+    type Error = core::str::Utf8Error;
+    /// Copies `bytes` into a new `ArcStr` if they're valid UTF-8. The `Vec`'s
+    /// allocation can't be reused, for the same reason as with
+    /// `From<String>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// assert_eq!(ArcStr::try_from(b"abc".to_vec()).unwrap(), "abc");
+    /// assert!(ArcStr::try_from(b"\xff".to_vec()).is_err());
+    /// ```
+    #[inline]
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+/// Concatenation always allocates a new `ArcStr` (unless one side is empty),
+/// since the existing one can't be appended to.
+///
+/// # Examples
+///
+/// ```
+/// # use arcstr::ArcStr;
+/// let s = ArcStr::from("foo") + "bar" + ArcStr::from("baz");
+/// assert_eq!(s, "foobarbaz");
+/// ```
+impl core::ops::Add<&str> for ArcStr {
+    type Output = ArcStr;
+    #[inline]
+    fn add(self, rhs: &str) -> ArcStr {
+        if rhs.is_empty() {
+            self
+        } else {
+            concat_pair(&self, rhs)
+        }
+    }
+}
+
+impl core::ops::Add<ArcStr> for ArcStr {
+    type Output = ArcStr;
+    #[inline]
+    fn add(self, rhs: ArcStr) -> ArcStr {
+        if self.is_empty() {
+            rhs
+        } else {
+            self + rhs.as_str()
+        }
+    }
+}
+
+impl core::ops::Add<&str> for &ArcStr {
+    type Output = ArcStr;
+    #[inline]
+    fn add(self, rhs: &str) -> ArcStr {
+        self.clone() + rhs
+    }
+}
+
+impl core::ops::Add<ArcStr> for &ArcStr {
+    type Output = ArcStr;
+    #[inline]
+    fn add(self, rhs: ArcStr) -> ArcStr {
+        self.clone() + rhs
+    }
+}
+
+pub(crate) fn make_ascii_titlecase(bytes: &mut [u8]) {
+    if let Some(i) = bytes.iter().position(u8::is_ascii_alphabetic) {
+        bytes[i].make_ascii_uppercase();
+        bytes[i + 1..].make_ascii_lowercase();
+    }
+}
+
+// Shared by `ArcStr::char_count` and `Substr::char_count`.
+#[inline]
+pub(crate) fn char_count(s: &str) -> usize {
+    // TODO: this is a candidate for a faster (e.g. SIMD) implementation
+    // that counts non-continuation bytes.
+    s.chars().count()
+}
+
+// The byte offset of the `n`th char of `s`, or `s.len()` if there are fewer.
+// Shared by the `proptest` and `quickcheck` strategies for `Substr`.
+#[cfg(all(feature = "substr", any(feature = "proptest", feature = "quickcheck")))]
+pub(crate) fn nth_char_boundary(s: &str, n: usize) -> usize {
+    s.char_indices().nth(n).map_or(s.len(), |(i, _)| i)
+}
+
+// Appends `s` to `buf` as UTF-16, and returns the part which was appended.
+pub(crate) fn encode_utf16_to_buf<'a>(s: &str, buf: &'a mut Vec<u16>) -> &'a [u16] {
+    let start = buf.len();
+    buf.extend(s.encode_utf16());
+    &buf[start..]
+}
+
+// Copies `s` into a new `ArcStr`, and applies `f` (which must only change the
+// case of ASCII letters) to the copy.
+pub(crate) fn ascii_case_mapped(s: &str, f: fn(&mut [u8])) -> ArcStr {
+    if s.is_empty() {
+        return ArcStr::new();
+    }
+    unsafe {
+        ArcStr::init_with_unchecked(s.len(), |buf| {
+            let dst = buf.as_mut_ptr().cast::<u8>();
+            core::ptr::copy_nonoverlapping(s.as_ptr(), dst, s.len());
+            // Safety: we just initialized all of `buf`. Changing the case of
+            // ASCII letters keeps the contents valid UTF-8.
+            f(core::slice::from_raw_parts_mut(dst, s.len()));
+        })
+    }
+}
+
+fn concat_pair(a: &str, b: &str) -> ArcStr {
+    let len = a.len().checked_add(b.len()).expect("capacity overflow");
+    unsafe {
+        ArcStr::init_with_unchecked(len, |buf| {
+            let dst = buf.as_mut_ptr().cast::<u8>();
+            core::ptr::copy_nonoverlapping(a.as_ptr(), dst, a.len());
+            core::ptr::copy_nonoverlapping(b.as_ptr(), dst.add(a.len()), b.len());
+        })
+    }
+}
+
 impl core::fmt::Debug for ArcStr {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -1495,7 +2993,7 @@ impl_peq! {
 impl PartialOrd for ArcStr {
     #[inline]
     fn partial_cmp(&self, s: &Self) -> Option<core::cmp::Ordering> {
-        Some(self.as_str().cmp(s.as_str()))
+        Some(self.cmp(s))
     }
 }
 
@@ -1506,6 +3004,32 @@ impl Ord for ArcStr {
     }
 }
 
+macro_rules! impl_pord {
+    (@one $a:ty, $b:ty) => {
+        #[allow(clippy::extra_unused_lifetimes)]
+        impl<'a> PartialOrd<$b> for $a {
+            #[inline]
+            fn partial_cmp(&self, s: &$b) -> Option<core::cmp::Ordering> {
+                PartialOrd::partial_cmp(&self[..], &s[..])
+            }
+        }
+    };
+    ($(($a:ty, $b:ty),)+) => {$(
+        impl_pord!(@one $a, $b);
+        impl_pord!(@one $b, $a);
+    )+};
+}
+
+impl_pord! {
+    (ArcStr, str),
+    (ArcStr, &'a str),
+    (ArcStr, String),
+}
+
+/// Hashes exactly the same as the `str` we contain, and so the same as a
+/// `Substr` or `RcStr` with the same contents. This is relied on for
+/// `Borrow<str>`, which lets a `HashMap` with `ArcStr` keys be queried with a
+/// `&str`.
 impl core::hash::Hash for ArcStr {
     #[inline]
     fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
@@ -1548,6 +3072,117 @@ impl AsRef<[u8]> for ArcStr {
     }
 }
 
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for ArcStr {
+    #[inline]
+    fn as_ref(&self) -> &std::path::Path {
+        std::path::Path::new(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::ffi::OsStr> for ArcStr {
+    #[inline]
+    fn as_ref(&self) -> &std::ffi::OsStr {
+        std::ffi::OsStr::new(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ArcStr> for std::path::PathBuf {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ArcStr> for std::ffi::OsString {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<&std::ffi::OsStr> for ArcStr {
+    type Error = NotUnicodeError;
+    /// Copies `s` into a new `ArcStr` if it's valid Unicode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// # use std::ffi::OsStr;
+    /// assert_eq!(ArcStr::try_from(OsStr::new("foo")).unwrap(), "foo");
+    /// ```
+    #[inline]
+    fn try_from(s: &std::ffi::OsStr) -> Result<Self, Self::Error> {
+        s.to_str().map(Self::from).ok_or(NotUnicodeError(()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<&std::ffi::CStr> for ArcStr {
+    type Error = core::str::Utf8Error;
+    /// Copies `s` (without its nul terminator) into a new `ArcStr` if it's
+    /// valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// # use std::ffi::CStr;
+    /// let c = CStr::from_bytes_with_nul(b"foo\0").unwrap();
+    /// assert_eq!(ArcStr::try_from(c).unwrap(), "foo");
+    /// ```
+    #[inline]
+    fn try_from(s: &std::ffi::CStr) -> Result<Self, Self::Error> {
+        s.to_str().map(Self::from)
+    }
+}
+
+/// This can't be a `From` impl, since the string may contain a nul byte.
+#[cfg(feature = "std")]
+// `std::ffi::NulError` has been stable forever, clippy just sees it as
+// `alloc::ffi::NulError`, which hasn't.
+#[allow(clippy::incompatible_msrv)]
+impl TryFrom<ArcStr> for std::ffi::CString {
+    type Error = std::ffi::NulError;
+    /// Copies `s` into a new `CString`, appending a nul terminator. Fails if
+    /// `s` already contains a nul byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// # use std::ffi::CString;
+    /// let c = CString::try_from(ArcStr::from("foo")).unwrap();
+    /// assert_eq!(c.as_bytes_with_nul(), b"foo\0");
+    /// assert!(CString::try_from(ArcStr::from("a\0b")).is_err());
+    /// ```
+    #[inline]
+    fn try_from(s: ArcStr) -> Result<Self, Self::Error> {
+        Self::new(s.as_str())
+    }
+}
+
+/// `feature = "std"`: The error returned when converting an
+/// [`OsStr`](std::ffi::OsStr) which isn't valid Unicode into an [`ArcStr`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotUnicodeError(());
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for NotUnicodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("string is not valid unicode")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotUnicodeError {}
+
 impl core::borrow::Borrow<str> for ArcStr {
     #[inline]
     fn borrow(&self) -> &str {
@@ -1574,6 +3209,10 @@ mod test {
             core::mem::offset_of!(StaticArcStrInner<Buf>, count_flag),
             OFFSET_COUNTFLAGS
         );
+        assert_eq!(
+            core::mem::offset_of!(StaticArcStrInner<Buf>, weak_count),
+            OFFSET_WEAKCOUNT
+        );
         assert_eq!(
             core::mem::offset_of!(StaticArcStrInner<Buf>, len_flag),
             OFFSET_LENFLAGS
@@ -1603,6 +3242,10 @@ mod test {
             OFFSET_COUNTFLAGS,
         );
         assert_eq!(core::mem::offset_of!(ThinInner, len_flag), OFFSET_LENFLAGS);
+        assert_eq!(
+            core::mem::offset_of!(ThinInner, weak_count),
+            OFFSET_WEAKCOUNT
+        );
         assert_eq!(core::mem::offset_of!(ThinInner, data), OFFSET_DATA);
 
         assert!(align_of::<ThinInner>() >= 8);
@@ -1650,6 +3293,99 @@ mod loomtest {
             t2.join().unwrap();
         });
     }
+    #[test]
+    fn strong_count_concurrent() {
+        loom::model(|| {
+            let a = Arc::new(ArcStr::from("abcdefgh"));
+            let a2 = a.clone();
+            let t = thread::spawn(move || {
+                let b = (*a2).clone();
+                assert_eq!(ArcStr::strong_count(&b), Some(2));
+                drop(b);
+            });
+            let n = ArcStr::strong_count(&a).unwrap();
+            assert!((1..=2).contains(&n), "{}", n);
+            t.join().unwrap();
+            assert_eq!(ArcStr::strong_count(&a), Some(1));
+        });
+    }
+
+    #[test]
+    fn strong_count_vs_drop() {
+        loom::model(|| {
+            let a = ArcStr::from("abcdefgh");
+            let b = a.clone();
+            let shared = Arc::new(a);
+            let (s1, s2) = (shared.clone(), shared.clone());
+
+            let t1 = thread::spawn(move || {
+                let n = ArcStr::strong_count(&s1).unwrap();
+                assert!((1..=2).contains(&n), "{}", n);
+            });
+            let t2 = thread::spawn(move || {
+                let n = ArcStr::strong_count(&s2).unwrap();
+                assert!((1..=2).contains(&n), "{}", n);
+            });
+            let t3 = thread::spawn(move || drop(b));
+            t1.join().unwrap();
+            t2.join().unwrap();
+            t3.join().unwrap();
+            assert_eq!(ArcStr::strong_count(&shared), Some(1));
+        });
+    }
+
+    #[test]
+    fn into_raw_from_raw() {
+        struct SendPtr(core::ptr::NonNull<()>);
+        unsafe impl Send for SendPtr {}
+        loom::model(|| {
+            let a = ArcStr::from("abcdefgh");
+            let b = a.clone();
+
+            let t1 = thread::spawn(move || SendPtr(ArcStr::into_raw(a)));
+            let t2 = thread::spawn(move || {
+                assert_eq!(b, "abcdefgh");
+                drop(b);
+            });
+            // Joining orders the `into_raw` before the `from_raw`, as any
+            // real code passing the pointer between threads would have to.
+            let raw = t1.join().unwrap();
+            let t3 = thread::spawn(move || {
+                let c = unsafe { ArcStr::from_raw(raw.0) };
+                assert_eq!(c, "abcdefgh");
+                c
+            });
+            t2.join().unwrap();
+            let c = t3.join().unwrap();
+            assert_eq!(ArcStr::strong_count(&c), Some(1));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "substr")]
+    fn substr_across_threads() {
+        use crate::Substr;
+        loom::model(|| {
+            let a = ArcStr::from("abcdefgh");
+            let a2 = a.clone();
+
+            let t1 = thread::spawn(move || {
+                let full = Substr::full(a);
+                let sub = full.clone().substr(1..3);
+                assert_eq!(sub, "bc");
+                full
+            });
+            let t2 = thread::spawn(move || {
+                let sub = a2.substr(2..);
+                assert_eq!(sub.substr(4..), "gh");
+            });
+            let full = t1.join().unwrap();
+            t2.join().unwrap();
+            assert_eq!(full, "abcdefgh");
+            assert_eq!(ArcStr::strong_count(full.parent()), Some(1));
+        });
+    }
+
     #[test]
     fn drop_timing() {
         loom::model(|| {
@@ -1694,4 +3430,78 @@ mod loomtest {
             assert_eq!(leaked, "foo");
         });
     }
+
+    #[test]
+    fn try_reuse_unique() {
+        loom::model(|| {
+            let a1 = ArcStr::from("foo");
+            let a2 = a1.clone();
+
+            let t1 = thread::spawn(move || {
+                assert_eq!(a2, "foo");
+                drop(a2);
+            });
+            let b = ArcStr::try_reuse(a1, "bar");
+            assert_eq!(b, "bar");
+            t1.join().unwrap();
+            // Whichever way that went, we're unique now.
+            let addr = b.as_ptr() as usize;
+            let c = ArcStr::try_reuse(b, "baz");
+            assert_eq!(c, "baz");
+            assert_eq!(c.as_ptr() as usize, addr);
+        });
+    }
+
+    #[test]
+    fn weak_upgrade_vs_drop() {
+        loom::model(|| {
+            let a = ArcStr::from("foo");
+            let w1 = ArcStr::downgrade(&a);
+            let w2 = w1.clone();
+
+            let t1 = thread::spawn(move || drop(a));
+            let t2 = thread::spawn(move || {
+                if let Some(s) = w2.upgrade() {
+                    assert_eq!(s, "foo");
+                }
+            });
+            t1.join().unwrap();
+            t2.join().unwrap();
+            assert_eq!(w1.strong_count(), Some(0));
+            assert!(w1.upgrade().is_none());
+        });
+    }
+
+    #[test]
+    fn weak_drop_vs_strong_drop() {
+        loom::model(|| {
+            let a = ArcStr::from("foo");
+            let w = ArcStr::downgrade(&a);
+
+            let t1 = thread::spawn(move || drop(a));
+            let t2 = thread::spawn(move || drop(w));
+            t1.join().unwrap();
+            t2.join().unwrap();
+        });
+    }
+
+    #[test]
+    fn try_reuse_vs_weak() {
+        loom::model(|| {
+            let a = ArcStr::from("foo");
+            let w = ArcStr::downgrade(&a);
+
+            let t1 = thread::spawn(move || {
+                let s = w.upgrade();
+                drop(w);
+                // Nobody may write to the string while we hold it.
+                if let Some(s) = s {
+                    assert_eq!(s, "foo");
+                }
+            });
+            let b = ArcStr::try_reuse(a, "bar");
+            assert_eq!(b, "bar");
+            t1.join().unwrap();
+        });
+    }
 }
@@ -0,0 +1,218 @@
+#![cfg_attr(feature = "substr-usize-indices", allow(clippy::unnecessary_cast))]
+use crate::substr::Idx;
+use crate::{ArcStr, Substr};
+use alloc::vec::Vec;
+
+/// A list of [`Substr`]s which all share the same parent [`ArcStr`].
+///
+/// This stores the parent once, along with the bounds of each substring,
+/// rather than storing a full `Substr` (which contains its own reference to
+/// the parent) for each one. That makes each element smaller than a `Substr`
+/// (half the size on 64-bit targets, unless the `substr-usize-indices`
+/// feature is enabled), and means adding or removing elements never touches
+/// the parent's reference count. It's intended for things like the
+/// tokens a parser produces from a single input string.
+///
+/// `Substr`s are created on demand (by [`ArcStrVec::get`] and
+/// [`ArcStrVec::iter`]), each of which clones the parent once. If you only
+/// need the text, [`ArcStrVec::get_str`] avoids even that.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, ArcStrVec, Substr};
+/// let input = ArcStr::from("let x = 42;");
+/// let mut tokens = ArcStrVec::new(input.clone());
+/// for word in input.substr(..).split_as_substrs(" ") {
+///     tokens.push(word).unwrap();
+/// }
+/// assert_eq!(tokens.len(), 4);
+/// assert_eq!(tokens.get(3).unwrap(), "42;");
+/// assert_eq!(tokens.get_str(1), Some("x"));
+/// let all: Vec<Substr> = tokens.iter().collect();
+/// assert_eq!(all, ["let", "x", "=", "42;"]);
+///
+/// // Substrings of other strings are rejected.
+/// assert!(tokens.push(Substr::from("nope")).is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct ArcStrVec {
+    parent: ArcStr,
+    bounds: Vec<(Idx, Idx)>,
+}
+
+impl ArcStrVec {
+    /// Create an empty `ArcStrVec`, which holds substrings of `parent`.
+    #[inline]
+    pub fn new(parent: ArcStr) -> Self {
+        Self {
+            parent,
+            bounds: Vec::new(),
+        }
+    }
+
+    /// Create an empty `ArcStrVec`, which holds substrings of `parent`, with
+    /// space for at least `capacity` of them.
+    #[inline]
+    pub fn with_capacity(parent: ArcStr, capacity: usize) -> Self {
+        Self {
+            parent,
+            bounds: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the string that all of our substrings are from.
+    #[inline]
+    pub fn parent(&self) -> &ArcStr {
+        &self.parent
+    }
+
+    /// Returns the number of substrings we hold.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Returns true if we hold no substrings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// Adds `s` to the end, as long as its parent is ours (by pointer, as with
+    /// [`ArcStr::ptr_eq`]). Otherwise, `s` is returned as the error.
+    ///
+    /// Empty substrings are always accepted, whatever their parent: things
+    /// like [`ArcStr::substr_from`] return [`Substr::new`] for an empty `&str`,
+    /// whose parent is the shared empty string. One with a different parent is
+    /// stored as the empty range at the start of ours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, ArcStrVec};
+    /// let a = ArcStr::from("abc");
+    /// let mut v = ArcStrVec::new(a.clone());
+    /// assert!(v.push(a.substr(1..)).is_ok());
+    /// // Same contents, different parent.
+    /// let b = ArcStr::from("abc");
+    /// assert_eq!(v.push(b.substr(1..)).unwrap_err(), "bc");
+    /// assert_eq!(v.len(), 1);
+    /// // Empty ones are fine, though.
+    /// assert!(v.push(b.substr(1..1)).is_ok());
+    /// assert_eq!(v.len(), 2);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, s: Substr) -> Result<(), Substr> {
+        if ArcStr::ptr_eq(s.parent(), &self.parent) {
+            self.bounds.push(s.idx_bounds());
+            Ok(())
+        } else if s.is_empty() {
+            self.bounds.push((0, 0));
+            Ok(())
+        } else {
+            Err(s)
+        }
+    }
+
+    /// Returns the substring at `index` (or `None` if it's out of bounds).
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Substr> {
+        let bounds = *self.bounds.get(index)?;
+        // Safety: everything in `self.bounds` came from a `Substr` of
+        // `self.parent`.
+        Some(unsafe { Substr::from_idx_bounds(self.parent.clone(), bounds) })
+    }
+
+    /// Returns the text of the substring at `index` (or `None` if it's out of
+    /// bounds), without creating a `Substr`.
+    #[inline]
+    pub fn get_str(&self, index: usize) -> Option<&str> {
+        let (start, end) = *self.bounds.get(index)?;
+        Some(&self.parent[start as usize..end as usize])
+    }
+
+    /// Removes and returns the last substring, if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<Substr> {
+        let bounds = self.bounds.pop()?;
+        // Safety: as in `get`.
+        Some(unsafe { Substr::from_idx_bounds(self.parent.clone(), bounds) })
+    }
+
+    /// Removes all of the substrings, keeping the parent.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bounds.clear();
+    }
+
+    /// Returns an iterator over our substrings, in order.
+    #[inline]
+    pub fn iter(&self) -> ArcStrVecIter<'_> {
+        ArcStrVecIter {
+            parent: &self.parent,
+            inner: self.bounds.iter(),
+        }
+    }
+}
+
+impl core::fmt::Debug for ArcStrVec {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries((0..self.len()).filter_map(|i| self.get_str(i)))
+            .finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a ArcStrVec {
+    type Item = Substr;
+    type IntoIter = ArcStrVecIter<'a>;
+    #[inline]
+    fn into_iter(self) -> ArcStrVecIter<'a> {
+        self.iter()
+    }
+}
+
+impl From<ArcStrVec> for Vec<Substr> {
+    #[inline]
+    fn from(v: ArcStrVec) -> Self {
+        v.iter().collect()
+    }
+}
+
+/// An iterator over the [`Substr`]s in an [`ArcStrVec`].
+///
+/// Created by [`ArcStrVec::iter`].
+#[derive(Clone, Debug)]
+pub struct ArcStrVecIter<'a> {
+    parent: &'a ArcStr,
+    inner: core::slice::Iter<'a, (Idx, Idx)>,
+}
+
+impl Iterator for ArcStrVecIter<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let bounds = *self.inner.next()?;
+        // Safety: as in `ArcStrVec::get`.
+        Some(unsafe { Substr::from_idx_bounds(self.parent.clone(), bounds) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ArcStrVecIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Substr> {
+        let bounds = *self.inner.next_back()?;
+        // Safety: as in `ArcStrVec::get`.
+        Some(unsafe { Substr::from_idx_bounds(self.parent.clone(), bounds) })
+    }
+}
+
+impl ExactSizeIterator for ArcStrVecIter<'_> {}
+
+impl core::iter::FusedIterator for ArcStrVecIter<'_> {}
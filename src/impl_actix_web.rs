@@ -0,0 +1,40 @@
+use crate::impl_bytes::body_bytes;
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use actix_web::body::BoxBody;
+use actix_web::http::header::ContentType;
+use actix_web::web::Bytes;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+
+fn text_response(body: Bytes) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ContentType::plaintext())
+        .body(body)
+}
+
+/// `feature = "actix-web"`: Responds with the string as a `text/plain;
+/// charset=utf-8` body, the same as `String` does.
+///
+/// The body doesn't copy the string data if it's static, or if the `bytes`
+/// feature is also enabled.
+impl Responder for ArcStr {
+    type Body = BoxBody;
+
+    #[inline]
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+        text_response(body_bytes(&self, 0..self.len()))
+    }
+}
+
+/// `feature = "actix-web"`: Responds with the substring as a `text/plain;
+/// charset=utf-8` body. See the impl for `ArcStr`.
+#[cfg(feature = "substr")]
+impl Responder for Substr {
+    type Body = BoxBody;
+
+    #[inline]
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+        text_response(body_bytes(self.parent(), self.range()))
+    }
+}
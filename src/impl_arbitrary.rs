@@ -0,0 +1,52 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+impl<'a> Arbitrary<'a> for ArcStr {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&str as Arbitrary>::arbitrary(u).map(ArcStr::from)
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        <&str as Arbitrary>::arbitrary_take_rest(u).map(ArcStr::from)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str as Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl<'a> Arbitrary<'a> for Substr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let parent = ArcStr::arbitrary(u)?;
+        let start = arbitrary_char_boundary(u, &parent, 0)?;
+        let end = arbitrary_char_boundary(u, &parent, start)?;
+        Ok(parent.substr(start..end))
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <ArcStr as Arbitrary>::size_hint(depth)
+    }
+}
+
+/// Picks a char boundary of `s` which is at or after `lo` (which must itself be
+/// a char boundary).
+#[cfg(feature = "substr")]
+fn arbitrary_char_boundary(u: &mut Unstructured<'_>, s: &str, lo: usize) -> Result<usize> {
+    let rest = &s[lo..];
+    let which = u.int_in_range(0..=rest.chars().count())?;
+    let boundary = rest
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(Some(rest.len()))
+        .nth(which)
+        .unwrap_or(rest.len());
+    Ok(lo + boundary)
+}
@@ -0,0 +1,38 @@
+use crate::impl_bytes::body_bytes;
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderValue};
+use axum::response::{IntoResponse, Response};
+
+fn text_response(body: Bytes) -> Response {
+    let mut res = Body::from(body).into_response();
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+    res
+}
+
+/// `feature = "axum"`: Responds with the string as a `text/plain;
+/// charset=utf-8` body, the same as `String` does.
+///
+/// The body doesn't copy the string data if it's static, or if the `bytes`
+/// feature is also enabled.
+impl IntoResponse for ArcStr {
+    #[inline]
+    fn into_response(self) -> Response {
+        text_response(body_bytes(&self, 0..self.len()))
+    }
+}
+
+/// `feature = "axum"`: Responds with the substring as a `text/plain;
+/// charset=utf-8` body. See the impl for `ArcStr`.
+#[cfg(feature = "substr")]
+impl IntoResponse for Substr {
+    #[inline]
+    fn into_response(self) -> Response {
+        text_response(body_bytes(self.parent(), self.range()))
+    }
+}
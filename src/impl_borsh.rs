@@ -0,0 +1,37 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use alloc::string::String;
+use borsh::io::{Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+impl BorshSerialize for ArcStr {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.as_str().serialize(writer)
+    }
+}
+
+impl BorshDeserialize for ArcStr {
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        String::deserialize_reader(reader).map(ArcStr::from)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl BorshSerialize for Substr {
+    #[inline]
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        self.as_str().serialize(writer)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl BorshDeserialize for Substr {
+    #[inline]
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        String::deserialize_reader(reader).map(Substr::from)
+    }
+}
@@ -0,0 +1,61 @@
+use super::ArcStr;
+
+// `axum` and `actix-web` both re-export `bytes::Bytes`, which lets them share
+// `body_bytes` even when our `bytes` feature is off.
+#[cfg(all(feature = "actix-web", not(any(feature = "bytes", feature = "axum"))))]
+use actix_web::web::Bytes;
+#[cfg(all(feature = "axum", not(feature = "bytes")))]
+use axum::body::Bytes;
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+#[cfg(feature = "bytes")]
+use core::convert::TryFrom;
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+use core::ops::Range;
+#[cfg(feature = "bytes")]
+use core::str::Utf8Error;
+
+/// Converts to [`Bytes`] without copying the string data.
+///
+/// Static `ArcStr`s become a `Bytes` which refers directly to the static data,
+/// and dynamic ones become a `Bytes` which holds onto a reference to the
+/// `ArcStr`.
+#[cfg(feature = "bytes")]
+impl From<ArcStr> for Bytes {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        match ArcStr::as_static(&s) {
+            Some(st) => Bytes::from_static(st.as_bytes()),
+            None => Bytes::from_owner(s),
+        }
+    }
+}
+
+/// Copies the data out of a [`Bytes`] containing UTF-8.
+#[cfg(feature = "bytes")]
+impl TryFrom<Bytes> for ArcStr {
+    type Error = Utf8Error;
+
+    #[inline]
+    fn try_from(b: Bytes) -> Result<Self, Self::Error> {
+        core::str::from_utf8(&b).map(ArcStr::from)
+    }
+}
+
+// Returns `parent[range]` as `Bytes`, for the `axum` and `actix-web` response
+// bodies. This only copies if it has to: without our `bytes` feature we can
+// still avoid it for static strings.
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+pub(crate) fn body_bytes(parent: &ArcStr, range: Range<usize>) -> Bytes {
+    #[cfg(feature = "bytes")]
+    {
+        Bytes::from(parent.clone()).slice(range)
+    }
+    #[cfg(not(feature = "bytes"))]
+    {
+        match ArcStr::as_static(parent) {
+            Some(s) => Bytes::from_static(s[range].as_bytes()),
+            None => Bytes::copy_from_slice(parent[range].as_bytes()),
+        }
+    }
+}
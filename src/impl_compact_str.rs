@@ -0,0 +1,51 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use compact_str::CompactString;
+
+/// `feature = "compact-str"`: Copies the data into a new `ArcStr`.
+impl From<CompactString> for ArcStr {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "compact-str"`: Copies the data into a new `ArcStr`.
+impl From<&CompactString> for ArcStr {
+    #[inline]
+    fn from(s: &CompactString) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "compact-str"`: Converts to a `CompactString`, which copies the
+/// data unless it's static (for example, from
+/// [`arcstr::literal!`](crate::literal)).
+impl From<ArcStr> for CompactString {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        match ArcStr::as_static(&s) {
+            Some(st) => CompactString::const_new(st),
+            None => CompactString::new(s.as_str()),
+        }
+    }
+}
+
+/// `feature = "compact-str"`: Copies the data into a new `Substr`.
+#[cfg(feature = "substr")]
+impl From<CompactString> for Substr {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        Substr::from(s.as_str())
+    }
+}
+
+/// `feature = "compact-str"`: Copies the data into a new `CompactString`.
+#[cfg(feature = "substr")]
+impl From<Substr> for CompactString {
+    #[inline]
+    fn from(s: Substr) -> Self {
+        CompactString::new(s.as_str())
+    }
+}
@@ -0,0 +1,23 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use defmt::{Format, Formatter};
+
+// `=str` formats the string as-is, and doesn't allocate (or copy), since the
+// data is sent directly to the logger.
+
+impl Format for ArcStr {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "{=str}", self.as_str())
+    }
+}
+
+#[cfg(feature = "substr")]
+impl Format for Substr {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "{=str}", self.as_str())
+    }
+}
@@ -0,0 +1,38 @@
+use super::ArcStr;
+
+use alloc::string::String;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+// Generates the `AsExpression` and `Queryable`/`FromSqlRow` impls for
+// `ArcStr`, which defer to the `FromSql`/`ToSql` impls below.
+#[derive(AsExpression, FromSqlRow)]
+#[diesel(foreign_derive)]
+#[diesel(sql_type = Text)]
+#[allow(dead_code)]
+struct ArcStrProxy(ArcStr);
+
+impl<DB> FromSql<Text, DB> for ArcStr
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    #[inline]
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        <String as FromSql<Text, DB>>::from_sql(bytes).map(ArcStr::from)
+    }
+}
+
+impl<DB> ToSql<Text, DB> for ArcStr
+where
+    DB: Backend,
+    str: ToSql<Text, DB>,
+{
+    #[inline]
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <str as ToSql<Text, DB>>::to_sql(self.as_str(), out)
+    }
+}
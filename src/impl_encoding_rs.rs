@@ -0,0 +1,90 @@
+use crate::ArcStr;
+use alloc::string::String;
+use alloc::vec::Vec;
+use encoding_rs::{CoderResult, Decoder, Encoding};
+
+fn decode_with(mut decoder: Decoder, bytes: &[u8]) -> (ArcStr, bool) {
+    let cap = decoder
+        .max_utf8_buffer_length(bytes.len())
+        .expect("capacity overflow");
+    let mut s = String::with_capacity(cap);
+    let (result, read, replaced) = decoder.decode_to_string(bytes, &mut s, true);
+    // Can't run out of space, since we reserved the worst case.
+    debug_assert!(result == CoderResult::InputEmpty && read == bytes.len());
+    (ArcStr::from(s), replaced)
+}
+
+impl ArcStr {
+    /// `feature = "encoding"`: Decode `bytes`, which are in the given
+    /// `encoding`, into an `ArcStr`.
+    ///
+    /// Malformed sequences are replaced with the REPLACEMENT CHARACTER
+    /// (`U+FFFD`), and the returned `bool` is true if that happened.
+    ///
+    /// This performs BOM sniffing: if `bytes` starts with a UTF-8 or UTF-16
+    /// byte order mark, it's used to decide the encoding instead of
+    /// `encoding`, and is removed. Use [`ArcStr::decode_without_bom_handling`]
+    /// if you don't want this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// use encoding_rs::{SHIFT_JIS, WINDOWS_1252};
+    /// let (s, replaced) = ArcStr::decode(b"caf\xe9 \x80", WINDOWS_1252);
+    /// assert_eq!(s, "café €");
+    /// assert!(!replaced);
+    ///
+    /// let (s, replaced) = ArcStr::decode(b"\x93\xfa\x96\x7b\x82", SHIFT_JIS);
+    /// assert_eq!(s, "日本\u{fffd}");
+    /// assert!(replaced);
+    ///
+    /// // The BOM overrides the encoding we passed in.
+    /// let (s, _) = ArcStr::decode(b"\xef\xbb\xbfcaf\xc3\xa9", WINDOWS_1252);
+    /// assert_eq!(s, "café");
+    /// ```
+    pub fn decode(bytes: &[u8], encoding: &'static Encoding) -> (Self, bool) {
+        decode_with(encoding.new_decoder(), bytes)
+    }
+
+    /// `feature = "encoding"`: Decode `bytes`, which are in the given
+    /// `encoding`, into an `ArcStr`, without BOM sniffing or removal.
+    ///
+    /// This is the same as [`ArcStr::decode`], except that a byte order mark at
+    /// the start of `bytes` is decoded the same as any other bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// use encoding_rs::WINDOWS_1252;
+    /// let (s, replaced) = ArcStr::decode_without_bom_handling(b"\xef\xbb\xbfA", WINDOWS_1252);
+    /// assert_eq!(s, "ï»¿A");
+    /// assert!(!replaced);
+    /// ```
+    pub fn decode_without_bom_handling(bytes: &[u8], encoding: &'static Encoding) -> (Self, bool) {
+        decode_with(encoding.new_decoder_without_bom_handling(), bytes)
+    }
+
+    /// `feature = "encoding"`: Encode this string into the given `encoding`.
+    ///
+    /// This has the same behavior as [`Encoding::encode`], notably:
+    ///
+    /// - Characters which can't be represented in `encoding` are replaced with
+    ///   HTML decimal numeric character references (for example, `&#8364;`).
+    /// - UTF-16BE, UTF-16LE and replacement encodings produce UTF-8 instead,
+    ///   as required by the Encoding Standard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// use encoding_rs::{ISO_8859_2, WINDOWS_1252};
+    /// let s = ArcStr::from("café €");
+    /// assert_eq!(s.encode_to(WINDOWS_1252), b"caf\xe9 \x80");
+    /// assert_eq!(s.encode_to(ISO_8859_2), b"caf\xe9 &#8364;");
+    /// ```
+    pub fn encode_to(&self, encoding: &'static Encoding) -> Vec<u8> {
+        encoding.encode(self).0.into_owned()
+    }
+}
@@ -0,0 +1,89 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use core::convert::TryFrom;
+use http::header::{InvalidHeaderValue, ToStrError};
+use http::HeaderValue;
+
+/// `feature = "http"`: Copies the value into an `ArcStr`.
+///
+/// This fails if the value isn't entirely visible ASCII, the same as
+/// [`HeaderValue::to_str`].
+impl TryFrom<&HeaderValue> for ArcStr {
+    type Error = ToStrError;
+    #[inline]
+    fn try_from(v: &HeaderValue) -> Result<Self, Self::Error> {
+        v.to_str().map(ArcStr::from)
+    }
+}
+
+/// `feature = "http"`: Copies the value into an `ArcStr`. See the impl for
+/// `&HeaderValue`.
+impl TryFrom<HeaderValue> for ArcStr {
+    type Error = ToStrError;
+    #[inline]
+    fn try_from(v: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&v)
+    }
+}
+
+/// `feature = "http"`: Converts the string to a `HeaderValue`.
+///
+/// This fails if the string contains characters which aren't allowed in a
+/// header value, the same as [`HeaderValue::from_str`]. If the `bytes` feature
+/// is also enabled, the `HeaderValue` shares the string's data rather than
+/// copying it.
+impl TryFrom<ArcStr> for HeaderValue {
+    type Error = InvalidHeaderValue;
+    #[inline]
+    fn try_from(s: ArcStr) -> Result<Self, Self::Error> {
+        #[cfg(feature = "bytes")]
+        {
+            HeaderValue::from_maybe_shared(bytes::Bytes::from(s))
+        }
+        #[cfg(not(feature = "bytes"))]
+        {
+            HeaderValue::from_str(&s)
+        }
+    }
+}
+
+/// `feature = "http"`: Copies the value into a new `Substr`. See the impl for
+/// `ArcStr`.
+#[cfg(feature = "substr")]
+impl TryFrom<&HeaderValue> for Substr {
+    type Error = ToStrError;
+    #[inline]
+    fn try_from(v: &HeaderValue) -> Result<Self, Self::Error> {
+        ArcStr::try_from(v).map(Substr::from)
+    }
+}
+
+/// `feature = "http"`: Copies the value into a new `Substr`. See the impl for
+/// `ArcStr`.
+#[cfg(feature = "substr")]
+impl TryFrom<HeaderValue> for Substr {
+    type Error = ToStrError;
+    #[inline]
+    fn try_from(v: HeaderValue) -> Result<Self, Self::Error> {
+        ArcStr::try_from(&v).map(Substr::from)
+    }
+}
+
+/// `feature = "http"`: Converts the substring to a `HeaderValue`. See the impl
+/// for `ArcStr`.
+#[cfg(feature = "substr")]
+impl TryFrom<Substr> for HeaderValue {
+    type Error = InvalidHeaderValue;
+    #[inline]
+    fn try_from(s: Substr) -> Result<Self, Self::Error> {
+        #[cfg(feature = "bytes")]
+        {
+            HeaderValue::from_maybe_shared(bytes::Bytes::from(s.parent().clone()).slice(s.range()))
+        }
+        #[cfg(not(feature = "bytes"))]
+        {
+            HeaderValue::from_str(&s)
+        }
+    }
+}
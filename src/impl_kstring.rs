@@ -0,0 +1,53 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use kstring::backend::HeapStr;
+use kstring::KStringBase;
+
+/// `feature = "kstring"`: Copies the data into a new `ArcStr`.
+impl<B: HeapStr> From<KStringBase<B>> for ArcStr {
+    #[inline]
+    fn from(s: KStringBase<B>) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "kstring"`: Copies the data into a new `ArcStr`.
+impl<B: HeapStr> From<&KStringBase<B>> for ArcStr {
+    #[inline]
+    fn from(s: &KStringBase<B>) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "kstring"`: Converts to a `KString` (or other `KStringBase`),
+/// which copies the data unless it's static (for example, from
+/// [`arcstr::literal!`](crate::literal)).
+impl<B: HeapStr> From<ArcStr> for KStringBase<B> {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        match ArcStr::as_static(&s) {
+            Some(st) => KStringBase::from_static(st),
+            None => KStringBase::from_ref(s.as_str()),
+        }
+    }
+}
+
+/// `feature = "kstring"`: Copies the data into a new `Substr`.
+#[cfg(feature = "substr")]
+impl<B: HeapStr> From<KStringBase<B>> for Substr {
+    #[inline]
+    fn from(s: KStringBase<B>) -> Self {
+        Substr::from(s.as_str())
+    }
+}
+
+/// `feature = "kstring"`: Copies the data into a new `KString` (or other
+/// `KStringBase`).
+#[cfg(feature = "substr")]
+impl<B: HeapStr> From<Substr> for KStringBase<B> {
+    #[inline]
+    fn from(s: Substr) -> Self {
+        KStringBase::from_ref(s.as_str())
+    }
+}
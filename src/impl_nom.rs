@@ -0,0 +1,150 @@
+use super::substr::CharIndices;
+use super::Substr;
+
+use alloc::string::String;
+use core::str::FromStr;
+use nom::{
+    AsBytes, Compare, CompareResult, ExtendInto, FindSubstring, FindToken, Input, Needed, Offset,
+    ParseTo,
+};
+
+// These mostly defer to the impls for `&str`, but take care that any slices
+// nom produces are `Substr`s of the same parent, rather than copies.
+
+impl Input for Substr {
+    type Item = char;
+    type Iter = core::iter::Map<CharIndices, fn((usize, char)) -> char>;
+    type IterIndices = CharIndices;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn take(&self, index: usize) -> Self {
+        self.substr(..index)
+    }
+
+    #[inline]
+    fn take_from(&self, index: usize) -> Self {
+        self.substr(index..)
+    }
+
+    #[inline]
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        let (prefix, suffix) = self.split_at(index);
+        (suffix, prefix)
+    }
+
+    #[inline]
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.find(predicate)
+    }
+
+    #[inline]
+    fn iter_elements(&self) -> Self::Iter {
+        self.iter_indices().map(|(_, c)| c)
+    }
+
+    #[inline]
+    fn iter_indices(&self) -> Self::IterIndices {
+        CharIndices::new(self.clone())
+    }
+
+    #[inline]
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.as_str().slice_index(count)
+    }
+}
+
+impl Offset for Substr {
+    #[inline]
+    fn offset(&self, second: &Self) -> usize {
+        self.as_str().offset(second.as_str())
+    }
+}
+
+impl AsBytes for Substr {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+impl<'b> Compare<&'b str> for Substr {
+    #[inline]
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.as_str().compare(t)
+    }
+
+    #[inline]
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.as_str().compare_no_case(t)
+    }
+}
+
+impl<'b> Compare<&'b [u8]> for Substr {
+    #[inline]
+    fn compare(&self, t: &'b [u8]) -> CompareResult {
+        self.as_str().compare(t)
+    }
+
+    #[inline]
+    fn compare_no_case(&self, t: &'b [u8]) -> CompareResult {
+        self.as_str().compare_no_case(t)
+    }
+}
+
+impl FindToken<char> for Substr {
+    #[inline]
+    fn find_token(&self, token: char) -> bool {
+        self.as_str().find_token(token)
+    }
+}
+
+impl FindToken<u8> for Substr {
+    #[inline]
+    fn find_token(&self, token: u8) -> bool {
+        self.as_str().find_token(token)
+    }
+}
+
+impl<'a> FindToken<&'a u8> for Substr {
+    #[inline]
+    fn find_token(&self, token: &'a u8) -> bool {
+        self.as_str().find_token(token)
+    }
+}
+
+impl<'b> FindSubstring<&'b str> for Substr {
+    #[inline]
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.find(substr)
+    }
+}
+
+impl<R: FromStr> ParseTo<R> for Substr {
+    #[inline]
+    fn parse_to(&self) -> Option<R> {
+        self.parse().ok()
+    }
+}
+
+impl ExtendInto for Substr {
+    type Item = char;
+    type Extender = String;
+
+    #[inline]
+    fn new_builder(&self) -> String {
+        String::new()
+    }
+
+    #[inline]
+    fn extend_into(&self, acc: &mut String) {
+        acc.push_str(self);
+    }
+}
@@ -0,0 +1,55 @@
+use super::ArcStr;
+
+use alloc::string::String;
+use core::convert::Infallible;
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyString};
+use pyo3::Borrowed;
+
+impl<'py> IntoPyObject<'py> for ArcStr {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(PyString::new(py, &self))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &ArcStr {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(PyString::new(py, self))
+    }
+}
+
+/// Accepts Python `str`, as well as `bytes` and `bytearray` objects which
+/// contain valid UTF-8.
+impl FromPyObject<'_, '_> for ArcStr {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let not_str = match ob.cast::<PyString>() {
+            Ok(s) => return Ok(ArcStr::from(&*s.to_cow()?)),
+            Err(e) => e,
+        };
+        if let Ok(b) = ob.cast::<PyBytes>() {
+            let bytes = b.as_bytes();
+            return match core::str::from_utf8(bytes) {
+                Ok(s) => Ok(ArcStr::from(s)),
+                // Go through `FromUtf8Error`, which pyo3 turns into a proper
+                // `UnicodeDecodeError`.
+                Err(_) => Err(String::from_utf8(bytes.to_vec()).unwrap_err().into()),
+            };
+        }
+        if let Ok(b) = ob.cast::<PyByteArray>() {
+            return Ok(ArcStr::from(String::from_utf8(b.to_vec())?));
+        }
+        Err(not_str.into())
+    }
+}
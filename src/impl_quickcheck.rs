@@ -0,0 +1,61 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+#[cfg(feature = "substr")]
+use crate::arc_str::nth_char_boundary;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use quickcheck::{Arbitrary, Gen};
+
+impl Arbitrary for ArcStr {
+    #[inline]
+    fn arbitrary(g: &mut Gen) -> Self {
+        ArcStr::from(String::arbitrary(g))
+    }
+
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(String::from(self.as_str()).shrink().map(ArcStr::from))
+    }
+}
+
+#[cfg(feature = "substr")]
+impl Arbitrary for Substr {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let parent = ArcStr::arbitrary(g);
+        let boundaries = parent.chars().count() + 1;
+        let a = usize::arbitrary(g) % boundaries;
+        let b = usize::arbitrary(g) % boundaries;
+        let start = nth_char_boundary(&parent, a.min(b));
+        let end = nth_char_boundary(&parent, a.max(b));
+        parent.substr(start..end)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        // Every candidate either has a shorter parent, or a shorter range of
+        // the same parent, so shrinking always terminates.
+        let this = self.clone();
+        // First, try dropping the part of the parent that isn't in range...
+        let detached = if self.is_full() {
+            None
+        } else {
+            Some(Substr::from(self.as_str()))
+        };
+        // ... then narrowing the range from either end...
+        let narrowed = self
+            .chars()
+            .next()
+            .map(|c| this.substr(c.len_utf8()..))
+            .into_iter()
+            .chain(
+                self.chars()
+                    .next_back()
+                    .filter(|_| self.chars().nth(1).is_some())
+                    .map(|c| this.substr(..this.len() - c.len_utf8())),
+            );
+        // ... and finally, shrinking the contents themselves.
+        let contents = String::from(self.as_str()).shrink().map(Substr::from);
+        Box::new(detached.into_iter().chain(narrowed).chain(contents))
+    }
+}
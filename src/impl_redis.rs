@@ -0,0 +1,57 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use alloc::string::String;
+use redis::{FromRedisValue, ParsingError, RedisWrite, ToRedisArgs, ToSingleRedisArg, Value};
+
+impl ToRedisArgs for ArcStr {
+    #[inline]
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_bytes());
+    }
+}
+
+impl ToSingleRedisArg for ArcStr {}
+
+impl FromRedisValue for ArcStr {
+    #[inline]
+    fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+        String::from_redis_value_ref(v).map(ArcStr::from)
+    }
+
+    #[inline]
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        String::from_redis_value(v).map(ArcStr::from)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl ToRedisArgs for Substr {
+    #[inline]
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_bytes());
+    }
+}
+
+#[cfg(feature = "substr")]
+impl ToSingleRedisArg for Substr {}
+
+#[cfg(feature = "substr")]
+impl FromRedisValue for Substr {
+    #[inline]
+    fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+        ArcStr::from_redis_value_ref(v).map(Substr::from)
+    }
+
+    #[inline]
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        ArcStr::from_redis_value(v).map(Substr::from)
+    }
+}
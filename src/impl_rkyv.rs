@@ -0,0 +1,94 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+impl Archive for ArcStr {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out);
+    }
+}
+
+impl<S: rkyv::ser::Serializer + ?Sized> Serialize<S> for ArcStr {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<ArcStr, D> for ArchivedString {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<ArcStr, D::Error> {
+        Ok(ArcStr::from(self.as_str()))
+    }
+}
+
+/// The archived form of a [`Substr`]: the entire parent string, followed by the
+/// start and end of the range within it.
+#[cfg(feature = "substr")]
+type ArchivedSubstr = (ArchivedString, rkyv::Archived<u32>, rkyv::Archived<u32>);
+
+#[cfg(feature = "substr")]
+impl Substr {
+    #[inline]
+    fn rkyv_range(&self) -> (u32, u32) {
+        let range = self.range();
+        match (u32::try_from(range.start), u32::try_from(range.end)) {
+            (Ok(start), Ok(end)) => (start, end),
+            _ => panic!("Substr range {:?} is too large to archive", range),
+        }
+    }
+}
+
+#[cfg(feature = "substr")]
+impl Archive for Substr {
+    type Archived = ArchivedSubstr;
+    type Resolver = StringResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (start, end) = self.rkyv_range();
+        let (fp, fo) = rkyv::out_field!(out.0);
+        ArchivedString::resolve_from_str(self.parent().as_str(), pos + fp, resolver, fo);
+        let (fp, fo) = rkyv::out_field!(out.1);
+        start.resolve(pos + fp, (), fo);
+        let (fp, fo) = rkyv::out_field!(out.2);
+        end.resolve(pos + fp, (), fo);
+    }
+}
+
+#[cfg(feature = "substr")]
+impl<S: rkyv::ser::Serializer + ?Sized> Serialize<S> for Substr {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        // Check the range up front, so that we fail before writing anything.
+        let _ = self.rkyv_range();
+        ArchivedString::serialize_from_str(self.parent().as_str(), serializer)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl<D: Fallible + ?Sized> Deserialize<Substr, D> for ArchivedSubstr {
+    /// If the archived range isn't valid for the parent (which only happens
+    /// with a malformed archive), this returns the entire parent instead of
+    /// panicking.
+    #[inline]
+    // `Archived<u32>` is only a distinct type with rkyv's `archive_le` or
+    // `archive_be` features.
+    #[allow(clippy::useless_conversion)]
+    fn deserialize(&self, _: &mut D) -> Result<Substr, D::Error> {
+        let parent = ArcStr::from(self.0.as_str());
+        let (start, end) = (u32::from(self.1) as usize, u32::from(self.2) as usize);
+        if parent.get(start..end).is_some() {
+            Ok(parent.substr(start..end))
+        } else {
+            Ok(Substr::full(parent))
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+// Both types are serialized as plain strings (see `impl_serde.rs`), so they
+// should describe themselves exactly as `String` does.
+macro_rules! impl_schema_as_string {
+    ($($ty:ty),+ $(,)?) => {$(
+        impl JsonSchema for $ty {
+            #[inline]
+            fn inline_schema() -> bool {
+                <String as JsonSchema>::inline_schema()
+            }
+
+            #[inline]
+            fn schema_name() -> Cow<'static, str> {
+                <String as JsonSchema>::schema_name()
+            }
+
+            #[inline]
+            fn schema_id() -> Cow<'static, str> {
+                <String as JsonSchema>::schema_id()
+            }
+
+            #[inline]
+            fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                <String as JsonSchema>::json_schema(generator)
+            }
+        }
+    )+};
+}
+
+impl_schema_as_string!(ArcStr);
+#[cfg(feature = "substr")]
+impl_schema_as_string!(Substr);
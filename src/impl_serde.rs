@@ -31,6 +31,20 @@ impl<'de> Deserialize<'de> for Substr {
     }
 }
 
+#[cfg(feature = "typed")]
+impl<Tag> Serialize for crate::TypedArcStr<Tag> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "typed")]
+impl<'de, Tag> Deserialize<'de> for crate::TypedArcStr<Tag> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        ArcStr::deserialize(d).map(crate::TypedArcStr::new)
+    }
+}
+
 struct StrVisitor<StrTy>(PhantomData<fn() -> StrTy>);
 
 impl<'de, StrTy> de::Visitor<'de> for StrVisitor<StrTy>
@@ -51,3 +65,43 @@ where
         }
     }
 }
+
+#[cfg(feature = "bytes-type")]
+impl Serialize for crate::ArcBytes {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        ser.serialize_bytes(self)
+    }
+}
+
+#[cfg(feature = "bytes-type")]
+impl<'de> Deserialize<'de> for crate::ArcBytes {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_bytes(BytesVisitor)
+    }
+}
+
+#[cfg(feature = "bytes-type")]
+struct BytesVisitor;
+
+#[cfg(feature = "bytes-type")]
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = crate::ArcBytes;
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        formatter.write_str("a byte array")
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+    // Formats without a native byte string type (JSON, for example) encode
+    // bytes as a sequence of integers.
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut v = alloc::vec::Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element::<u8>()? {
+            v.push(b);
+        }
+        Ok(v.into())
+    }
+}
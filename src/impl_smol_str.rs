@@ -0,0 +1,50 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use smol_str::SmolStr;
+
+/// `feature = "smol-str"`: Copies the data into a new `ArcStr`.
+impl From<SmolStr> for ArcStr {
+    #[inline]
+    fn from(s: SmolStr) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "smol-str"`: Copies the data into a new `ArcStr`.
+impl From<&SmolStr> for ArcStr {
+    #[inline]
+    fn from(s: &SmolStr) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+/// `feature = "smol-str"`: Converts to a `SmolStr`, which copies the data
+/// unless it's static (for example, from [`arcstr::literal!`](crate::literal)).
+impl From<ArcStr> for SmolStr {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        match ArcStr::as_static(&s) {
+            Some(st) => SmolStr::new_static(st),
+            None => SmolStr::new(s.as_str()),
+        }
+    }
+}
+
+/// `feature = "smol-str"`: Copies the data into a new `Substr`.
+#[cfg(feature = "substr")]
+impl From<SmolStr> for Substr {
+    #[inline]
+    fn from(s: SmolStr) -> Self {
+        Substr::from(s.as_str())
+    }
+}
+
+/// `feature = "smol-str"`: Copies the data into a new `SmolStr`.
+#[cfg(feature = "substr")]
+impl From<Substr> for SmolStr {
+    #[inline]
+    fn from(s: Substr) -> Self {
+        SmolStr::new(s.as_str())
+    }
+}
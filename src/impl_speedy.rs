@@ -0,0 +1,62 @@
+use super::ArcStr;
+#[cfg(feature = "substr")]
+use super::Substr;
+
+use alloc::borrow::Cow;
+use speedy::{Context, Readable, Reader, Writable, Writer};
+
+// Note: `Readable` may hand us data borrowed from the input buffer, but an
+// `ArcStr` always owns its data, so we read a `Cow<str>` (which avoids an
+// intermediate `String` when the data can be borrowed), and copy out of that.
+
+impl<'a, C: Context> Readable<'a, C> for ArcStr {
+    #[inline]
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let s: Cow<'a, str> = reader.read_value()?;
+        Ok(ArcStr::from(&*s))
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <Cow<'a, str> as Readable<'a, C>>::minimum_bytes_needed()
+    }
+}
+
+impl<C: Context> Writable<C> for ArcStr {
+    #[inline]
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        self.as_str().write_to(writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(self.as_str())
+    }
+}
+
+#[cfg(feature = "substr")]
+impl<'a, C: Context> Readable<'a, C> for Substr {
+    #[inline]
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let s: Cow<'a, str> = reader.read_value()?;
+        Ok(Substr::from(&*s))
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <Cow<'a, str> as Readable<'a, C>>::minimum_bytes_needed()
+    }
+}
+
+#[cfg(feature = "substr")]
+impl<C: Context> Writable<C> for Substr {
+    #[inline]
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        self.as_str().write_to(writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(self.as_str())
+    }
+}
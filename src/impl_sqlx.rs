@@ -0,0 +1,48 @@
+use super::ArcStr;
+
+use alloc::string::String;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type};
+
+// These are generic over the database, and just defer to the impls for
+// `String` (or `&str` when decoding, which avoids an extra copy).
+
+impl<DB: Database> Type<DB> for ArcStr
+where
+    String: Type<DB>,
+{
+    #[inline]
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+
+    #[inline]
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for ArcStr
+where
+    String: Encode<'q, DB>,
+{
+    #[inline]
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        // The buffer may outlive `self`, so we can't hand it a `&str`.
+        <String as Encode<'q, DB>>::encode(String::from(self.as_str()), buf)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for ArcStr
+where
+    &'r str: Decode<'r, DB>,
+{
+    #[inline]
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        <&'r str as Decode<'r, DB>>::decode(value).map(ArcStr::from)
+    }
+}
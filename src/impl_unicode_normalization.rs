@@ -0,0 +1,179 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use unicode_normalization::{is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick};
+use unicode_normalization::{IsNormalized, UnicodeNormalization};
+
+// Returns `None` if `s` is already normalized, and the normalized string
+// otherwise. `quick` is the result of the quick check for the normal form, and
+// `normalized` is the (lazy) iterator that produces it.
+fn normalize(
+    s: &str,
+    quick: IsNormalized,
+    normalized: impl Iterator<Item = char>,
+) -> Option<ArcStr> {
+    if quick == IsNormalized::Yes {
+        return None;
+    }
+    // The quick check can answer "maybe", in which case we only find out by
+    // normalizing.
+    let n: ArcStr = normalized.collect();
+    if n == s {
+        None
+    } else {
+        Some(n)
+    }
+}
+
+impl ArcStr {
+    /// `feature = "unicode-normalization"`: Returns this string in Unicode
+    /// Normalization Form C (canonical decomposition followed by canonical
+    /// composition).
+    ///
+    /// If the string is already in NFC, this returns a clone of `self`,
+    /// without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("e\u{301}");
+    /// assert_eq!(s.to_nfc(), "\u{e9}");
+    /// let nfc = ArcStr::from("caf\u{e9}");
+    /// assert!(ArcStr::ptr_eq(&nfc.to_nfc(), &nfc));
+    /// ```
+    pub fn to_nfc(&self) -> ArcStr {
+        normalize(self, is_nfc_quick(self.chars()), self.nfc()).unwrap_or_else(|| self.clone())
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this string in Unicode
+    /// Normalization Form D (canonical decomposition).
+    ///
+    /// If the string is already in NFD, this returns a clone of `self`,
+    /// without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("\u{e9}");
+    /// assert_eq!(s.to_nfd(), "e\u{301}");
+    /// let nfd = ArcStr::from("cafe\u{301}");
+    /// assert!(ArcStr::ptr_eq(&nfd.to_nfd(), &nfd));
+    /// ```
+    pub fn to_nfd(&self) -> ArcStr {
+        normalize(self, is_nfd_quick(self.chars()), self.nfd()).unwrap_or_else(|| self.clone())
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this string in Unicode
+    /// Normalization Form KC (compatibility decomposition followed by
+    /// canonical composition).
+    ///
+    /// If the string is already in NFKC, this returns a clone of `self`,
+    /// without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("\u{fb01}ne e\u{301}");
+    /// assert_eq!(s.to_nfkc(), "fine \u{e9}");
+    /// ```
+    pub fn to_nfkc(&self) -> ArcStr {
+        normalize(self, is_nfkc_quick(self.chars()), self.nfkc()).unwrap_or_else(|| self.clone())
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this string in Unicode
+    /// Normalization Form KD (compatibility decomposition).
+    ///
+    /// If the string is already in NFKD, this returns a clone of `self`,
+    /// without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("\u{fb01}ne \u{e9}");
+    /// assert_eq!(s.to_nfkd(), "fine e\u{301}");
+    /// ```
+    pub fn to_nfkd(&self) -> ArcStr {
+        normalize(self, is_nfkd_quick(self.chars()), self.nfkd()).unwrap_or_else(|| self.clone())
+    }
+}
+
+#[cfg(feature = "substr")]
+impl Substr {
+    /// `feature = "unicode-normalization"`: Returns this substring in Unicode
+    /// Normalization Form C. See [`ArcStr::to_nfc`].
+    ///
+    /// If the substring is already in NFC, this returns a clone of `self`
+    /// (which shares our parent). Otherwise, the result is a new string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let s = ArcStr::from("e\u{301}, e\u{301}");
+    /// assert_eq!(s.substr(..3).to_nfc(), "\u{e9}");
+    /// let ok = s.substr(3..5);
+    /// assert!(Substr::shallow_eq(&ok.to_nfc(), &ok));
+    /// ```
+    pub fn to_nfc(&self) -> Substr {
+        normalize(self, is_nfc_quick(self.chars()), self.nfc())
+            .map_or_else(|| self.clone(), Substr::from)
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this substring in Unicode
+    /// Normalization Form D. See [`ArcStr::to_nfd`].
+    ///
+    /// If the substring is already in NFD, this returns a clone of `self`
+    /// (which shares our parent). Otherwise, the result is a new string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("[\u{e9}]");
+    /// assert_eq!(s.substr(1..3).to_nfd(), "e\u{301}");
+    /// ```
+    pub fn to_nfd(&self) -> Substr {
+        normalize(self, is_nfd_quick(self.chars()), self.nfd())
+            .map_or_else(|| self.clone(), Substr::from)
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this substring in Unicode
+    /// Normalization Form KC. See [`ArcStr::to_nfkc`].
+    ///
+    /// If the substring is already in NFKC, this returns a clone of `self`
+    /// (which shares our parent). Otherwise, the result is a new string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("[\u{fb01}]");
+    /// assert_eq!(s.substr(1..4).to_nfkc(), "fi");
+    /// ```
+    pub fn to_nfkc(&self) -> Substr {
+        normalize(self, is_nfkc_quick(self.chars()), self.nfkc())
+            .map_or_else(|| self.clone(), Substr::from)
+    }
+
+    /// `feature = "unicode-normalization"`: Returns this substring in Unicode
+    /// Normalization Form KD. See [`ArcStr::to_nfkd`].
+    ///
+    /// If the substring is already in NFKD, this returns a clone of `self`
+    /// (which shares our parent). Otherwise, the result is a new string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("[\u{fb01}\u{e9}]");
+    /// assert_eq!(s.substr(1..6).to_nfkd(), "fie\u{301}");
+    /// ```
+    pub fn to_nfkd(&self) -> Substr {
+        normalize(self, is_nfkd_quick(self.chars()), self.nfkd())
+            .map_or_else(|| self.clone(), Substr::from)
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{ArcStr, Substr};
+use unicode_segmentation::{
+    GraphemeIndices, UnicodeSegmentation, UnicodeSentences, UnicodeWordIndices,
+};
+
+impl ArcStr {
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the
+    /// grapheme clusters of this string, as [`Substr`]s of `self`.
+    ///
+    /// If `extended` is true, these are the [extended grapheme clusters];
+    /// otherwise, they're the legacy grapheme clusters. See
+    /// [`UnicodeSegmentation::graphemes`] for more information.
+    ///
+    /// [extended grapheme clusters]:
+    ///     http://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("a\u{310}e\u{301}o\u{308}\u{332}\r\n");
+    /// let gs: Vec<Substr> = text.grapheme_clusters(true).collect();
+    /// assert_eq!(gs, ["a\u{310}", "e\u{301}", "o\u{308}\u{332}", "\r\n"]);
+    /// assert!(ArcStr::ptr_eq(gs[1].parent(), &text));
+    /// ```
+    #[inline]
+    pub fn grapheme_clusters(&self, extended: bool) -> ArcStrGraphemeClusters<'_> {
+        ArcStrGraphemeClusters {
+            parent: self.clone(),
+            offset: 0,
+            inner: self.as_str().grapheme_indices(extended),
+        }
+    }
+
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the words
+    /// of this string, as [`Substr`]s of `self`.
+    ///
+    /// The words are the substrings between [word boundaries] which contain
+    /// alphanumeric characters. See [`UnicodeSegmentation::unicode_words`] for
+    /// more information.
+    ///
+    /// [word boundaries]: http://www.unicode.org/reports/tr29/#Word_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("The quick (\"brown\") fox can't jump 32.3 feet, right?");
+    /// let words: Vec<Substr> = text.unicode_words().collect();
+    /// assert_eq!(
+    ///     words,
+    ///     ["The", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"],
+    /// );
+    /// assert_eq!(words[2].range(), 12..17);
+    /// ```
+    #[inline]
+    pub fn unicode_words(&self) -> ArcStrUnicodeWords<'_> {
+        ArcStrUnicodeWords {
+            parent: self.clone(),
+            offset: 0,
+            inner: self.as_str().unicode_word_indices(),
+        }
+    }
+
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the
+    /// sentences of this string, as [`Substr`]s of `self`.
+    ///
+    /// The sentences are the substrings between [sentence boundaries] which
+    /// contain alphanumeric characters. See
+    /// [`UnicodeSegmentation::unicode_sentences`] for more information.
+    ///
+    /// [sentence boundaries]:
+    ///     http://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("Mr. Fox jumped. [...] The dog was too lazy.");
+    /// let sentences: Vec<Substr> = text.unicode_sentences().collect();
+    /// assert_eq!(sentences, ["Mr. ", "Fox jumped. ", "The dog was too lazy."]);
+    /// assert_eq!(sentences[2].range(), 22..43);
+    /// ```
+    #[inline]
+    pub fn unicode_sentences(&self) -> ArcStrUnicodeSentences<'_> {
+        ArcStrUnicodeSentences {
+            parent: self.clone(),
+            base: self.as_str(),
+            offset: 0,
+            inner: self.as_str().unicode_sentences(),
+        }
+    }
+}
+
+impl Substr {
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the
+    /// grapheme clusters of this substring, as `Substr`s which share our
+    /// parent. See [`ArcStr::grapheme_clusters`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("--🇯🇵🇫🇷--");
+    /// let flags = text.substr(2..18);
+    /// let gs: Vec<Substr> = flags.grapheme_clusters(true).collect();
+    /// assert_eq!(gs, ["🇯🇵", "🇫🇷"]);
+    /// assert_eq!(gs[1].range(), 10..18);
+    /// ```
+    #[inline]
+    pub fn grapheme_clusters(&self, extended: bool) -> ArcStrGraphemeClusters<'_> {
+        ArcStrGraphemeClusters {
+            parent: self.parent().clone(),
+            offset: self.range().start,
+            inner: self.as_str().grapheme_indices(extended),
+        }
+    }
+
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the words
+    /// of this substring, as `Substr`s which share our parent. See
+    /// [`ArcStr::unicode_words`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("# Hello, world!");
+    /// let words: Vec<Substr> = text.substr(2..).unicode_words().collect();
+    /// assert_eq!(words, ["Hello", "world"]);
+    /// assert_eq!(words[1].range(), 9..14);
+    /// ```
+    #[inline]
+    pub fn unicode_words(&self) -> ArcStrUnicodeWords<'_> {
+        ArcStrUnicodeWords {
+            parent: self.parent().clone(),
+            offset: self.range().start,
+            inner: self.as_str().unicode_word_indices(),
+        }
+    }
+
+    /// `feature = "unicode-segmentation"`: Returns an iterator over the
+    /// sentences of this substring, as `Substr`s which share our parent. See
+    /// [`ArcStr::unicode_sentences`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = ArcStr::from("> One. Two.");
+    /// let sentences: Vec<Substr> = text.substr(2..).unicode_sentences().collect();
+    /// assert_eq!(sentences, ["One. ", "Two."]);
+    /// assert_eq!(sentences[1].range(), 7..11);
+    /// ```
+    #[inline]
+    pub fn unicode_sentences(&self) -> ArcStrUnicodeSentences<'_> {
+        ArcStrUnicodeSentences {
+            parent: self.parent().clone(),
+            base: self.as_str(),
+            offset: self.range().start,
+            inner: self.as_str().unicode_sentences(),
+        }
+    }
+}
+
+/// `feature = "unicode-segmentation"`: An iterator over the grapheme clusters
+/// of an [`ArcStr`] or [`Substr`], as `Substr`s.
+///
+/// Created by [`ArcStr::grapheme_clusters`] and [`Substr::grapheme_clusters`].
+#[derive(Clone, Debug)]
+pub struct ArcStrGraphemeClusters<'a> {
+    parent: ArcStr,
+    // Where the string that `inner` is iterating over starts in `parent`.
+    offset: usize,
+    inner: GraphemeIndices<'a>,
+}
+
+impl Iterator for ArcStrGraphemeClusters<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let (i, s) = self.inner.next()?;
+        let start = self.offset + i;
+        Some(self.parent.substr(start..start + s.len()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ArcStrGraphemeClusters<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Substr> {
+        let (i, s) = self.inner.next_back()?;
+        let start = self.offset + i;
+        Some(self.parent.substr(start..start + s.len()))
+    }
+}
+
+/// `feature = "unicode-segmentation"`: An iterator over the words of an
+/// [`ArcStr`] or [`Substr`], as `Substr`s.
+///
+/// Created by [`ArcStr::unicode_words`] and [`Substr::unicode_words`].
+#[derive(Debug)]
+pub struct ArcStrUnicodeWords<'a> {
+    parent: ArcStr,
+    // Where the string that `inner` is iterating over starts in `parent`.
+    offset: usize,
+    inner: UnicodeWordIndices<'a>,
+}
+
+impl Iterator for ArcStrUnicodeWords<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let (i, s) = self.inner.next()?;
+        let start = self.offset + i;
+        Some(self.parent.substr(start..start + s.len()))
+    }
+}
+
+impl DoubleEndedIterator for ArcStrUnicodeWords<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Substr> {
+        let (i, s) = self.inner.next_back()?;
+        let start = self.offset + i;
+        Some(self.parent.substr(start..start + s.len()))
+    }
+}
+
+/// `feature = "unicode-segmentation"`: An iterator over the sentences of an
+/// [`ArcStr`] or [`Substr`], as `Substr`s.
+///
+/// Created by [`ArcStr::unicode_sentences`] and [`Substr::unicode_sentences`].
+#[derive(Clone, Debug)]
+pub struct ArcStrUnicodeSentences<'a> {
+    parent: ArcStr,
+    // The string that `inner` is iterating over, and where it starts in
+    // `parent`. `UnicodeSentences` has no `_indices` variant, so we find the
+    // index of each sentence from its address in `base`.
+    base: &'a str,
+    offset: usize,
+    inner: UnicodeSentences<'a>,
+}
+
+impl Iterator for ArcStrUnicodeSentences<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let s = self.inner.next()?;
+        let start = self.offset + (s.as_ptr() as usize - self.base.as_ptr() as usize);
+        Some(self.parent.substr(start..start + s.len()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
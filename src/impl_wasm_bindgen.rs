@@ -0,0 +1,49 @@
+use super::ArcStr;
+
+use alloc::string::String;
+use wasm_bindgen::convert::{FromWasmAbi, IntoWasmAbi, OptionFromWasmAbi, OptionIntoWasmAbi};
+use wasm_bindgen::describe::WasmDescribe;
+
+// These all go through `String`, so that on the JS side an `ArcStr` is just a
+// `string` (and is typed that way in generated TypeScript). This does mean a
+// copy in each direction, which is unavoidable: the JS glue takes ownership
+// of (and frees) the buffer it's handed, and hands us a fresh one.
+
+impl WasmDescribe for ArcStr {
+    #[inline]
+    fn describe() {
+        <String as WasmDescribe>::describe()
+    }
+}
+
+impl IntoWasmAbi for ArcStr {
+    type Abi = <String as IntoWasmAbi>::Abi;
+
+    #[inline]
+    fn into_abi(self) -> Self::Abi {
+        String::from(self.as_str()).into_abi()
+    }
+}
+
+impl OptionIntoWasmAbi for ArcStr {
+    #[inline]
+    fn none() -> Self::Abi {
+        <String as OptionIntoWasmAbi>::none()
+    }
+}
+
+impl FromWasmAbi for ArcStr {
+    type Abi = <String as FromWasmAbi>::Abi;
+
+    #[inline]
+    unsafe fn from_abi(js: Self::Abi) -> Self {
+        ArcStr::from(String::from_abi(js))
+    }
+}
+
+impl OptionFromWasmAbi for ArcStr {
+    #[inline]
+    fn is_none(abi: &Self::Abi) -> bool {
+        <String as OptionFromWasmAbi>::is_none(abi)
+    }
+}
@@ -0,0 +1,179 @@
+use super::substr::CharIndices;
+use super::{ArcStr, Substr};
+
+use core::str::FromStr;
+use winnow::stream::{
+    AsBStr, Compare, CompareResult, FindSlice, Location, Needed, Offset, ParseSlice, SliceLen,
+    Stream, StreamIsPartial, UpdateSlice,
+};
+
+// As with the `nom` impls, these mostly defer to the impls for `&str`, but
+// every slice winnow produces is a `Substr` of the same parent, rather than a
+// copy.
+
+impl Stream for Substr {
+    type Token = char;
+    type Slice = Substr;
+    type IterOffsets = CharIndices;
+    // A `Substr` is cheap to clone, and already knows where it is within its
+    // parent, so it can serve as its own checkpoint.
+    type Checkpoint = Substr;
+
+    #[inline]
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        CharIndices::new(self.clone())
+    }
+
+    #[inline]
+    fn eof_offset(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_token(&mut self) -> Option<Self::Token> {
+        let c = self.peek_token()?;
+        *self = self.substr(c.len_utf8()..);
+        Some(c)
+    }
+
+    #[inline]
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.chars().next()
+    }
+
+    #[inline]
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.find(predicate)
+    }
+
+    #[inline]
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.as_str().offset_at(tokens)
+    }
+
+    #[inline]
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let (slice, next) = self.split_at(offset);
+        *self = next;
+        slice
+    }
+
+    #[inline]
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        self.substr(..offset)
+    }
+
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.clone()
+    }
+
+    #[inline]
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        *self = checkpoint.clone();
+    }
+
+    #[inline]
+    fn trace(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:#?}", self.as_str())
+    }
+}
+
+impl StreamIsPartial for Substr {
+    type PartialState = ();
+
+    #[inline]
+    fn complete(&mut self) -> Self::PartialState {}
+
+    #[inline]
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    #[inline]
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for Substr {
+    #[inline]
+    fn offset_from(&self, start: &Self) -> usize {
+        debug_assert!(ArcStr::ptr_eq(self.parent(), start.parent()));
+        self.range().start - start.range().start
+    }
+}
+
+/// Locations are byte offsets within the parent `ArcStr`, not within the
+/// `Substr` that parsing started from: as parsing advances, the input is just
+/// a narrower `Substr` of the same parent, with nowhere to remember where it
+/// started. Wrap the input in a `winnow::stream::LocatingSlice` for the
+/// latter.
+impl Location for Substr {
+    #[inline]
+    fn previous_token_end(&self) -> usize {
+        self.range().start
+    }
+
+    #[inline]
+    fn current_token_start(&self) -> usize {
+        self.range().start
+    }
+}
+
+impl SliceLen for Substr {
+    #[inline]
+    fn slice_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl AsBStr for Substr {
+    #[inline]
+    fn as_bstr(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl UpdateSlice for Substr {
+    #[inline]
+    fn update_slice(self, inner: Self::Slice) -> Self {
+        inner
+    }
+}
+
+impl<'b> Compare<&'b str> for Substr {
+    #[inline]
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.as_str().compare(t)
+    }
+}
+
+impl Compare<char> for Substr {
+    #[inline]
+    fn compare(&self, t: char) -> CompareResult {
+        self.as_str().compare(t)
+    }
+}
+
+impl<'s> FindSlice<&'s str> for Substr {
+    #[inline]
+    fn find_slice(&self, substr: &'s str) -> Option<core::ops::Range<usize>> {
+        self.as_str().find_slice(substr)
+    }
+}
+
+impl FindSlice<char> for Substr {
+    #[inline]
+    fn find_slice(&self, substr: char) -> Option<core::ops::Range<usize>> {
+        self.as_str().find_slice(substr)
+    }
+}
+
+impl<R: FromStr> ParseSlice<R> for Substr {
+    #[inline]
+    fn parse_slice(&self) -> Option<R> {
+        self.parse().ok()
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{ArcStr, WeakArcStr};
+use core::sync::atomic::{AtomicPtr, Ordering};
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// `feature = "intern"`: An [`ArcStr`] which is deduplicated through a global
+/// pool.
+///
+/// Creating an `InternedArcStr` returns the existing one if an
+/// `InternedArcStr` with the same contents is still alive, so there's only
+/// ever one allocation for each string. As a result, comparing two
+/// `InternedArcStr`s for equality is just a pointer comparison (and hashing
+/// one only hashes the pointer).
+///
+/// The pool only holds [`WeakArcStr`]s, and a string is removed from it when
+/// the last `InternedArcStr` for it is dropped. Note that `ArcStr`s obtained
+/// from an `InternedArcStr` (via [`InternedArcStr::as_arcstr`] or `From`) do
+/// keep the string alive, but it may stay in the pool until it's interned
+/// again after they're dropped.
+///
+/// The pool is protected by a single `Mutex`, which is locked when creating an
+/// `InternedArcStr`, and when dropping one (but not when cloning one, or for
+/// any other operation).
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, InternedArcStr};
+/// let a = InternedArcStr::new("foo");
+/// let b = InternedArcStr::new(&String::from("foo"));
+/// assert!(ArcStr::ptr_eq(a.as_arcstr(), b.as_arcstr()));
+/// assert_eq!(a, b);
+/// assert_ne!(a, InternedArcStr::new("bar"));
+/// ```
+#[derive(Clone)]
+pub struct InternedArcStr(ArcStr);
+
+impl InternedArcStr {
+    /// Returns the interned string with the contents of `s`, adding it to the
+    /// pool if it isn't present already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::InternedArcStr;
+    /// let a = InternedArcStr::new("abc");
+    /// assert_eq!(a, "abc");
+    /// assert!(InternedArcStr::ptr_eq(&a, &InternedArcStr::new("abc")));
+    /// ```
+    pub fn new(s: &str) -> Self {
+        let mut pool = pool();
+        if let Some(existing) = pool.get(s).and_then(|e| e.0.upgrade()) {
+            return Self(existing);
+        }
+        let a = ArcStr::from(s);
+        // May replace an entry whose strings have all been dropped.
+        pool.replace(Entry(ArcStr::downgrade(&a)));
+        Self(a)
+    }
+
+    /// Returns the interned string with the contents of `s` if one exists,
+    /// without adding it to the pool otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::InternedArcStr;
+    /// assert_eq!(InternedArcStr::get("never interned"), None);
+    /// let a = InternedArcStr::new("interned");
+    /// assert_eq!(InternedArcStr::get("interned"), Some(a.clone()));
+    /// drop(a);
+    /// assert_eq!(InternedArcStr::get("interned"), None);
+    /// ```
+    pub fn get(s: &str) -> Option<Self> {
+        pool().get(s).and_then(|e| e.0.upgrade()).map(Self)
+    }
+
+    /// Returns the underlying `ArcStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, InternedArcStr};
+    /// let a = InternedArcStr::new("foo");
+    /// let s: &ArcStr = a.as_arcstr();
+    /// assert_eq!(s, "foo");
+    /// ```
+    #[inline]
+    pub fn as_arcstr(&self) -> &ArcStr {
+        &self.0
+    }
+
+    /// Extract a string slice containing our data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::InternedArcStr;
+    /// assert_eq!(InternedArcStr::new("foo").as_str(), "foo");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if the two `InternedArcStr`s point to the same allocation,
+    /// which for interned strings is the same as having the same contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::InternedArcStr;
+    /// let a = InternedArcStr::new("foo");
+    /// assert!(InternedArcStr::ptr_eq(&a, &InternedArcStr::new("foo")));
+    /// assert!(!InternedArcStr::ptr_eq(&a, &InternedArcStr::new("bar")));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(lhs: &Self, rhs: &Self) -> bool {
+        ArcStr::ptr_eq(&lhs.0, &rhs.0)
+    }
+}
+
+impl Drop for InternedArcStr {
+    fn drop(&mut self) {
+        // Our string must be dropped while holding the lock: otherwise two
+        // threads dropping the last two `InternedArcStr`s could each see that
+        // the other one is still alive, and neither would remove the entry.
+        let mut pool = pool();
+        let s = core::mem::take(&mut self.0);
+        if ArcStr::strong_count(&s) == Some(1) {
+            let ours = pool
+                .get(s.as_str())
+                .map_or(false, |e| e.0.contents().as_ptr() == s.as_ptr());
+            if ours {
+                pool.remove(s.as_str());
+            }
+        }
+        drop(s);
+    }
+}
+
+impl core::ops::Deref for InternedArcStr {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for InternedArcStr {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        Self::ptr_eq(self, o)
+    }
+}
+
+impl Eq for InternedArcStr {}
+
+impl PartialEq<str> for InternedArcStr {
+    #[inline]
+    fn eq(&self, o: &str) -> bool {
+        self.as_str() == o
+    }
+}
+
+impl PartialEq<&str> for InternedArcStr {
+    #[inline]
+    fn eq(&self, o: &&str) -> bool {
+        self.as_str() == *o
+    }
+}
+
+impl PartialEq<ArcStr> for InternedArcStr {
+    #[inline]
+    fn eq(&self, o: &ArcStr) -> bool {
+        self.0 == *o
+    }
+}
+
+impl PartialOrd for InternedArcStr {
+    #[inline]
+    fn partial_cmp(&self, o: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+impl Ord for InternedArcStr {
+    #[inline]
+    fn cmp(&self, o: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(o.as_str())
+    }
+}
+
+/// Only hashes the pointer, which is consistent with `Eq`. Note that this means
+/// `InternedArcStr` doesn't implement `Borrow<str>`.
+impl core::hash::Hash for InternedArcStr {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.0.as_ptr().hash(h)
+    }
+}
+
+impl core::fmt::Debug for InternedArcStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for InternedArcStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl AsRef<str> for InternedArcStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl From<&str> for InternedArcStr {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<&ArcStr> for InternedArcStr {
+    #[inline]
+    fn from(s: &ArcStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<InternedArcStr> for ArcStr {
+    #[inline]
+    fn from(s: InternedArcStr) -> Self {
+        s.0.clone()
+    }
+}
+
+impl From<&InternedArcStr> for ArcStr {
+    #[inline]
+    fn from(s: &InternedArcStr) -> Self {
+        s.0.clone()
+    }
+}
+
+// An entry in the pool, which hashes and compares by the string contents.
+struct Entry(WeakArcStr);
+
+impl PartialEq for Entry {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        self.0.contents() == o.0.contents()
+    }
+}
+
+impl Eq for Entry {}
+
+impl core::hash::Hash for Entry {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.0.contents().hash(h)
+    }
+}
+
+impl core::borrow::Borrow<str> for Entry {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.0.contents()
+    }
+}
+
+type Pool = Mutex<HashSet<Entry>>;
+
+fn pool() -> MutexGuard<'static, HashSet<Entry>> {
+    // `Mutex::new` isn't `const` on our MSRV, so lazily allocate the pool, and
+    // never free it.
+    static POOL: AtomicPtr<Pool> = AtomicPtr::new(core::ptr::null_mut());
+    let mut p = POOL.load(Ordering::Acquire);
+    if p.is_null() {
+        let new = Box::into_raw(Box::new(Mutex::new(HashSet::new())));
+        match POOL.compare_exchange(p, new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => p = new,
+            Err(winner) => {
+                drop(unsafe { Box::from_raw(new) });
+                p = winner;
+            }
+        }
+    }
+    // Nothing can panic while the lock is held (short of allocation failure),
+    // so the pool can't be left in a bad state.
+    unsafe { &*p }
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,135 @@
+use crate::ArcStr;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use std::sync::Once;
+
+/// `feature = "std"`: An [`ArcStr`] which is computed the first time it's
+/// accessed, and which can be used as a `static`.
+///
+/// This is essentially a `std::sync::LazyLock<ArcStr>` (or
+/// `once_cell::sync::Lazy<ArcStr>`), but works on our MSRV. If the string is a
+/// literal you should use [`arcstr::literal!`](crate::literal) instead, which
+/// doesn't need any initialization at all.
+///
+/// The initializer runs at most once: if several threads access the string at
+/// the same time, one of them runs it and the others wait for the result. If
+/// the initializer panics, the panic propagates, and all later accesses will
+/// panic too.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, LazyArcStr};
+/// static GREETING: LazyArcStr = LazyArcStr::new(|| arcstr::format!("hello {}", 1 + 1));
+///
+/// assert_eq!(LazyArcStr::get(&GREETING), None);
+/// assert_eq!(*GREETING, "hello 2");
+/// let s: ArcStr = GREETING.clone();
+/// assert_eq!(LazyArcStr::get(&GREETING), Some(&s));
+/// ```
+pub struct LazyArcStr<F = fn() -> ArcStr> {
+    once: Once,
+    value: UnsafeCell<MaybeUninit<ArcStr>>,
+    init: F,
+}
+
+// `ArcStr` is `Send + Sync`, so this is only about the initializer, which may
+// be called from any thread.
+unsafe impl<F: Send + Sync> Sync for LazyArcStr<F> {}
+
+impl<F> LazyArcStr<F> {
+    /// Create a new `LazyArcStr`, which will call `init` to produce its value.
+    ///
+    /// This is a `const fn`, so it can be used to initialize a `static`. Note
+    /// that only closures which don't capture anything can be used in a
+    /// `static` of the default type, `LazyArcStr<fn() -> ArcStr>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::LazyArcStr;
+    /// let suffix = "!";
+    /// let lazy = LazyArcStr::new(move || arcstr::format!("wow{}", suffix));
+    /// assert_eq!(*lazy, "wow!");
+    /// ```
+    #[inline]
+    pub const fn new(init: F) -> Self {
+        Self {
+            once: Once::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+            init,
+        }
+    }
+
+    /// Returns the value if it has been initialized already, and `None`
+    /// otherwise. This never runs the initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::LazyArcStr;
+    /// let lazy = LazyArcStr::new(|| arcstr::literal!("foo"));
+    /// assert_eq!(LazyArcStr::get(&lazy), None);
+    /// LazyArcStr::force(&lazy);
+    /// assert_eq!(LazyArcStr::get(&lazy).unwrap(), "foo");
+    /// ```
+    #[inline]
+    pub fn get(this: &Self) -> Option<&ArcStr> {
+        if this.once.is_completed() {
+            // Safety: the value is written before `once` completes, and never
+            // again after.
+            Some(unsafe { (*this.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<F: Fn() -> ArcStr + Send + Sync> LazyArcStr<F> {
+    /// Returns the value, running the initializer first if it hasn't been run
+    /// already. This is the same as dereferencing `this`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::LazyArcStr;
+    /// let lazy = LazyArcStr::new(|| arcstr::literal!("foo"));
+    /// assert_eq!(LazyArcStr::force(&lazy), "foo");
+    /// ```
+    #[inline]
+    pub fn force(this: &Self) -> &ArcStr {
+        this.once.call_once(|| {
+            let value = (this.init)();
+            // Safety: `call_once` guarantees that nobody else is accessing the
+            // value (and `get` won't until we've completed).
+            unsafe { (*this.value.get()).as_mut_ptr().write(value) };
+        });
+        // Safety: `call_once` only returns once the value is written.
+        unsafe { (*this.value.get()).assume_init_ref() }
+    }
+}
+
+impl<F: Fn() -> ArcStr + Send + Sync> core::ops::Deref for LazyArcStr<F> {
+    type Target = ArcStr;
+    #[inline]
+    fn deref(&self) -> &ArcStr {
+        Self::force(self)
+    }
+}
+
+impl<F> Drop for LazyArcStr<F> {
+    fn drop(&mut self) {
+        if self.once.is_completed() {
+            unsafe { (*self.value.get()).as_mut_ptr().drop_in_place() };
+        }
+    }
+}
+
+impl<F> core::fmt::Debug for LazyArcStr<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match Self::get(self) {
+            Some(s) => f.debug_tuple("LazyArcStr").field(s).finish(),
+            None => f.write_str("LazyArcStr(<uninit>)"),
+        }
+    }
+}
@@ -75,15 +75,120 @@ pub use core;
 
 #[macro_use]
 mod mac;
+#[cfg(feature = "bytes-type")]
+mod arc_bytes;
 mod arc_str;
+#[cfg(feature = "substr")]
+mod arc_str_vec;
+#[cfg(feature = "actix-web")]
+mod impl_actix_web;
+#[cfg(feature = "arbitrary")]
+mod impl_arbitrary;
+#[cfg(feature = "axum")]
+mod impl_axum;
+#[cfg(feature = "borsh")]
+mod impl_borsh;
+#[cfg(any(feature = "bytes", feature = "axum", feature = "actix-web"))]
+mod impl_bytes;
+#[cfg(feature = "compact-str")]
+mod impl_compact_str;
+#[cfg(feature = "defmt")]
+mod impl_defmt;
+#[cfg(feature = "diesel")]
+mod impl_diesel;
+#[cfg(feature = "encoding")]
+mod impl_encoding_rs;
+#[cfg(feature = "http")]
+mod impl_http;
+#[cfg(feature = "kstring")]
+mod impl_kstring;
+#[cfg(all(feature = "nom", feature = "substr"))]
+mod impl_nom;
+#[cfg(feature = "pyo3")]
+mod impl_pyo3;
+#[cfg(feature = "quickcheck")]
+mod impl_quickcheck;
+#[cfg(feature = "redis")]
+mod impl_redis;
+#[cfg(feature = "rkyv")]
+mod impl_rkyv;
+#[cfg(feature = "schemars")]
+mod impl_schemars;
 #[cfg(feature = "serde")]
 mod impl_serde;
-pub use arc_str::ArcStr;
+#[cfg(feature = "smol-str")]
+mod impl_smol_str;
+#[cfg(feature = "speedy")]
+mod impl_speedy;
+#[cfg(feature = "sqlx")]
+mod impl_sqlx;
+#[cfg(feature = "unicode-normalization")]
+mod impl_unicode_normalization;
+#[cfg(all(feature = "unicode-segmentation", feature = "substr"))]
+mod impl_unicode_segmentation;
+#[cfg(feature = "wasm-bindgen")]
+mod impl_wasm_bindgen;
+#[cfg(all(feature = "winnow", feature = "substr"))]
+mod impl_winnow;
+#[cfg(feature = "bytes-type")]
+pub use arc_bytes::ArcBytes;
+#[cfg(feature = "std")]
+pub use arc_str::NotUnicodeError;
+pub use arc_str::{ArcStr, ArcStrCharBoundaries, WeakArcStr};
+#[cfg(feature = "substr")]
+pub use arc_str_vec::{ArcStrVec, ArcStrVecIter};
+#[cfg(all(feature = "unicode-segmentation", feature = "substr"))]
+pub use impl_unicode_segmentation::{
+    ArcStrGraphemeClusters, ArcStrUnicodeSentences, ArcStrUnicodeWords,
+};
+
+#[cfg(feature = "intern")]
+mod intern;
+#[cfg(feature = "intern")]
+pub use intern::InternedArcStr;
+
+#[cfg(feature = "std")]
+mod lazy;
+#[cfg(feature = "std")]
+pub use lazy::LazyArcStr;
+
+#[cfg(feature = "map")]
+mod map;
+#[cfg(feature = "map")]
+pub use map::{LazyArcStrMap, LazyArcStrMapIter};
+
+#[cfg(feature = "pool-alloc")]
+mod pool;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+pub use reader::ArcStrReader;
+#[cfg(all(feature = "std", feature = "substr"))]
+pub use reader::SubstrReader;
+
+#[cfg(feature = "rc-str")]
+mod rc_str;
+#[cfg(feature = "rc-str")]
+pub use rc_str::RcStr;
 
 #[cfg(feature = "substr")]
 mod substr;
 #[cfg(feature = "substr")]
-pub use substr::Substr;
+pub use substr::{
+    Substr, SubstrChars, SubstrLines, SubstrMatches, SubstrRSplitN, SubstrSplit,
+    SubstrSplitInclusive, SubstrSplitN,
+};
+
+#[cfg(feature = "substr")]
+pub mod testing;
+
+#[cfg(feature = "typed")]
+mod typed;
+#[cfg(feature = "typed")]
+pub use typed::TypedArcStr;
 
 // Not public API, exists for macros
 #[doc(hidden)]
@@ -107,5 +212,7 @@ pub mod _private {
         pub a: &'static Arr,
     }
     pub use crate::arc_str::StaticArcStrInner;
+    #[cfg(feature = "rc-str")]
+    pub use crate::rc_str::StaticRcStrInner;
     pub use core::primitive::{str, u8};
 }
@@ -25,14 +25,28 @@
 /// const VERY_IMPORTANT_FILE: ArcStr =
 ///     arcstr::literal!(include_str!("./very-important.txt"));
 /// ```
+///
+/// The macro works inside generic functions and methods, and inside closures.
+/// However, the argument is evaluated as a `const`, so it can't refer to
+/// generic parameters, function arguments, or values captured by a closure.
+///
+/// ```
+/// # use arcstr::ArcStr;
+/// fn name_of<T>(_: &T) -> ArcStr {
+///     arcstr::literal!("a thing")
+/// }
+/// assert_eq!(name_of(&123), "a thing");
+/// ```
 #[macro_export]
 macro_rules! literal {
     ($text:expr $(,)?) => {{
         // Note: extra scope to reduce the size of what's in `$text`'s scope
         // (note that consts in macros dont have hygene the way let does).
+        #[allow(non_upper_case_globals, dead_code)]
         const __TEXT: &$crate::_private::str = $text;
         {
-            #[allow(clippy::declare_interior_mutable_const)]
+            #[allow(non_upper_case_globals, dead_code)]
+            #[allow(clippy::declare_interior_mutable_const, clippy::borrow_interior_mutable_const)]
             const SI: &$crate::_private::StaticArcStrInner<[$crate::_private::u8; __TEXT.len()]> = unsafe {
                 &$crate::_private::StaticArcStrInner {
                     len_flag: match $crate::_private::StaticArcStrInner::<[$crate::_private::u8; __TEXT.len()]>::encode_len(__TEXT.len()) {
@@ -40,6 +54,7 @@ macro_rules! literal {
                         None => $crate::core::panic!("impossibly long length")
                     },
                     count_flag: $crate::_private::StaticArcStrInner::<[$crate::_private::u8; __TEXT.len()]>::STATIC_COUNT_VALUE,
+                    weak_count: 0,
                     // See comment for `_private::ConstPtrDeref` for what the hell's
                     // going on here.
                     data: *$crate::_private::ConstPtrDeref::<[$crate::_private::u8; __TEXT.len()]> {
@@ -49,18 +64,117 @@ macro_rules! literal {
                     // data: __TEXT.as_ptr().cast::<[$crate::_private::u8; __TEXT.len()]>().read(),
                 }
             };
-            #[allow(clippy::declare_interior_mutable_const)]
+            #[allow(non_upper_case_globals, dead_code)]
+            #[allow(clippy::declare_interior_mutable_const, clippy::borrow_interior_mutable_const)]
             const S: $crate::ArcStr = unsafe { $crate::ArcStr::_private_new_from_static_data(SI) };
             S
         }
     }};
 }
 
+/// `feature = "rc-str"`: Create a const [`RcStr`](crate::RcStr) from a string
+/// literal.
+///
+/// This is the [`RcStr`](crate::RcStr) version of
+/// [`arcstr::literal!`](crate::literal), and works the same way: the result
+/// requires no heap allocation, and cloning or dropping it doesn't touch any
+/// reference count.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::RcStr;
+/// const MY_RCSTR: RcStr = arcstr::rc_literal!("testing testing");
+/// assert_eq!(MY_RCSTR, "testing testing");
+/// assert!(RcStr::is_static(&MY_RCSTR));
+/// ```
+#[macro_export]
+#[cfg(feature = "rc-str")]
+macro_rules! rc_literal {
+    ($text:expr $(,)?) => {{
+        #[allow(non_upper_case_globals, dead_code)]
+        const __TEXT: &$crate::_private::str = $text;
+        {
+            #[allow(non_upper_case_globals, dead_code)]
+            const SI: &$crate::_private::StaticRcStrInner<[$crate::_private::u8; __TEXT.len()]> = unsafe {
+                &$crate::_private::StaticRcStrInner {
+                    len_flag: match $crate::_private::StaticRcStrInner::<[$crate::_private::u8; __TEXT.len()]>::encode_len(__TEXT.len()) {
+                        Some(len) => len,
+                        None => $crate::core::panic!("impossibly long length")
+                    },
+                    count: 0,
+                    // See `arcstr::literal!`.
+                    data: *$crate::_private::ConstPtrDeref::<[$crate::_private::u8; __TEXT.len()]> {
+                        p: __TEXT.as_ptr(),
+                    }
+                    .a,
+                }
+            };
+            #[allow(non_upper_case_globals, dead_code)]
+            const S: $crate::RcStr = unsafe { $crate::RcStr::_private_new_from_static_data(SI) };
+            S
+        }
+    }};
+}
+
+/// `feature = "bytes-type"`: Create a const [`ArcBytes`](crate::ArcBytes)
+/// from a byte string literal.
+///
+/// This is the [`ArcBytes`](crate::ArcBytes) version of
+/// [`arcstr::literal!`](crate::literal), and works the same way: the result
+/// requires no heap allocation, and cloning or dropping it doesn't touch any
+/// reference count. The argument may be any constant `&[u8]` (or `&[u8; N]`),
+/// such as the result of `include_bytes!`.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::ArcBytes;
+/// const MY_BYTES: ArcBytes = arcstr::literal_bytes!(b"\x00\xffabc");
+/// assert_eq!(MY_BYTES, b"\x00\xffabc");
+/// assert!(ArcBytes::is_static(&MY_BYTES));
+/// ```
+#[macro_export]
+#[cfg(feature = "bytes-type")]
+macro_rules! literal_bytes {
+    ($bytes:expr $(,)?) => {{
+        #[allow(non_upper_case_globals, dead_code)]
+        const __BYTES: &[$crate::_private::u8] = $bytes;
+        {
+            #[allow(non_upper_case_globals, dead_code)]
+            #[allow(clippy::declare_interior_mutable_const, clippy::borrow_interior_mutable_const)]
+            const SI: &$crate::_private::StaticArcStrInner<[$crate::_private::u8; __BYTES.len()]> = unsafe {
+                &$crate::_private::StaticArcStrInner {
+                    len_flag: match $crate::_private::StaticArcStrInner::<[$crate::_private::u8; __BYTES.len()]>::encode_len(__BYTES.len()) {
+                        Some(len) => len,
+                        None => $crate::core::panic!("impossibly long length")
+                    },
+                    count_flag: $crate::_private::StaticArcStrInner::<[$crate::_private::u8; __BYTES.len()]>::STATIC_COUNT_VALUE,
+                    weak_count: 0,
+                    // See `arcstr::literal!`.
+                    data: *$crate::_private::ConstPtrDeref::<[$crate::_private::u8; __BYTES.len()]> {
+                        p: __BYTES.as_ptr(),
+                    }
+                    .a,
+                }
+            };
+            #[allow(non_upper_case_globals, dead_code)]
+            #[allow(clippy::declare_interior_mutable_const, clippy::borrow_interior_mutable_const)]
+            const S: $crate::ArcBytes = unsafe { $crate::ArcBytes::_private_new_from_static_data(SI) };
+            S
+        }
+    }};
+}
+
 /// Conceptually equivalent to `ArcStr::from(format!("...", args...))`.
 ///
 /// In the future, this will be implemented in such a way to avoid an additional
 /// string copy which is required by the `from` operation.
 ///
+/// The arguments are forwarded to [`core::format_args!`] untouched, so
+/// everything it supports works here too, including implicitly captured
+/// identifiers (`arcstr::format!("{name}")`) on Rust 1.58 and later.
+///
 /// # Example
 ///
 /// ```
@@ -74,6 +188,78 @@ macro_rules! format {
     };
 }
 
+/// Create a const [`ArcStr`](crate::ArcStr) by concatenating literals at
+/// compile time.
+///
+/// This is equivalent to `arcstr::literal!(core::concat!(...))`, and so the
+/// result is a static `ArcStr` which requires no heap allocation, exactly as
+/// with [`arcstr::literal!`](crate::literal).
+///
+/// The arguments are passed directly to [`core::concat!`], so the same
+/// restrictions apply: each one must be a literal (of any type `concat!`
+/// accepts), or a macro invocation which expands to one, such as
+/// `include_str!`. Notably, named `&str` constants are not accepted.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::ArcStr;
+/// const GREETING: ArcStr = arcstr::concat!("hello", ", ", "world", '!');
+/// assert_eq!(GREETING, "hello, world!");
+/// assert!(ArcStr::is_static(&GREETING));
+///
+/// let versioned = arcstr::concat!("v", 1, ".", 2);
+/// assert_eq!(versioned, "v1.2");
+/// ```
+#[macro_export]
+macro_rules! concat {
+    ($($toks:tt)*) => {
+        $crate::literal!($crate::core::concat!($($toks)*))
+    };
+}
+
+/// Create a const [`ArcStr`](crate::ArcStr) by joining literals with a
+/// separator at compile time.
+///
+/// The first argument is the separator, and the remaining arguments are the
+/// items to join. For example, `arcstr::join!(", ", "a", "b")` is equivalent
+/// to `arcstr::concat!("a", ", ", "b")`, and produces a static `ArcStr` which
+/// requires no heap allocation.
+///
+/// As with [`arcstr::concat!`](crate::concat), the separator and all items
+/// must be literals (or macros that expand to them, like `include_str!`). At
+/// least one item must be provided after the separator.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::ArcStr;
+/// const GREETING: ArcStr = arcstr::join!(" ", "hello", "world");
+/// assert_eq!(GREETING, "hello world");
+/// assert!(ArcStr::is_static(&GREETING));
+///
+/// assert_eq!(arcstr::join!(", ", "a", "b", "c"), "a, b, c");
+/// // A single item is left as-is.
+/// assert_eq!(arcstr::join!(", ", "a"), "a");
+/// ```
+///
+/// Providing only a separator is an error:
+///
+/// ```compile_fail
+/// let nothing = arcstr::join!(", ");
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($sep:expr $(,)?) => {
+        $crate::core::compile_error!(
+            "`arcstr::join!` requires at least one item to join after the separator"
+        )
+    };
+    ($sep:expr, $first:expr $(, $rest:expr)* $(,)?) => {
+        $crate::literal!($crate::core::concat!($first $(, $sep, $rest)*))
+    };
+}
+
 /// `feature = "substr"`: Create a `const` [`Substr`][crate::Substr].
 ///
 /// This is a wrapper that initializes a `Substr` over the entire contents of a
@@ -97,18 +283,97 @@ macro_rules! format {
 /// // Or, just in normal expressions.
 /// assert_eq!("Wow!", literal_substr!("Wow!"));
 /// ```
+///
+/// An optional second argument may be provided to restrict the `Substr` to a
+/// byte range within the literal. This must be a `Range<usize>` (e.g.
+/// `start..end`) which can be evaluated in a `const`. The parent `ArcStr` still
+/// holds the entire literal.
+///
+/// ```
+/// use arcstr::{Substr, literal_substr};
+/// const HELLO: Substr = literal_substr!("hello world", 0..5);
+/// assert_eq!(HELLO, "hello");
+/// assert_eq!(HELLO.parent(), "hello world");
+/// ```
+///
+/// The range is checked at compile time, in the same way as
+/// [`ArcStr::substr`](crate::ArcStr::substr) checks it at runtime. Ranges
+/// which are out of bounds, backwards, or which aren't on char boundaries are a
+/// compile error:
+///
+/// ```compile_fail
+/// const NOPE: arcstr::Substr = arcstr::literal_substr!("🙀", 0..1);
+/// ```
 #[macro_export]
 #[cfg(feature = "substr")]
 macro_rules! literal_substr {
     ($text:expr $(,)?) => {{
+        #[allow(dead_code)]
         const __S: &$crate::_private::str = $text;
         {
+            #[allow(
+                dead_code,
+                clippy::declare_interior_mutable_const,
+                clippy::borrow_interior_mutable_const
+            )]
             const PARENT: $crate::ArcStr = $crate::literal!(__S);
+            #[allow(
+                dead_code,
+                clippy::declare_interior_mutable_const,
+                clippy::borrow_interior_mutable_const
+            )]
             const SUBSTR: $crate::Substr =
                 unsafe { $crate::Substr::from_parts_unchecked(PARENT, 0..__S.len()) };
             SUBSTR
         }
     }};
+    ($text:expr, $range:expr $(,)?) => {{
+        #[allow(dead_code)]
+        const __S: &$crate::_private::str = $text;
+        {
+            #[allow(
+                dead_code,
+                clippy::declare_interior_mutable_const,
+                clippy::borrow_interior_mutable_const
+            )]
+            const PARENT: $crate::ArcStr = $crate::literal!(__S);
+            #[allow(
+                dead_code,
+                clippy::declare_interior_mutable_const,
+                clippy::borrow_interior_mutable_const
+            )]
+            const SUBSTR: $crate::Substr = unsafe {
+                $crate::Substr::from_parts_unchecked(
+                    PARENT,
+                    $crate::Substr::_private_check_const_range(__S, $range),
+                )
+            };
+            SUBSTR
+        }
+    }};
+}
+
+/// `feature = "typed"`: Create a const [`TypedArcStr`](crate::TypedArcStr)
+/// from a string literal and a tag type.
+///
+/// This is [`arcstr::literal!`](crate::literal), wrapped in a `TypedArcStr`, so
+/// the result doesn't need a heap allocation either.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, TypedArcStr};
+/// enum TableName {}
+/// const USERS: TypedArcStr<TableName> = arcstr::typed_literal!("users", TableName);
+/// assert_eq!(USERS, "users");
+/// assert!(ArcStr::is_static(&USERS));
+/// ```
+#[macro_export]
+#[cfg(feature = "typed")]
+macro_rules! typed_literal {
+    ($text:expr, $tag:ty $(,)?) => {
+        $crate::TypedArcStr::<$tag>::new($crate::literal!($text))
+    };
 }
 
 #[cfg(test)]
@@ -122,6 +387,11 @@ mod test {
             let substr = literal_substr!("bar");
             assert_eq!(substr, "bar");
         }
+        #[cfg(feature = "typed")]
+        {
+            let typed = typed_literal!("baz", ());
+            assert_eq!(typed, "baz");
+        }
         // Loom doesn't like it if you do things outside `loom::model`, AFAICT.
         // These calls produce error messages from inside `libstd` about
         // accessing thread_locals that haven't been initialized.
@@ -0,0 +1,264 @@
+use crate::ArcStr;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+
+/// Maps with at most this many entries find keys by pointer with a linear
+/// scan, rather than using `by_ptr`.
+const SMALL: usize = 16;
+
+/// `feature = "map"`: A map with [`ArcStr`] keys, which can find a key without
+/// hashing its contents when given the same `ArcStr` (by pointer) that was
+/// used to insert it.
+///
+/// This is intended for cases where the same handful of `ArcStr`s (for
+/// example, constants created with [`arcstr::literal!`](crate::literal), or
+/// strings which were interned up front) are used as keys over and over.
+/// Lookups with [`LazyArcStrMap::get`] first look for a key which is
+/// [`ArcStr::ptr_eq`] to the one given, and only hash the string's contents if
+/// there isn't one:
+///
+/// - While the map has at most 16 entries, the pointer check is a linear scan
+///   over the keys, which is just a few comparisons.
+/// - Once it grows past that, it lazily builds an index of the keys by address
+///   (hashing an address is much cheaper than hashing a long string), which is
+///   maintained from then on.
+///
+/// Otherwise this behaves like a `HashMap<ArcStr, V>`: keys with equal contents
+/// are the same key, regardless of pointer. Iteration order is insertion order,
+/// except that [`LazyArcStrMap::remove`] moves the last entry into the removed
+/// one's place.
+///
+/// Note that a `const` `ArcStr` isn't guaranteed to have the same address
+/// every time it's used (see [`ArcStr::ptr_eq`]). Lookups with those are still
+/// correct, but may not take the fast path. Storing the key in a `static` (or
+/// cloning one `ArcStr`) avoids this.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, LazyArcStrMap};
+/// static NAME: ArcStr = arcstr::literal!("name");
+/// let mut m = LazyArcStrMap::new();
+/// m.insert(NAME.clone(), 1);
+/// m.insert(ArcStr::from("age"), 2);
+///
+/// // Same pointer: found without hashing "name".
+/// assert_eq!(m.get(&NAME), Some(&1));
+/// // Different pointer, same contents: found by hashing.
+/// assert_eq!(m.get(&ArcStr::from("age")), Some(&2));
+/// assert_eq!(m.get_str("age"), Some(&2));
+/// assert_eq!(m.get_str("nope"), None);
+/// ```
+#[derive(Clone)]
+pub struct LazyArcStrMap<V> {
+    entries: Vec<(ArcStr, V)>,
+    // Index into `entries` by contents.
+    by_str: HashMap<ArcStr, usize>,
+    // Index into `entries` by the address of the key's data. Only populated
+    // when `entries.len() > SMALL`. This is sound to use because we hold the
+    // keys, so nothing else can be at their address while they're here.
+    by_ptr: HashMap<usize, usize>,
+}
+
+#[inline]
+fn addr(s: &ArcStr) -> usize {
+    s.as_ptr() as usize
+}
+
+impl<V> LazyArcStrMap<V> {
+    /// Create an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            by_str: HashMap::new(),
+            by_ptr: HashMap::new(),
+        }
+    }
+
+    /// Create an empty map with space for at least `capacity` entries.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            by_str: HashMap::with_capacity(capacity),
+            by_ptr: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    fn find(&self, key: &ArcStr) -> Option<usize> {
+        let fast = if self.entries.len() <= SMALL {
+            self.entries
+                .iter()
+                .position(|(k, _)| ArcStr::ptr_eq(k, key))
+        } else {
+            self.by_ptr.get(&addr(key)).copied()
+        };
+        fast.or_else(|| self.by_str.get(key.as_str()).copied())
+    }
+
+    /// Returns the value for `key`, if there is one. See the type docs for how
+    /// this avoids hashing `key` when possible.
+    #[inline]
+    pub fn get(&self, key: &ArcStr) -> Option<&V> {
+        let i = self.find(key)?;
+        Some(&self.entries[i].1)
+    }
+
+    /// Returns a mutable reference to the value for `key`, if there is one.
+    #[inline]
+    pub fn get_mut(&mut self, key: &ArcStr) -> Option<&mut V> {
+        let i = self.find(key)?;
+        Some(&mut self.entries[i].1)
+    }
+
+    /// Returns the value for the key with contents `key`, if there is one.
+    ///
+    /// This always hashes `key`.
+    #[inline]
+    pub fn get_str(&self, key: &str) -> Option<&V> {
+        let i = *self.by_str.get(key)?;
+        Some(&self.entries[i].1)
+    }
+
+    /// Returns true if the map has an entry for `key`.
+    #[inline]
+    pub fn contains_key(&self, key: &ArcStr) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Inserts `value` for `key`, returning the old value if there was one.
+    ///
+    /// As with `HashMap`, if there's already a key with the same contents, it's
+    /// kept (so the fast path applies to it, not to `key`).
+    pub fn insert(&mut self, key: ArcStr, value: V) -> Option<V> {
+        if let Some(i) = self.find(&key) {
+            return Some(core::mem::replace(&mut self.entries[i].1, value));
+        }
+        let i = self.entries.len();
+        self.by_str.insert(key.clone(), i);
+        self.entries.push((key, value));
+        if i == SMALL {
+            // Just grew past `SMALL`: index everything so far.
+            self.by_ptr.reserve(SMALL + 1);
+            for (j, (k, _)) in self.entries.iter().enumerate() {
+                self.by_ptr.insert(addr(k), j);
+            }
+        } else if i > SMALL {
+            self.by_ptr.insert(addr(&self.entries[i].0), i);
+        }
+        None
+    }
+
+    /// Removes the entry for `key`, returning its value if there was one.
+    ///
+    /// The last entry is moved into the removed one's place (as with
+    /// `Vec::swap_remove`).
+    pub fn remove(&mut self, key: &ArcStr) -> Option<V> {
+        let i = self.find(key)?;
+        let use_ptrs = self.entries.len() > SMALL;
+        let (k, v) = self.entries.swap_remove(i);
+        self.by_str.remove(k.as_str());
+        if use_ptrs {
+            self.by_ptr.remove(&addr(&k));
+        }
+        if let Some((moved, _)) = self.entries.get(i) {
+            *self.by_str.get_mut(moved.as_str()).unwrap() = i;
+            if use_ptrs {
+                *self.by_ptr.get_mut(&addr(moved)).unwrap() = i;
+            }
+        }
+        if self.entries.len() <= SMALL {
+            self.by_ptr.clear();
+        }
+        Some(v)
+    }
+
+    /// Removes all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.by_str.clear();
+        self.by_ptr.clear();
+    }
+
+    /// Returns an iterator over the entries of the map.
+    #[inline]
+    pub fn iter(&self) -> LazyArcStrMapIter<'_, V> {
+        LazyArcStrMapIter(self.entries.iter())
+    }
+}
+
+impl<V> Default for LazyArcStrMap<V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: core::fmt::Debug> core::fmt::Debug for LazyArcStrMap<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> Extend<(ArcStr, V)> for LazyArcStrMap<V> {
+    fn extend<I: IntoIterator<Item = (ArcStr, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<V> core::iter::FromIterator<(ArcStr, V)> for LazyArcStrMap<V> {
+    fn from_iter<I: IntoIterator<Item = (ArcStr, V)>>(iter: I) -> Self {
+        let mut m = Self::new();
+        m.extend(iter);
+        m
+    }
+}
+
+impl<'a, V> IntoIterator for &'a LazyArcStrMap<V> {
+    type Item = (&'a ArcStr, &'a V);
+    type IntoIter = LazyArcStrMapIter<'a, V>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// `feature = "map"`: An iterator over the entries of a [`LazyArcStrMap`].
+///
+/// Created by [`LazyArcStrMap::iter`].
+#[derive(Clone, Debug)]
+pub struct LazyArcStrMapIter<'a, V>(core::slice::Iter<'a, (ArcStr, V)>);
+
+impl<'a, V> Iterator for LazyArcStrMapIter<'a, V> {
+    type Item = (&'a ArcStr, &'a V);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for LazyArcStrMapIter<'_, V> {}
+
+impl<V> core::iter::FusedIterator for LazyArcStrMapIter<'_, V> {}
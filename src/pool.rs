@@ -0,0 +1,202 @@
+//! `feature = "pool-alloc"`: A small-block pool used for the allocations
+//! backing `ArcStr`.
+//!
+//! Allocations of at most [`MAX_BLOCK`] bytes are rounded up to one of a few
+//! fixed block sizes. When such a block is freed, rather than handing it back
+//! to the global allocator, we push it onto a free list for its size class, and
+//! the next allocation in that class pops it back off. This saves a round trip
+//! through the global allocator for each short string in workloads which
+//! create and drop lots of them.
+//!
+//! The functions here have the same signatures and contracts as the ones in
+//! `alloc::alloc`, so `arc_str.rs` can switch between the two with a `use`.
+//!
+//! A few notes on the design:
+//!
+//! - Blocks which come out of the pool are always allocated from (and, if they
+//!   don't fit in the pool when freed, returned to) the global allocator with
+//!   the layout of their size class. This means it never matters whether a
+//!   given block was ever in the pool or not.
+//!
+//! - Each free list is guarded by a lock which we only ever *try* to take. If
+//!   another thread holds it, we just use the global allocator directly for
+//!   that call, so a thread never waits on another.
+//!
+//! - Each free list holds at most [`MAX_FREE`] blocks, so the pool doesn't
+//!   keep a large amount of memory alive after a spike in usage.
+//!
+//! - This deliberately uses `core`'s atomics, even under `cfg(loom)`. The lock
+//!   is never held across anything loom would consider a yield point, so the
+//!   pool is invisible to it, and loom's atomics can't be used in a `static`
+//!   anyway.
+use alloc::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::ptr::null_mut;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Block sizes are multiples of this, and blocks are aligned to it.
+const BLOCK_ALIGN: usize = 16;
+/// The largest allocation (in bytes, including the header) we'll serve from
+/// the pool.
+const MAX_BLOCK: usize = 64;
+const NUM_CLASSES: usize = MAX_BLOCK / BLOCK_ALIGN;
+/// The most free blocks we'll keep around for each size class.
+const MAX_FREE: usize = 1024;
+
+// Stored in the first bytes of each free block.
+struct FreeBlock {
+    next: *mut FreeBlock,
+}
+
+struct FreeList {
+    locked: AtomicBool,
+    // Only accessed while `locked` is held.
+    head: UnsafeCell<*mut FreeBlock>,
+    len: UnsafeCell<usize>,
+}
+
+// Safety: the contents of the `UnsafeCell`s are only accessed with the lock
+// held, and the blocks themselves are just memory.
+unsafe impl Sync for FreeList {}
+
+impl FreeList {
+    const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            head: UnsafeCell::new(null_mut()),
+            len: UnsafeCell::new(0),
+        }
+    }
+
+    /// Runs `f` with the list's head and length, or returns `None` without
+    /// calling it if the list is currently locked by someone else.
+    #[inline]
+    fn try_with<R>(&self, f: impl FnOnce(&mut *mut FreeBlock, &mut usize) -> R) -> Option<R> {
+        if self
+            .locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return None;
+        }
+        // Safety: we hold the lock.
+        let r = unsafe { f(&mut *self.head.get(), &mut *self.len.get()) };
+        self.locked.store(false, Ordering::Release);
+        Some(r)
+    }
+}
+
+static POOLS: [FreeList; NUM_CLASSES] = [
+    FreeList::new(),
+    FreeList::new(),
+    FreeList::new(),
+    FreeList::new(),
+];
+
+/// Returns the size class for `layout`, and the layout of blocks in it, or
+/// `None` if it's not served by the pool.
+#[inline]
+fn class_of(layout: Layout) -> Option<(usize, Layout)> {
+    if layout.size() == 0 || layout.size() > MAX_BLOCK || layout.align() > BLOCK_ALIGN {
+        return None;
+    }
+    let class = (layout.size() - 1) / BLOCK_ALIGN;
+    let size = (class + 1) * BLOCK_ALIGN;
+    debug_assert!(size >= layout.size());
+    // Safety: `size` is a small nonzero multiple of `BLOCK_ALIGN`, which is a
+    // power of two.
+    Some((class, unsafe {
+        Layout::from_size_align_unchecked(size, BLOCK_ALIGN)
+    }))
+}
+
+#[inline]
+unsafe fn pop(class: usize) -> *mut u8 {
+    POOLS[class]
+        .try_with(|head, len| {
+            let block = *head;
+            if !block.is_null() {
+                *head = (*block).next;
+                *len -= 1;
+            }
+            block.cast::<u8>()
+        })
+        .unwrap_or(null_mut())
+}
+
+/// Same as [`alloc::alloc::alloc`].
+#[inline]
+pub(crate) unsafe fn alloc(layout: Layout) -> *mut u8 {
+    match class_of(layout) {
+        Some((class, block_layout)) => {
+            let p = pop(class);
+            if p.is_null() {
+                alloc::alloc::alloc(block_layout)
+            } else {
+                p
+            }
+        }
+        None => alloc::alloc::alloc(layout),
+    }
+}
+
+/// Same as [`alloc::alloc::alloc_zeroed`].
+#[inline]
+pub(crate) unsafe fn alloc_zeroed(layout: Layout) -> *mut u8 {
+    match class_of(layout) {
+        Some((class, block_layout)) => {
+            let p = pop(class);
+            if p.is_null() {
+                alloc::alloc::alloc_zeroed(block_layout)
+            } else {
+                p.write_bytes(0, layout.size());
+                p
+            }
+        }
+        None => alloc::alloc::alloc_zeroed(layout),
+    }
+}
+
+/// Same as [`alloc::alloc::dealloc`].
+#[inline]
+pub(crate) unsafe fn dealloc(ptr: *mut u8, layout: Layout) {
+    let (class, block_layout) = match class_of(layout) {
+        Some(c) => c,
+        None => return alloc::alloc::dealloc(ptr, layout),
+    };
+    let pushed = POOLS[class]
+        .try_with(|head, len| {
+            if *len >= MAX_FREE {
+                return false;
+            }
+            let block = ptr.cast::<FreeBlock>();
+            block.write(FreeBlock { next: *head });
+            *head = block;
+            *len += 1;
+            true
+        })
+        .unwrap_or(false);
+    if !pushed {
+        alloc::alloc::dealloc(ptr, block_layout);
+    }
+}
+
+/// Same as [`alloc::alloc::realloc`].
+#[inline]
+pub(crate) unsafe fn realloc(ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+    match (class_of(layout), class_of(new_layout)) {
+        // Same block, it already has room.
+        (Some((old, _)), Some((new, _))) if old == new => ptr,
+        // Neither is from the pool, so the global allocator can do it.
+        (None, None) => alloc::alloc::realloc(ptr, layout, new_size),
+        _ => {
+            let new_ptr = alloc(new_layout);
+            if !new_ptr.is_null() {
+                core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                dealloc(ptr, layout);
+            }
+            new_ptr
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! `feature = "proptest"`: [`proptest`](https://docs.rs/proptest) strategies
+//! for generating [`ArcStr`] and [`Substr`] values.
+//!
+//! # Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // Normally this would be a `#[test]`.
+//!     fn roundtrips(s in arcstr::proptest::arcstr_strategy()) {
+//!         prop_assert_eq!(arcstr::ArcStr::from(s.as_str()), s);
+//!     }
+//! }
+//! roundtrips();
+//! ```
+#[cfg(feature = "substr")]
+use crate::arc_str::nth_char_boundary;
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+
+use ::proptest::prelude::*;
+
+/// Returns a strategy which generates arbitrary (heap-allocated) `ArcStr`s.
+///
+/// This generates the same strings as `any::<String>()`, and shrinks them in
+/// the same way.
+pub fn arcstr_strategy() -> impl Strategy<Value = ArcStr> {
+    any::<alloc::string::String>().prop_map(ArcStr::from)
+}
+
+/// Returns a strategy which generates `Substr`s of arbitrary parent strings.
+///
+/// The parent is generated by [`arcstr_strategy`], and then a range within it
+/// is chosen, which is always on `char` boundaries.
+#[cfg(feature = "substr")]
+pub fn substr_strategy() -> impl Strategy<Value = Substr> {
+    arcstr_strategy().prop_flat_map(|parent| {
+        let boundaries = parent.chars().count() + 1;
+        (Just(parent), 0..boundaries, 0..boundaries).prop_map(|(parent, a, b)| {
+            let start = nth_char_boundary(&parent, a.min(b));
+            let end = nth_char_boundary(&parent, a.max(b));
+            parent.substr(start..end)
+        })
+    })
+}
+
+/// Returns a strategy which generates static `ArcStr`s (as produced by
+/// [`arcstr::literal!`](crate::literal)), chosen from a small built-in pool.
+///
+/// These are useful for covering the code paths which treat static and
+/// dynamically allocated `ArcStr`s differently.
+pub fn static_arcstr_strategy() -> impl Strategy<Value = ArcStr> {
+    ::proptest::sample::select(&STATIC_POOL[..])
+}
+
+static STATIC_POOL: [ArcStr; 8] = [
+    literal!(""),
+    literal!("a"),
+    literal!(" "),
+    literal!("foobar"),
+    literal!("hello, world!"),
+    literal!("line one\nline two\r\n"),
+    literal!("épée"),
+    literal!("🙀 ∆ 𝔘𝔫𝔦𝔠𝔬𝔡𝔢"),
+];
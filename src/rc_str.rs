@@ -0,0 +1,600 @@
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::mem::{align_of, size_of};
+use core::ptr::NonNull;
+
+use crate::arc_str::{char_count, PackedFlagUint};
+use crate::ArcStr;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+
+/// `feature = "rc-str"`: A single-threaded version of [`ArcStr`].
+///
+/// This is the same as `ArcStr` (a thin pointer to an immutable,
+/// reference-counted string, with support for zero-cost static strings via
+/// [`arcstr::rc_literal!`](crate::rc_literal)), except that the reference
+/// count isn't atomic. In exchange, `RcStr` is neither `Send` nor `Sync`, in
+/// the same way as [`Rc<str>`](alloc::rc::Rc) is.
+///
+/// This is useful for workloads which are confined to a single thread, such
+/// as an interpreter, where the atomic operations in `ArcStr`'s `clone` and
+/// `drop` are pure overhead.
+///
+/// Converting between `RcStr` and `ArcStr` has to copy the string data.
+///
+/// Its API is deliberately a subset of `ArcStr`'s, covering construction,
+/// access to the string data, and the reference count helpers (`ptr_eq`,
+/// `strong_count`, `is_static` and `as_static`). Much of the rest of
+/// `ArcStr`'s API hands out other types which share its allocation (such as
+/// `Substr` and `WeakArcStr`), and mirroring it would need a non-atomic copy
+/// of each of those too. So there's no `substr` and friends, no `downgrade`,
+/// and no `repeat` or `leak`. Convert to an `ArcStr` if you need those.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::RcStr;
+/// let s = RcStr::from("abc");
+/// let t = s.clone();
+/// assert!(RcStr::ptr_eq(&s, &t));
+/// assert_eq!(RcStr::strong_count(&s), Some(2));
+///
+/// const LIT: RcStr = arcstr::rc_literal!("static");
+/// assert_eq!(LIT, "static");
+/// assert!(RcStr::is_static(&LIT));
+/// ```
+///
+/// `RcStr` can't be sent to or shared with another thread:
+///
+/// ```compile_fail
+/// let s = arcstr::RcStr::from("abc");
+/// std::thread::spawn(move || drop(s));
+/// ```
+///
+/// ```compile_fail
+/// fn assert_sync<T: Sync>(_: &T) {}
+/// assert_sync(&arcstr::RcStr::from("abc"));
+/// ```
+#[repr(transparent)]
+pub struct RcStr(NonNull<RcInner>);
+
+impl RcStr {
+    /// Construct a new empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let s = RcStr::new();
+    /// assert_eq!(s, "");
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        EMPTY
+    }
+
+    /// Attempt to copy the provided string into a newly allocated `RcStr`, but
+    /// return `None` if we cannot allocate the required memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let s = RcStr::try_alloc("abc").unwrap();
+    /// assert_eq!(s, "abc");
+    /// ```
+    #[inline]
+    pub fn try_alloc(copy_from: &str) -> Option<Self> {
+        if copy_from.is_empty() {
+            return Some(Self::new());
+        }
+        RcInner::try_allocate(copy_from).ok().map(Self)
+    }
+
+    /// Extract a string slice containing our data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let s = RcStr::from("abc");
+    /// assert_eq!(s.as_str(), "abc");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Returns the length of this `RcStr` in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert_eq!(RcStr::from("foo").len(), 3);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len_flag().uint_part()
+    }
+
+    /// Returns true if this `RcStr` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert!(!RcStr::from("foo").is_empty());
+    /// assert!(RcStr::new().is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of `char`s (Unicode scalar values) in this `RcStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert_eq!(RcStr::from("héllo 🙀").char_count(), 7);
+    /// ```
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        char_count(self)
+    }
+
+    /// Convert us to a `std::string::String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert_eq!(RcStr::from("abc").to_string(), "abc");
+    /// ```
+    #[inline]
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        #[cfg(not(feature = "std"))]
+        use alloc::borrow::ToOwned;
+        self.as_str().to_owned()
+    }
+
+    /// Extract a byte slice containing the string's data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert_eq!(RcStr::from("foobar").as_bytes(), b"foobar");
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let len = self.len();
+        let p = self.0.as_ptr();
+        unsafe {
+            let data = p.cast::<u8>().add(OFFSET_DATA);
+            debug_assert_eq!(core::ptr::addr_of!((*p).data).cast::<u8>(), data);
+            core::slice::from_raw_parts(data, len)
+        }
+    }
+
+    /// Returns true if the two `RcStr`s point to the same allocation.
+    ///
+    /// This has the same caveats about `const`s as [`ArcStr::ptr_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let a = RcStr::from("foo");
+    /// let b = a.clone();
+    /// assert!(RcStr::ptr_eq(&a, &b));
+    /// assert!(!RcStr::ptr_eq(&a, &RcStr::from("foo")));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(lhs: &Self, rhs: &Self) -> bool {
+        core::ptr::eq(lhs.0.as_ptr(), rhs.0.as_ptr())
+    }
+
+    /// Returns the number of references that exist to this `RcStr`. If this is
+    /// a static `RcStr` (for example, one from
+    /// [`arcstr::rc_literal!`][crate::rc_literal]), returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let foobar = RcStr::from("foobar");
+    /// assert_eq!(Some(1), RcStr::strong_count(&foobar));
+    /// let also_foobar = RcStr::clone(&foobar);
+    /// assert_eq!(Some(2), RcStr::strong_count(&foobar));
+    ///
+    /// assert_eq!(None, RcStr::strong_count(&RcStr::new()));
+    /// ```
+    #[inline]
+    pub fn strong_count(this: &Self) -> Option<usize> {
+        if Self::is_static(this) {
+            None
+        } else {
+            Some(unsafe { (*this.0.as_ptr()).count.get() })
+        }
+    }
+
+    /// Returns true if `this` is a "static" `RcStr`, for example, if it was
+    /// created from a call to [`arcstr::rc_literal!`][crate::rc_literal], or
+    /// returned by `RcStr::new`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// assert!(RcStr::is_static(&arcstr::rc_literal!("abc")));
+    /// assert!(!RcStr::is_static(&RcStr::from("abc")));
+    /// ```
+    #[inline]
+    pub fn is_static(this: &Self) -> bool {
+        this.len_flag().flag_part()
+    }
+
+    /// Returns a `&'static str` if `this` is static, and `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::RcStr;
+    /// let s = arcstr::rc_literal!("abc");
+    /// assert_eq!(RcStr::as_static(&s), Some("abc"));
+    /// assert_eq!(RcStr::as_static(&RcStr::from("abc")), None);
+    /// ```
+    #[inline]
+    pub fn as_static(this: &Self) -> Option<&'static str> {
+        if Self::is_static(this) {
+            // Static strings live forever.
+            Some(unsafe { &*(this.as_str() as *const str) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn len_flag(&self) -> PackedFlagUint {
+        // Safety: the length is at the same offset for the static and
+        // dynamic cases, and is never written after construction.
+        unsafe { *self.0.as_ptr().cast::<PackedFlagUint>() }
+    }
+
+    // Not public API. Exists so the `arcstr::rc_literal` macro can call it.
+    #[inline]
+    #[doc(hidden)]
+    pub const unsafe fn _private_new_from_static_data<B>(
+        ptr: &'static StaticRcStrInner<B>,
+    ) -> Self {
+        Self(NonNull::new_unchecked(ptr as *const _ as *mut RcInner))
+    }
+}
+
+impl Clone for RcStr {
+    #[inline]
+    fn clone(&self) -> Self {
+        if !Self::is_static(self) {
+            let count = unsafe { &(*self.0.as_ptr()).count };
+            match count.get().checked_add(1) {
+                Some(n) => count.set(n),
+                None => rc_overflow(),
+            }
+        }
+        Self(self.0)
+    }
+}
+
+impl Drop for RcStr {
+    #[inline]
+    fn drop(&mut self) {
+        if Self::is_static(self) {
+            return;
+        }
+        unsafe {
+            let this = self.0.as_ptr();
+            let n = (*this).count.get() - 1;
+            (*this).count.set(n);
+            if n == 0 {
+                RcInner::destroy_cold(this)
+            }
+        }
+    }
+}
+
+// This is the same layout as `ThinInner` (minus the weak count), and the
+// static case works the same way: a `StaticRcStrInner` is never written to.
+// See the comments there for details.
+#[repr(C, align(8))]
+struct RcInner {
+    len_flag: PackedFlagUint,
+    count: Cell<usize>,
+    data: [u8; 0],
+}
+
+const OFFSET_DATA: usize = size_of::<PackedFlagUint>() + size_of::<Cell<usize>>();
+
+// Not public API, exists for macros.
+#[repr(C, align(8))]
+#[doc(hidden)]
+pub struct StaticRcStrInner<Buf> {
+    pub len_flag: usize,
+    pub count: usize,
+    pub data: Buf,
+}
+
+impl<Buf> StaticRcStrInner<Buf> {
+    #[doc(hidden)]
+    #[inline]
+    pub const fn encode_len(v: usize) -> Option<usize> {
+        match PackedFlagUint::new(true, v) {
+            Some(v) => Some(v.encoded_value()),
+            None => None,
+        }
+    }
+}
+
+const _: [(); size_of::<RcStr>()] = [(); size_of::<usize>()];
+const _: [(); size_of::<StaticRcStrInner<[u8; 0]>>()] = [(); 2 * size_of::<usize>()];
+const _: [(); size_of::<RcInner>()] = [(); 2 * size_of::<usize>()];
+const _: [(); align_of::<RcInner>()] = [(); 8];
+const _: [(); size_of::<Cell<usize>>()] = [(); size_of::<usize>()];
+
+const EMPTY: RcStr = rc_literal!("");
+
+impl RcInner {
+    fn try_allocate(data: &str) -> Result<NonNull<Self>, Option<Layout>> {
+        const ALIGN: usize = align_of::<RcInner>();
+        let len = data.len();
+        debug_assert_ne!(len, 0);
+        if len >= (isize::MAX as usize) - (OFFSET_DATA + ALIGN) {
+            return Err(None);
+        }
+        let len_flag = PackedFlagUint::new(false, len).ok_or(None)?;
+        let layout = unsafe { Layout::from_size_align_unchecked(len + OFFSET_DATA, ALIGN) };
+        unsafe {
+            let ptr = alloc::alloc::alloc(layout) as *mut RcInner;
+            if ptr.is_null() {
+                return Err(Some(layout));
+            }
+            core::ptr::addr_of_mut!((*ptr).len_flag).write(len_flag);
+            core::ptr::addr_of_mut!((*ptr).count).write(Cell::new(1));
+            let dst = ptr.cast::<u8>().add(OFFSET_DATA);
+            core::ptr::copy_nonoverlapping(data.as_ptr(), dst, len);
+            Ok(NonNull::new_unchecked(ptr))
+        }
+    }
+
+    #[cold]
+    unsafe fn destroy_cold(p: *mut RcInner) {
+        let len = (*p).len_flag.uint_part();
+        let layout = Layout::from_size_align_unchecked(len + OFFSET_DATA, align_of::<RcInner>());
+        alloc::alloc::dealloc(p as *mut u8, layout);
+    }
+}
+
+#[inline(never)]
+#[cold]
+fn rc_overflow() -> ! {
+    panic!("`RcStr` reference count overflow")
+}
+
+impl From<&str> for RcStr {
+    #[inline]
+    fn from(s: &str) -> Self {
+        if s.is_empty() {
+            return Self::new();
+        }
+        match RcInner::try_allocate(s) {
+            Ok(inner) => Self(inner),
+            Err(None) => panic!("overflow during Layout computation"),
+            Err(Some(layout)) => alloc::alloc::handle_alloc_error(layout),
+        }
+    }
+}
+
+impl core::ops::Deref for RcStr {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.as_bytes()) }
+    }
+}
+
+impl Default for RcStr {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Copies the string data, so this is O(n) in the length of the string.
+impl From<ArcStr> for RcStr {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        Self::from(&s)
+    }
+}
+
+/// Copies the string data, so this is O(n) in the length of the string.
+impl From<&ArcStr> for RcStr {
+    #[inline]
+    fn from(s: &ArcStr) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+/// Copies the string data, so this is O(n) in the length of the string.
+impl From<RcStr> for ArcStr {
+    #[inline]
+    fn from(s: RcStr) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<String> for RcStr {
+    #[inline]
+    fn from(v: String) -> Self {
+        v.as_str().into()
+    }
+}
+
+impl From<&String> for RcStr {
+    #[inline]
+    fn from(s: &String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<&mut str> for RcStr {
+    #[inline]
+    fn from(s: &mut str) -> Self {
+        let s: &str = s;
+        Self::from(s)
+    }
+}
+
+impl From<Box<str>> for RcStr {
+    #[inline]
+    fn from(s: Box<str>) -> Self {
+        Self::from(&s[..])
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for RcStr {
+    #[inline]
+    fn from(s: Cow<'a, str>) -> Self {
+        Self::from(&*s)
+    }
+}
+
+impl From<&RcStr> for RcStr {
+    #[inline]
+    fn from(s: &RcStr) -> Self {
+        s.clone()
+    }
+}
+
+impl core::fmt::Debug for RcStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for RcStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for RcStr {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        RcStr::ptr_eq(self, o) || PartialEq::eq(self.as_str(), o.as_str())
+    }
+}
+
+impl Eq for RcStr {}
+
+macro_rules! impl_peq {
+    (@one $a:ty, $b:ty) => {
+        #[allow(clippy::extra_unused_lifetimes)]
+        impl<'a> PartialEq<$b> for $a {
+            #[inline]
+            fn eq(&self, s: &$b) -> bool {
+                PartialEq::eq(&self[..], &s[..])
+            }
+        }
+    };
+    ($(($a:ty, $b:ty),)+) => {$(
+        impl_peq!(@one $a, $b);
+        impl_peq!(@one $b, $a);
+    )+};
+}
+
+impl_peq! {
+    (RcStr, str),
+    (RcStr, &'a str),
+    (RcStr, String),
+    (RcStr, Cow<'a, str>),
+    (RcStr, Box<str>),
+    (RcStr, ArcStr),
+}
+
+impl PartialOrd for RcStr {
+    #[inline]
+    fn partial_cmp(&self, s: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(s))
+    }
+}
+
+impl Ord for RcStr {
+    #[inline]
+    fn cmp(&self, s: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(s.as_str())
+    }
+}
+
+impl core::hash::Hash for RcStr {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.as_str().hash(h)
+    }
+}
+
+macro_rules! impl_index {
+    ($($IdxT:ty,)*) => {$(
+        impl core::ops::Index<$IdxT> for RcStr {
+            type Output = str;
+            #[inline]
+            fn index(&self, i: $IdxT) -> &Self::Output {
+                &self.as_str()[i]
+            }
+        }
+    )*};
+}
+
+impl_index! {
+    core::ops::RangeFull,
+    core::ops::Range<usize>,
+    core::ops::RangeFrom<usize>,
+    core::ops::RangeTo<usize>,
+    core::ops::RangeInclusive<usize>,
+    core::ops::RangeToInclusive<usize>,
+}
+
+impl AsRef<str> for RcStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<[u8]> for RcStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl core::borrow::Borrow<str> for RcStr {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl core::str::FromStr for RcStr {
+    type Err = core::convert::Infallible;
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
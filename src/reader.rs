@@ -0,0 +1,176 @@
+use crate::ArcStr;
+#[cfg(feature = "substr")]
+use crate::Substr;
+use std::io::{BufRead, Read};
+
+/// `feature = "std"`: A reader over the bytes of an [`ArcStr`].
+///
+/// This implements [`std::io::Read`] and [`std::io::BufRead`], so that an
+/// `ArcStr` can be passed to APIs that read their input. Reading doesn't
+/// change the string: the reader just tracks how far into it we are.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, ArcStrReader};
+/// use std::io::{BufRead, Read};
+///
+/// let mut r = ArcStrReader::new(ArcStr::from("line 1\nline 2\n"));
+/// let mut line = String::new();
+/// r.read_line(&mut line).unwrap();
+/// assert_eq!(line, "line 1\n");
+/// let mut rest = String::new();
+/// r.read_to_string(&mut rest).unwrap();
+/// assert_eq!(rest, "line 2\n");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ArcStrReader {
+    s: ArcStr,
+    pos: usize,
+}
+
+impl ArcStrReader {
+    /// Create a reader which starts at the beginning of `s`.
+    #[inline]
+    pub fn new(s: ArcStr) -> Self {
+        Self { s, pos: 0 }
+    }
+
+    /// Returns the number of bytes which have been read so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, ArcStrReader};
+    /// # use std::io::Read;
+    /// let mut r = ArcStrReader::new(ArcStr::from("abcdef"));
+    /// r.read_exact(&mut [0; 4]).unwrap();
+    /// assert_eq!(r.position(), 4);
+    /// ```
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a reference to the string being read.
+    #[inline]
+    pub fn get_ref(&self) -> &ArcStr {
+        &self.s
+    }
+
+    /// Consumes this reader, returning the string being read.
+    #[inline]
+    pub fn into_inner(self) -> ArcStr {
+        self.s
+    }
+}
+
+impl From<ArcStr> for ArcStrReader {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl Read for ArcStrReader {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = Read::read(&mut self.fill_buf()?, buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for ArcStrReader {
+    #[inline]
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(&self.s.as_bytes()[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = self.s.len().min(self.pos.saturating_add(amt));
+    }
+}
+
+/// `feature = "std"`: A reader over the bytes of a [`Substr`].
+///
+/// This is the same as [`ArcStrReader`], except that it reads a `Substr`.
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, SubstrReader};
+/// use std::io::Read;
+///
+/// let s = ArcStr::from("[contents]");
+/// let mut r = SubstrReader::new(s.substr(1..9));
+/// let mut out = String::new();
+/// r.read_to_string(&mut out).unwrap();
+/// assert_eq!(out, "contents");
+/// ```
+#[cfg(feature = "substr")]
+#[derive(Clone, Debug, Default)]
+pub struct SubstrReader {
+    s: Substr,
+    pos: usize,
+}
+
+#[cfg(feature = "substr")]
+impl SubstrReader {
+    /// Create a reader which starts at the beginning of `s`.
+    #[inline]
+    pub fn new(s: Substr) -> Self {
+        Self { s, pos: 0 }
+    }
+
+    /// Returns the number of bytes which have been read so far (relative to
+    /// the start of the `Substr`, not its parent).
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a reference to the substring being read.
+    #[inline]
+    pub fn get_ref(&self) -> &Substr {
+        &self.s
+    }
+
+    /// Consumes this reader, returning the substring being read.
+    #[inline]
+    pub fn into_inner(self) -> Substr {
+        self.s
+    }
+}
+
+#[cfg(feature = "substr")]
+impl From<Substr> for SubstrReader {
+    #[inline]
+    fn from(s: Substr) -> Self {
+        Self::new(s)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl Read for SubstrReader {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = Read::read(&mut self.fill_buf()?, buf)?;
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+#[cfg(feature = "substr")]
+impl BufRead for SubstrReader {
+    #[inline]
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(&self.s.as_bytes()[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = self.s.len().min(self.pos.saturating_add(amt));
+    }
+}
@@ -7,14 +7,16 @@
     clippy::redundant_slicing,
 )]
 #![cfg_attr(feature = "substr-usize-indices", allow(clippy::unnecessary_cast))]
+use crate::arc_str::{ascii_case_mapped, char_count, encode_utf16_to_buf, make_ascii_titlecase};
 use crate::ArcStr;
+use alloc::vec::Vec;
 use core::ops::{Range, RangeBounds};
 
 #[cfg(feature = "substr-usize-indices")]
-type Idx = usize;
+pub(crate) type Idx = usize;
 
 #[cfg(not(feature = "substr-usize-indices"))]
-type Idx = u32;
+pub(crate) type Idx = u32;
 
 #[cfg(not(any(target_pointer_width = "64", target_pointer_width = "32")))]
 compile_error!(
@@ -92,10 +94,24 @@ fn to_idx(i: usize) -> Idx {
 fn index_overflow(i: usize) -> ! {
     panic!("The index {} is too large for arcstr::Substr (enable the `substr-usize-indices` feature in `arcstr` if you need this)", i);
 }
+#[cold]
+#[inline(never)]
+fn bad_substr_from(s: &Substr, substr: &str) -> ! {
+    let self_start = s.as_ptr() as usize;
+    let substr_start = substr.as_ptr() as usize;
+    if substr_start >= self_start && substr_start + substr.len() <= self_start + s.len() {
+        panic!(
+            "Substr::substr_from: substr over {}..{} does not start and end on char boundaries",
+            substr_start - self_start,
+            substr_start - self_start + substr.len(),
+        );
+    }
+    panic!("non-substring passed to Substr::substr_from");
+}
+
 #[cold]
 #[inline(never)]
 fn bad_substr_idx(s: &ArcStr, i: usize, e: usize) -> ! {
-    assert!(i <= e, "Bad substr range: start {} must be <= end {}", i, e);
     let max = if cfg!(all(
         target_pointer_width = "64",
         not(feature = "substr-usize-indices")
@@ -105,23 +121,38 @@ fn bad_substr_idx(s: &ArcStr, i: usize, e: usize) -> ! {
         usize::MAX
     };
     let len = s.len().min(max);
-    assert!(
-        e <= len,
-        "Bad substr range: end {} must be <= string length/index max size {}",
-        e,
-        len
-    );
-    assert!(
-        s.is_char_boundary(i) && s.is_char_boundary(e),
-        "Bad substr range: start and end must be on char boundaries"
-    );
-    unreachable!(
-        "[arcstr bug]: should have failed one of the above tests: \
-                  please report me. debugging info: b={}, e={}, l={}, max={:#x}",
+    let reason = if i > e {
+        "start must be <= end"
+    } else if e > len {
+        "end must be <= string length/index max size"
+    } else if !s.is_char_boundary(i) || !s.is_char_boundary(e) {
+        "start and end must be on char boundaries"
+    } else {
+        unreachable!(
+            "[arcstr bug]: should have failed one of the above tests: \
+                      please report me. debugging info: b={}, e={}, l={}, max={:#x}",
+            i,
+            e,
+            s.len(),
+            max
+        );
+    };
+    // Include the start of the string, to help figure out which one it was.
+    // Note that this must not panic itself, so cut it on a char boundary.
+    const EXCERPT_MAX: usize = 32;
+    let mut excerpt_len = s.len().min(EXCERPT_MAX);
+    while !s.is_char_boundary(excerpt_len) {
+        excerpt_len -= 1;
+    }
+    let ellipsis = if excerpt_len < s.len() { "..." } else { "" };
+    panic!(
+        "Bad substr range {}..{} for string of length {} ({}): {:?}{}",
         i,
         e,
         s.len(),
-        max
+        reason,
+        &s[..excerpt_len],
+        ellipsis,
     );
 }
 
@@ -171,7 +202,9 @@ impl Substr {
             Bound::Excluded(&n) => n,
             Bound::Unbounded => a.len(),
         };
-        let _ = &a.as_str()[begin..end];
+        if a.get(begin..end).is_none() {
+            bad_substr_idx(a, begin, end);
+        }
 
         Self(ArcStr::clone(a), to_idx(begin), to_idx(end))
     }
@@ -247,6 +280,22 @@ impl Substr {
         self
     }
 
+    /// Extract a byte slice containing our data.
+    ///
+    /// This is the same as `self.as_str().as_bytes()`, see
+    /// [`ArcStr::as_bytes`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s: Substr = arcstr::literal!("foobar").substr(3..);
+    /// assert_eq!(s.as_bytes(), b"bar");
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+
     /// Returns the length of this `Substr` in bytes.
     ///
     /// # Examples
@@ -277,6 +326,92 @@ impl Substr {
         self.2 == self.1
     }
 
+    /// Returns the number of `char`s (Unicode scalar values) in this `Substr`.
+    ///
+    /// This is equivalent to `s.chars().count()`, but may be optimized in the
+    /// future (it shares its implementation with [`ArcStr::char_count`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("_héllo 🙀_").substr(1..12);
+    /// assert_eq!(s.len(), 11);
+    /// assert_eq!(s.char_count(), 7);
+    /// ```
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        char_count(self)
+    }
+
+    /// Returns an iterator over the byte indices of every `char` boundary in
+    /// this substring. See [`ArcStr::char_boundary_indices`].
+    ///
+    /// The indices are relative to the start of this `Substr` (not its
+    /// parent), so they can be passed to [`Substr::substr`] directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("_aé🙀_").substr(1..8);
+    /// let bounds: Vec<usize> = s.char_boundary_indices().collect();
+    /// assert_eq!(bounds, [0, 1, 3, 7]);
+    /// assert_eq!(s.substr(bounds[1]..bounds[2]), "é");
+    /// ```
+    #[inline]
+    pub fn char_boundary_indices(&self) -> crate::ArcStrCharBoundaries<'_> {
+        crate::ArcStrCharBoundaries::new(self.as_str())
+    }
+
+    /// Returns this substring encoded as UTF-16, in a new `Vec<u16>`. See
+    /// [`ArcStr::encode_utf16_to_vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("[🙀]").substr(1..5);
+    /// assert_eq!(s.encode_utf16_to_vec(), [0xd83d, 0xde40]);
+    /// ```
+    #[inline]
+    pub fn encode_utf16_to_vec(&self) -> Vec<u16> {
+        self.encode_utf16().collect()
+    }
+
+    /// Appends this substring, encoded as UTF-16, to the end of `buf`, and
+    /// returns the part of `buf` that was appended. See
+    /// [`ArcStr::encode_utf16_to_buf`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let mut buf = vec![0x20];
+    /// let s = Substr::from("[a]").substr(1..2);
+    /// assert_eq!(s.encode_utf16_to_buf(&mut buf), [0x61]);
+    /// assert_eq!(buf, [0x20, 0x61]);
+    /// ```
+    #[inline]
+    pub fn encode_utf16_to_buf<'a>(&self, buf: &'a mut Vec<u16>) -> &'a [u16] {
+        encode_utf16_to_buf(self, buf)
+    }
+
+    /// Returns the `char`s of this substring as UTF-32 code points, in a new
+    /// `Vec<u32>`. See [`ArcStr::encode_utf32_to_vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("[é]").substr(1..3);
+    /// assert_eq!(s.encode_utf32_to_vec(), [0xe9]);
+    /// ```
+    #[inline]
+    pub fn encode_utf32_to_vec(&self) -> Vec<u32> {
+        self.chars().map(u32::from).collect()
+    }
+
     /// Convert us to a `std::string::String`.
     ///
     /// This is provided as an inherent method to avoid needing to route through
@@ -297,6 +432,29 @@ impl Substr {
         self.as_str().to_owned()
     }
 
+    /// Creates a new [`ArcStr`] by repeating our contents `n` times.
+    ///
+    /// This shadows [`str::repeat`], which returns a `String` instead. The
+    /// result never shares memory with our parent, and is a dynamically
+    /// allocated `ArcStr` unless it would be empty.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the capacity overflows, see
+    /// [`ArcStr::try_repeat`] if this is undesirable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = arcstr::literal!("ab cd").substr(..3);
+    /// assert_eq!(s.repeat(3), "ab ab ab ");
+    /// ```
+    #[inline]
+    pub fn repeat(&self, n: usize) -> ArcStr {
+        ArcStr::repeat(self.as_str(), n)
+    }
+
     /// Unchecked function to construct a [`Substr`] from an [`ArcStr`] and a
     /// byte range. Direct usage of this function is largely discouraged in
     /// favor of [`ArcStr::substr`][crate::ArcStr::substr], or the
@@ -337,6 +495,31 @@ impl Substr {
         Self(s, to_idx_const(range.start), to_idx_const(range.end))
     }
 
+    // Not public API. Exists so the `arcstr::literal_substr` macro can call it.
+    //
+    // Performs the checks from `Substr::substr` in a const context, returning
+    // the range unchanged if it passes them, and panicking (and thus failing
+    // compilation) otherwise.
+    #[inline]
+    #[doc(hidden)]
+    pub const fn _private_check_const_range(s: &str, range: Range<usize>) -> Range<usize> {
+        const fn is_char_boundary(b: &[u8], i: usize) -> bool {
+            // Same check `str::is_char_boundary` does: not a continuation byte.
+            i == b.len() || (b[i] as i8) >= -0x40
+        }
+        let bytes = s.as_bytes();
+        if range.start > range.end {
+            panic!("Bad substr range: start must be <= end");
+        }
+        if range.end > bytes.len() {
+            panic!("Bad substr range: end must be <= string length");
+        }
+        if !is_char_boundary(bytes, range.start) || !is_char_boundary(bytes, range.end) {
+            panic!("Bad substr range: start and end must be on char boundaries");
+        }
+        range
+    }
+
     /// Returns `true` if the two `Substr`s have identical parents, and are
     /// covering the same range.
     ///
@@ -419,6 +602,204 @@ impl Substr {
         (self.1 as usize)..(self.2 as usize)
     }
 
+    // Used by `ArcStrVec`, which stores the bounds without the parent.
+    #[inline]
+    pub(crate) fn idx_bounds(&self) -> (Idx, Idx) {
+        (self.1, self.2)
+    }
+
+    /// Safety: `bounds` must have come from `idx_bounds` on a `Substr` of
+    /// `parent`.
+    #[inline]
+    pub(crate) unsafe fn from_idx_bounds(parent: ArcStr, bounds: (Idx, Idx)) -> Self {
+        Self(parent, bounds.0, bounds.1)
+    }
+
+    /// `feature = "verify"` Checks that the guarantees described on
+    /// [`Substr::range`] hold, and panics with a description of the problem if
+    /// they don't.
+    ///
+    /// This should never panic, unless something (such as buggy `unsafe` code,
+    /// or a misuse of [`Substr::from_parts_unchecked`]) has produced an invalid
+    /// `Substr`. The same check happens automatically whenever a `Substr` is
+    /// dereferenced in builds with `debug_assertions` enabled.
+    ///
+    /// This doesn't check the parent; see [`ArcStr::verify_invariants`] for
+    /// that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc def");
+    /// parent.substr(2..5).verify_invariants();
+    /// ```
+    #[cfg(feature = "verify")]
+    #[inline]
+    pub fn verify_invariants(&self) {
+        self.check_invariants();
+    }
+
+    #[cfg(any(debug_assertions, feature = "verify"))]
+    fn check_invariants(&self) {
+        let (start, end, len) = (self.1 as usize, self.2 as usize, self.0.len());
+        assert!(
+            start <= end,
+            "Substr invariant violated: start {} is greater than end {}",
+            start,
+            end,
+        );
+        assert!(
+            end <= len,
+            "Substr invariant violated: end {} is past the end of the parent (length {})",
+            end,
+            len,
+        );
+        assert!(
+            self.0.is_char_boundary(start) && self.0.is_char_boundary(end),
+            "Substr invariant violated: range {}..{} is not on char boundaries",
+            start,
+            end,
+        );
+    }
+
+    /// Returns `true` if we cover the entirety of our parent.
+    ///
+    /// This is the case for `Substr`s produced by [`Substr::full`] (and
+    /// `Substr::from(arcstr)`), and for [`Substr::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("abc def");
+    /// assert!(Substr::full(parent.clone()).is_full());
+    /// assert!(parent.substr(..).is_full());
+    /// assert!(!parent.substr(1..).is_full());
+    /// assert!(Substr::new().is_full());
+    /// ```
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.1 == 0 && self.2 as usize == self.0.len()
+    }
+
+    /// Returns our parent if we cover the entirety of it, and `None` otherwise.
+    ///
+    /// This is a cheap way to get an `&ArcStr` with our exact contents, when
+    /// one is available. See [`Substr::is_full`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("abc def");
+    /// let full = Substr::full(parent.clone());
+    /// assert!(ArcStr::ptr_eq(full.as_arcstr_if_full().unwrap(), &parent));
+    /// assert_eq!(parent.substr(1..).as_arcstr_if_full(), None);
+    /// ```
+    #[inline]
+    pub fn as_arcstr_if_full(&self) -> Option<&ArcStr> {
+        if self.is_full() {
+            Some(&self.0)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a new [`ArcStr`] containing only our contents.
+    ///
+    /// In general this copies our data into a fresh allocation, which means
+    /// the result does not keep our parent alive. However, if we cover the
+    /// entirety of our parent (see [`Substr::is_full`]), no copy is needed, and
+    /// a clone of the parent is returned instead.
+    ///
+    /// See [`Substr::into_arcstr`] for a version which consumes `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc def");
+    /// let def = parent.substr(4..).to_arcstr();
+    /// assert_eq!(def, "def");
+    /// assert!(!ArcStr::ptr_eq(&def, &parent));
+    /// // No copy needed here.
+    /// let all = parent.substr(..).to_arcstr();
+    /// assert!(ArcStr::ptr_eq(&all, &parent));
+    /// ```
+    #[inline]
+    pub fn to_arcstr(&self) -> ArcStr {
+        if self.is_full() {
+            ArcStr::clone(&self.0)
+        } else {
+            ArcStr::from(self.as_str())
+        }
+    }
+
+    /// Returns a new [`ArcStr`] containing a copy of our contents.
+    ///
+    /// This is the same as `ArcStr::from(self.as_str())`: unlike
+    /// [`Substr::to_arcstr`], it always copies, so the result never shares an
+    /// allocation with our parent, even if we cover all of it. In most cases
+    /// `to_arcstr` is what you want instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc def");
+    /// let all = parent.substr(..).to_owned_arcstr();
+    /// assert_eq!(all, parent);
+    /// assert!(!ArcStr::ptr_eq(&all, &parent));
+    /// ```
+    #[inline]
+    pub fn to_owned_arcstr(&self) -> ArcStr {
+        ArcStr::from(self.as_str())
+    }
+
+    /// Returns a clone of our parent, without copying any string data.
+    ///
+    /// This is the same as `self.parent().clone()`. Note that the result holds
+    /// the whole parent string, not just the part we cover.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc def");
+    /// let whole = parent.substr(4..).clone_parent();
+    /// assert_eq!(whole, "abc def");
+    /// assert!(ArcStr::ptr_eq(&whole, &parent));
+    /// ```
+    #[inline]
+    pub fn clone_parent(&self) -> ArcStr {
+        ArcStr::clone(&self.0)
+    }
+
+    /// Convert this `Substr` into an [`ArcStr`] containing only our contents.
+    ///
+    /// This is the same as [`Substr::to_arcstr`], except that we can avoid
+    /// touching the parent's reference count when no copy is required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("abc def");
+    /// let abc: ArcStr = parent.substr(..3).into_arcstr();
+    /// assert_eq!(abc, "abc");
+    /// let all = Substr::from(parent.clone()).into_arcstr();
+    /// assert!(ArcStr::ptr_eq(&all, &parent));
+    /// ```
+    #[inline]
+    pub fn into_arcstr(self) -> ArcStr {
+        if self.is_full() {
+            self.0
+        } else {
+            ArcStr::from(self.as_str())
+        }
+    }
+
     /// Returns a [`Substr`] of self over the given `&str`, or panics.
     ///
     /// It is not rare to end up with a `&str` which holds a view into a
@@ -450,10 +831,10 @@ impl Substr {
     /// bytes away from our start, if we're a 64-bit machine and
     /// `substr-usize-indices` is not enabled.
     pub fn substr_from(&self, substr: &str) -> Substr {
-        // TODO: should outline `expect` call to avoid fmt bloat and let us
-        // provide better error message like we do for ArcStr
-        self.try_substr_from(substr)
-            .expect("non-substring passed to Substr::substr_from")
+        match self.try_substr_from(substr) {
+            Some(s) => s,
+            None => bad_substr_from(self, substr),
+        }
     }
 
     /// If possible, returns a [`Substr`] of self over the given `&str`.
@@ -505,6 +886,12 @@ impl Substr {
 
         let index = substr_start - self_start;
         let end = index + substr.len();
+        // A `&str` should always start and end on char boundaries, but one
+        // which was built with unsafe code might not.
+        let (abs_start, abs_end) = (self.1 as usize + index, self.1 as usize + end);
+        if !self.0.is_char_boundary(abs_start) || !self.0.is_char_boundary(abs_end) {
+            return None;
+        }
         Some(self.substr(index..end))
     }
     /// Compute a derived `&str` a function of `&str` => `&str`, and produce a
@@ -557,45 +944,615 @@ impl Substr {
     pub fn substr_using(&self, f: impl FnOnce(&str) -> &str) -> Self {
         self.substr_from(f(self.as_str()))
     }
-}
 
-impl From<ArcStr> for Substr {
+    /// Divide one `Substr` into two at an index.
+    ///
+    /// The argument, `mid`, should be a byte offset from the start of the
+    /// `Substr`. It must also be on the boundary of a UTF-8 code point.
+    ///
+    /// The two `Substr`s returned go from the start of `self` to `mid`, and
+    /// from `mid` to the end of `self`. Both share our parent.
+    ///
+    /// This shadows [`str::split_at`], which returns a pair of `&str`s instead.
+    /// See [`Substr::try_split_at`] for a version that returns an option
+    /// rather than panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("xxfoobarxx");
+    /// let s = parent.substr(2..8);
+    /// let (foo, bar) = s.split_at(3);
+    /// assert_eq!(foo, "foo");
+    /// assert_eq!(bar, "bar");
+    /// assert!(ArcStr::ptr_eq(foo.parent(), &parent));
+    /// assert_eq!(foo.range(), 2..5);
+    /// assert_eq!(bar.range(), 5..8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is greater than `self.len()`, or if it is not on a char
+    /// boundary.
     #[inline]
-    fn from(a: ArcStr) -> Self {
-        Self::full(a)
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        match self.try_split_at(mid) {
+            Some(pair) => pair,
+            None => bad_split_at(self, mid),
+        }
     }
-}
 
-impl From<&ArcStr> for Substr {
+    /// Divide one `Substr` into two at an index, if possible.
+    ///
+    /// This is a fallible version of [`Substr::split_at`], which returns `None`
+    /// if `mid` is greater than `self.len()`, or is not on a char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("🙀abc");
+    /// let (l, r) = s.try_split_at(4).unwrap();
+    /// assert_eq!((l.as_str(), r.as_str()), ("🙀", "abc"));
+    /// // Not on a char boundary.
+    /// assert!(s.try_split_at(1).is_none());
+    /// // Out of bounds.
+    /// assert!(s.try_split_at(8).is_none());
+    /// ```
     #[inline]
-    fn from(a: &ArcStr) -> Self {
-        Self::full(a.clone())
+    pub fn try_split_at(&self, mid: usize) -> Option<(Self, Self)> {
+        if mid > self.len() || !self.is_char_boundary(mid) {
+            return None;
+        }
+        let split = self.1 + mid as Idx;
+        Some((
+            Self(ArcStr::clone(&self.0), self.1, split),
+            Self(ArcStr::clone(&self.0), split, self.2),
+        ))
     }
-}
 
-impl core::ops::Deref for Substr {
-    type Target = str;
-    #[inline]
-    fn deref(&self) -> &str {
-        debug_assert!(self.0.get((self.1 as usize)..(self.2 as usize)).is_some());
-        unsafe { self.0.get_unchecked((self.1 as usize)..(self.2 as usize)) }
+    /// Returns a `Substr` over the first occurrence of `pat`, which shares our
+    /// parent.
+    ///
+    /// Only our own contents are searched, so the result is always within
+    /// `self.range()`. Returns `None` if `pat` is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc abc abc");
+    /// let s = parent.substr(1..);
+    /// let found = s.find_as_substr("abc").unwrap();
+    /// assert_eq!(found, "abc");
+    /// assert_eq!(found.range(), 4..7);
+    /// assert!(s.find_as_substr("xyz").is_none());
+    /// ```
+    pub fn find_as_substr(&self, pat: &str) -> Option<Self> {
+        let i = self.find(pat)?;
+        Some(self.substr(i..i + pat.len()))
     }
-}
 
-impl PartialEq for Substr {
-    #[inline]
-    fn eq(&self, o: &Self) -> bool {
-        Substr::shallow_eq(self, o) || PartialEq::eq(self.as_str(), o.as_str())
+    /// Returns a `Substr` over the last occurrence of `pat`, which shares our
+    /// parent.
+    ///
+    /// Only our own contents are searched, so the result is always within
+    /// `self.range()`. Returns `None` if `pat` is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("abc abc abc");
+    /// let s = parent.substr(..10);
+    /// let found = s.rfind_as_substr("abc").unwrap();
+    /// assert_eq!(found, "abc");
+    /// assert_eq!(found.range(), 4..7);
+    /// assert!(s.rfind_as_substr("xyz").is_none());
+    /// ```
+    pub fn rfind_as_substr(&self, pat: &str) -> Option<Self> {
+        let i = self.rfind(pat)?;
+        Some(self.substr(i..i + pat.len()))
     }
+
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// `Substr`s which share our parent.
+    ///
+    /// This behaves the same as [`str::split`] (including for an empty `sep`,
+    /// and for separators at the start or end), except that the items are
+    /// `Substr`s rather than `&str`s. See [`SubstrSplit`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("a,b,,c");
+    /// let parts: Vec<Substr> = parent.substr(..).split_as_substrs(",").collect();
+    /// assert_eq!(parts, ["a", "b", "", "c"]);
+    /// assert!(ArcStr::ptr_eq(parts[0].parent(), &parent));
+    /// ```
     #[inline]
-    fn ne(&self, o: &Self) -> bool {
-        !Substr::shallow_eq(self, o) && PartialEq::ne(self.as_str(), o.as_str())
+    pub fn split_as_substrs<'a>(&self, sep: &'a str) -> SubstrSplit<'a> {
+        SubstrSplit {
+            parent: ArcStr::clone(&self.0),
+            start: self.1 as usize,
+            search: self.1 as usize,
+            end: self.2 as usize,
+            sep,
+            finished: false,
+        }
     }
-}
 
-impl PartialEq<ArcStr> for Substr {
-    #[inline]
-    fn eq(&self, o: &ArcStr) -> bool {
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// `Substr`s which share our parent, where each part includes the
+    /// separator that ends it.
+    ///
+    /// This behaves the same as [`str::split_inclusive`]: every item except
+    /// possibly the last one ends with `sep`, and if `self` ends with `sep`,
+    /// there's no empty item at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("One. Two. Three");
+    /// let parts: Vec<Substr> = parent.substr(..).split_inclusive_as_substrs(". ").collect();
+    /// assert_eq!(parts, ["One. ", "Two. ", "Three"]);
+    /// assert_eq!(parts[1].range(), 5..10);
+    /// ```
+    #[inline]
+    pub fn split_inclusive_as_substrs<'a>(&self, sep: &'a str) -> SubstrSplitInclusive<'a> {
+        SubstrSplitInclusive {
+            parent: ArcStr::clone(&self.0),
+            start: self.1 as usize,
+            search: self.1 as usize,
+            end: self.2 as usize,
+            sep,
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// `Substr`s which share our parent, splitting at most `n - 1` times.
+    ///
+    /// This behaves the same as [`str::splitn`]: the last item is the rest of
+    /// the string, whether or not it contains `sep`. If `n` is 0 the iterator
+    /// is empty, and if it's 1 the only item is the whole of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("key=value=extra");
+    /// let parts: Vec<Substr> = parent.substr(..).splitn_as_substrs(2, "=").collect();
+    /// assert_eq!(parts, ["key", "value=extra"]);
+    /// assert_eq!(parts[1].range(), 4..15);
+    /// ```
+    #[inline]
+    pub fn splitn_as_substrs<'a>(&self, n: usize, sep: &'a str) -> SubstrSplitN<'a> {
+        SubstrSplitN {
+            split: self.split_as_substrs(sep),
+            n,
+        }
+    }
+
+    /// Returns an iterator over the parts of `self` separated by `sep`, as
+    /// `Substr`s which share our parent, starting from the end of the string
+    /// and splitting at most `n - 1` times.
+    ///
+    /// This behaves the same as [`str::rsplitn`]: the last item is the start
+    /// of the string, whether or not it contains `sep`. If `n` is 0 the
+    /// iterator is empty, and if it's 1 the only item is the whole of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("path/to/file.txt");
+    /// let parts: Vec<Substr> = parent.substr(..).rsplitn_as_substrs(2, "/").collect();
+    /// assert_eq!(parts, ["file.txt", "path/to"]);
+    /// assert_eq!(parts[1].range(), 0..7);
+    /// ```
+    #[inline]
+    pub fn rsplitn_as_substrs<'a>(&self, n: usize, sep: &'a str) -> SubstrRSplitN<'a> {
+        SubstrRSplitN {
+            parent: ArcStr::clone(&self.0),
+            start: self.1 as usize,
+            end: self.2 as usize,
+            search_end: self.2 as usize,
+            sep,
+            n,
+        }
+    }
+
+    /// Returns an iterator over the non-overlapping occurrences of `pat` in
+    /// `self`, as `Substr`s which share our parent.
+    ///
+    /// This behaves the same as [`str::matches`] (with a `&str` pattern),
+    /// except that the items are `Substr`s, so their position in the parent is
+    /// available from [`Substr::range`]. Only our part of the parent is
+    /// searched, so a match which would extend past either end of `self` isn't
+    /// found, even if it's there in the parent.
+    ///
+    /// As with `str::matches`, an empty `pat` matches (as an empty `Substr`) at
+    /// each char boundary, including the start and end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("abcabcab");
+    /// let sub = parent.substr(1..7); // "bcabca"
+    /// let found: Vec<Substr> = sub.matches_as_substrs("bc").collect();
+    /// assert_eq!(found, ["bc", "bc"]);
+    /// assert_eq!(found[1].range(), 4..6);
+    /// // "ab" occurs at 0..2 and 6..8 in the parent, but neither is inside `sub`.
+    /// let found: Vec<Substr> = sub.matches_as_substrs("ab").collect();
+    /// assert_eq!(found, ["ab"]);
+    /// assert_eq!(found[0].range(), 3..5);
+    /// ```
+    #[inline]
+    pub fn matches_as_substrs<'a>(&self, pat: &'a str) -> SubstrMatches<'a> {
+        SubstrMatches {
+            parent: ArcStr::clone(&self.0),
+            search: self.1 as usize,
+            end: self.2 as usize,
+            pat,
+        }
+    }
+
+    /// Returns an iterator over the lines of `self`, as `Substr`s which share
+    /// our parent.
+    ///
+    /// This behaves the same as [`str::lines`]: lines are ended by either a
+    /// newline (`\n`) or a carriage return followed by a newline (`\r\n`),
+    /// which is not included in the line. The final line ending is optional,
+    /// and a string that ends with one does not produce an empty final line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let text = Substr::from("foo\r\nbar\n\nbaz\n");
+    /// let lines: Vec<Substr> = text.lines_as_substrs().collect();
+    /// assert_eq!(lines, ["foo", "bar", "", "baz"]);
+    /// ```
+    #[inline]
+    pub fn lines_as_substrs(&self) -> SubstrLines {
+        SubstrLines {
+            parent: ArcStr::clone(&self.0),
+            start: self.1 as usize,
+            end: self.2 as usize,
+        }
+    }
+
+    /// Returns an iterator over the `char`s of `self`, where each is yielded as
+    /// a `Substr` covering exactly that char's bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let s = Substr::from("a🙀b");
+    /// let chars: Vec<Substr> = s.chars_as_substrs().collect();
+    /// assert_eq!(chars, ["a", "🙀", "b"]);
+    /// assert_eq!(chars[1].range(), 1..5);
+    /// ```
+    #[inline]
+    pub fn chars_as_substrs(&self) -> SubstrChars {
+        SubstrChars {
+            parent: ArcStr::clone(&self.0),
+            start: self.1 as usize,
+            end: self.2 as usize,
+        }
+    }
+
+    /// `feature = "std"`: Write the contents of this substring to `writer`.
+    /// See [`ArcStr::write_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let mut out = Vec::new();
+    /// ArcStr::from("hello world").substr(6..).write_to(&mut out).unwrap();
+    /// assert_eq!(out, b"world");
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_to<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+
+    /// Write the contents of this substring to the [`core::fmt::Write`]
+    /// `writer`. See [`ArcStr::write_to_fmt`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let mut out = String::new();
+    /// ArcStr::from("hello world").substr(..5).write_to_fmt(&mut out).unwrap();
+    /// assert_eq!(out, "hello");
+    /// ```
+    #[inline]
+    pub fn write_to_fmt<W: core::fmt::Write + ?Sized>(&self, writer: &mut W) -> core::fmt::Result {
+        writer.write_str(self)
+    }
+
+    /// Returns a copy of this substring where each character is mapped to its
+    /// ASCII upper case equivalent, as a new `ArcStr`. See
+    /// [`ArcStr::to_ascii_uppercase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("[héllo]");
+    /// assert_eq!(s.substr(1..7).to_ascii_uppercase(), "HéLLO");
+    /// ```
+    #[inline]
+    pub fn to_ascii_uppercase(&self) -> ArcStr {
+        ascii_case_mapped(self, <[u8]>::make_ascii_uppercase)
+    }
+
+    /// Returns a copy of this substring where each character is mapped to its
+    /// ASCII lower case equivalent, as a new `ArcStr`. See
+    /// [`ArcStr::to_ascii_lowercase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("[HÉLLO]");
+    /// assert_eq!(s.substr(1..7).to_ascii_lowercase(), "hÉllo");
+    /// ```
+    #[inline]
+    pub fn to_ascii_lowercase(&self) -> ArcStr {
+        ascii_case_mapped(self, <[u8]>::make_ascii_lowercase)
+    }
+
+    /// Returns a copy of this substring where the first ASCII letter is mapped
+    /// to upper case and every ASCII letter after it to lower case, as a new
+    /// `ArcStr`. See [`ArcStr::to_ascii_titlecase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let s = ArcStr::from("name: jOHN");
+    /// assert_eq!(s.substr(6..).to_ascii_titlecase(), "John");
+    /// ```
+    #[inline]
+    pub fn to_ascii_titlecase(&self) -> ArcStr {
+        ascii_case_mapped(self, make_ascii_titlecase)
+    }
+
+    /// Split on the first occurrence of `sep`, returning the parts before and
+    /// after it as `Substr`s which share our parent.
+    ///
+    /// This is like [`str::split_once`], except that it returns `Substr`s
+    /// rather than `&str`s, and so the result isn't tied to a borrow of `self`.
+    /// The separator itself is not included in either part.
+    ///
+    /// Returns `None` if `sep` is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("key=value=more");
+    /// let (k, v) = s.split_once_as_substrs("=").unwrap();
+    /// assert_eq!(k, "key");
+    /// assert_eq!(v, "value=more");
+    /// assert!(s.split_once_as_substrs(":").is_none());
+    /// ```
+    pub fn split_once_as_substrs(&self, sep: &str) -> Option<(Self, Self)> {
+        let i = self.find(sep)?;
+        Some((self.substr(..i), self.substr(i + sep.len()..)))
+    }
+
+    /// Split on the last occurrence of `sep`, returning the parts before and
+    /// after it as `Substr`s which share our parent.
+    ///
+    /// This is like [`str::rsplit_once`], except that it returns `Substr`s
+    /// rather than `&str`s. The separator itself is not included in either
+    /// part.
+    ///
+    /// Returns `None` if `sep` is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::Substr;
+    /// let s = Substr::from("key=value=more");
+    /// let (k, v) = s.rsplit_once_as_substrs("=").unwrap();
+    /// assert_eq!(k, "key=value");
+    /// assert_eq!(v, "more");
+    /// assert!(s.rsplit_once_as_substrs(":").is_none());
+    /// ```
+    pub fn rsplit_once_as_substrs(&self, sep: &str) -> Option<(Self, Self)> {
+        let i = self.rfind(sep)?;
+        Some((self.substr(..i), self.substr(i + sep.len()..)))
+    }
+
+    /// Returns a `Substr` spanning both `a` and `b`, if they share a parent.
+    ///
+    /// The result covers everything from the start of whichever begins first,
+    /// to the end of whichever ends last. Note that this includes any gap
+    /// between them, so the inputs don't need to be adjacent or overlapping.
+    ///
+    /// Returns `None` if `a` and `b` have different parents (as determined by
+    /// [`ArcStr::ptr_eq`], see [`Substr::shallow_eq`] for the caveats this
+    /// implies).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("foo bar baz");
+    /// let foo = parent.substr(0..3);
+    /// let bar = parent.substr(4..7);
+    /// let both = Substr::union(&foo, &bar).unwrap();
+    /// assert_eq!(both, "foo bar");
+    /// assert_eq!(both.range(), 0..7);
+    /// // Different parents have no union.
+    /// assert!(Substr::union(&foo, &Substr::from("foo")).is_none());
+    /// ```
+    #[inline]
+    pub fn union(a: &Self, b: &Self) -> Option<Self> {
+        if !ArcStr::ptr_eq(&a.0, &b.0) {
+            return None;
+        }
+        Some(Self(ArcStr::clone(&a.0), a.1.min(b.1), a.2.max(b.2)))
+    }
+
+    /// Returns the `Substr` where `a` and `b` overlap, if they share a parent.
+    ///
+    /// Returns `None` if `a` and `b` have different parents (as determined by
+    /// [`ArcStr::ptr_eq`]), or if their ranges have no bytes in common. In
+    /// particular, ranges that are adjacent but don't overlap have no
+    /// intersection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, Substr};
+    /// let parent = ArcStr::from("foo bar baz");
+    /// let a = parent.substr(0..6);
+    /// let b = parent.substr(4..9);
+    /// let overlap = Substr::intersection(&a, &b).unwrap();
+    /// assert_eq!(overlap, "ba");
+    /// assert_eq!(overlap.range(), 4..6);
+    /// // No overlap.
+    /// assert!(Substr::intersection(&a, &parent.substr(8..)).is_none());
+    /// ```
+    #[inline]
+    pub fn intersection(a: &Self, b: &Self) -> Option<Self> {
+        if !ArcStr::ptr_eq(&a.0, &b.0) {
+            return None;
+        }
+        let (start, end) = (a.1.max(b.1), a.2.min(b.2));
+        if start >= end {
+            return None;
+        }
+        Some(Self(ArcStr::clone(&a.0), start, end))
+    }
+
+    /// Returns `true` if `self` and `other` share a parent, and one of them
+    /// begins exactly where the other ends.
+    ///
+    /// The order doesn't matter: this is true both when `other` immediately
+    /// follows `self`, and when it immediately precedes it. Sharing a parent is
+    /// determined by [`ArcStr::ptr_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("foobar");
+    /// let foo = parent.substr(..3);
+    /// let bar = parent.substr(3..);
+    /// assert!(foo.is_adjacent_to(&bar));
+    /// assert!(bar.is_adjacent_to(&foo));
+    /// assert!(!foo.is_adjacent_to(&parent.substr(4..)));
+    /// ```
+    #[inline]
+    pub fn is_adjacent_to(&self, other: &Self) -> bool {
+        ArcStr::ptr_eq(&self.0, &other.0) && (self.2 == other.1 || other.2 == self.1)
+    }
+
+    /// Combine `self` and `other` into a single `Substr` if they are adjacent.
+    ///
+    /// Adjacency is checked using [`Substr::is_adjacent_to`], so the two may be
+    /// passed in either order. If they aren't adjacent, both are handed back in
+    /// the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::ArcStr;
+    /// let parent = ArcStr::from("foo bar");
+    /// let foo = parent.substr(..3);
+    /// let space = parent.substr(3..4);
+    /// let bar = parent.substr(4..);
+    ///
+    /// let foo_space = foo.try_merge_adjacent(space).unwrap();
+    /// assert_eq!(foo_space, "foo ");
+    /// let all = bar.try_merge_adjacent(foo_space).unwrap();
+    /// assert_eq!(all, "foo bar");
+    ///
+    /// let (foo, bar) = parent.substr(..3).try_merge_adjacent(parent.substr(4..)).unwrap_err();
+    /// assert_eq!((foo.as_str(), bar.as_str()), ("foo", "bar"));
+    /// ```
+    #[inline]
+    pub fn try_merge_adjacent(self, other: Self) -> Result<Self, (Self, Self)> {
+        if self.is_adjacent_to(&other) {
+            let (start, end) = (self.1.min(other.1), self.2.max(other.2));
+            Ok(Self(self.0, start, end))
+        } else {
+            Err((self, other))
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn bad_split_at(s: &Substr, mid: usize) -> ! {
+    if mid > s.len() {
+        panic!(
+            "mid {} is out of bounds of substr of length {}",
+            mid,
+            s.len()
+        );
+    } else {
+        panic!(
+            "mid {} is not a char boundary of substr of length {}",
+            mid,
+            s.len()
+        );
+    }
+}
+
+impl From<ArcStr> for Substr {
+    #[inline]
+    fn from(a: ArcStr) -> Self {
+        Self::full(a)
+    }
+}
+
+impl From<&ArcStr> for Substr {
+    #[inline]
+    fn from(a: &ArcStr) -> Self {
+        Self::full(a.clone())
+    }
+}
+
+impl core::ops::Deref for Substr {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        #[cfg(debug_assertions)]
+        self.check_invariants();
+        unsafe { self.0.get_unchecked((self.1 as usize)..(self.2 as usize)) }
+    }
+}
+
+impl PartialEq for Substr {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        Substr::shallow_eq(self, o) || PartialEq::eq(self.as_str(), o.as_str())
+    }
+    #[inline]
+    fn ne(&self, o: &Self) -> bool {
+        !Substr::shallow_eq(self, o) && PartialEq::ne(self.as_str(), o.as_str())
+    }
+}
+
+impl PartialEq<ArcStr> for Substr {
+    #[inline]
+    fn eq(&self, o: &ArcStr) -> bool {
         (ArcStr::ptr_eq(&self.0, o) && (self.1 == 0) && (self.2 as usize == o.len()))
             || PartialEq::eq(self.as_str(), o.as_str())
     }
@@ -621,7 +1578,7 @@ impl Eq for Substr {}
 impl PartialOrd for Substr {
     #[inline]
     fn partial_cmp(&self, s: &Self) -> Option<core::cmp::Ordering> {
-        Some(self.as_str().cmp(s.as_str()))
+        Some(self.cmp(s))
     }
 }
 
@@ -632,6 +1589,23 @@ impl Ord for Substr {
     }
 }
 
+impl PartialOrd<ArcStr> for Substr {
+    #[inline]
+    fn partial_cmp(&self, s: &ArcStr) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(self.as_str(), s.as_str())
+    }
+}
+
+impl PartialOrd<Substr> for ArcStr {
+    #[inline]
+    fn partial_cmp(&self, s: &Substr) -> Option<core::cmp::Ordering> {
+        PartialOrd::partial_cmp(self.as_str(), s.as_str())
+    }
+}
+
+/// Hashes exactly the same as the `str` we contain, and so the same as an
+/// `ArcStr` with the same contents. This is relied on for `Borrow<str>`, which
+/// lets a `HashMap` with `Substr` keys be queried with a `&str`.
 impl core::hash::Hash for Substr {
     #[inline]
     fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
@@ -728,6 +1702,30 @@ impl_peq! {
     (Substr, alloc::boxed::Box<str>),
     (Substr, alloc::sync::Arc<str>),
     (Substr, alloc::rc::Rc<str>),
+    (Substr, alloc::sync::Arc<alloc::string::String>),
+    (Substr, alloc::rc::Rc<alloc::string::String>),
+}
+
+macro_rules! impl_pord {
+    (@one $a:ty, $b:ty) => {
+        #[allow(clippy::extra_unused_lifetimes)]
+        impl<'a> PartialOrd<$b> for $a {
+            #[inline]
+            fn partial_cmp(&self, s: &$b) -> Option<core::cmp::Ordering> {
+                PartialOrd::partial_cmp(&self[..], &s[..])
+            }
+        }
+    };
+    ($(($a:ty, $b:ty),)+) => {$(
+        impl_pord!(@one $a, $b);
+        impl_pord!(@one $b, $a);
+    )+};
+}
+
+impl_pord! {
+    (Substr, str),
+    (Substr, &'a str),
+    (Substr, alloc::string::String),
 }
 
 macro_rules! impl_index {
@@ -772,6 +1770,9 @@ impl core::borrow::Borrow<str> for Substr {
     }
 }
 
+/// Always copies `s` into a new allocation: a `&'static str` can't be turned
+/// into a static `Substr` at runtime. Use
+/// [`arcstr::literal_substr!`](crate::literal_substr) for string literals.
 impl core::str::FromStr for Substr {
     type Err = core::convert::Infallible;
     #[inline]
@@ -780,6 +1781,425 @@ impl core::str::FromStr for Substr {
     }
 }
 
+/// An iterator over the parts of a [`Substr`] separated by a string.
+///
+/// Created by [`Substr::split_as_substrs`]. Each item is a `Substr` which
+/// shares the same parent as the original.
+///
+/// Note that, as with the `str::Split` iterator produced by `str::split` with
+/// a `&str` pattern, this is not a `DoubleEndedIterator`: searching from the
+/// back may find different separators than searching from the front, if they
+/// overlap (for example, `"aaa".split("aa")`).
+#[derive(Clone, Debug)]
+pub struct SubstrSplit<'a> {
+    parent: ArcStr,
+    // Start of the next item.
+    start: usize,
+    // Where to search for the next separator. Always `>= start`, and may be
+    // past `end` if no more separators may be found (this only happens with
+    // an empty separator).
+    search: usize,
+    end: usize,
+    sep: &'a str,
+    finished: bool,
+}
+
+impl Iterator for SubstrSplit<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        if self.finished {
+            return None;
+        }
+        let found = self
+            .parent
+            .get(self.search..self.end)
+            .and_then(|haystack| haystack.find(self.sep));
+        match found {
+            Some(i) => {
+                let sep_start = self.search + i;
+                let item = Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    sep_start as Idx,
+                );
+                self.start = sep_start + self.sep.len();
+                self.search = self.start;
+                if self.sep.is_empty() {
+                    // An empty separator matches at every char boundary, so
+                    // skip over the next char (or past the end) to avoid
+                    // matching at the same position forever.
+                    self.search += self.parent[self.start..self.end]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+                }
+                Some(item)
+            }
+            None => {
+                self.finished = true;
+                Some(Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    self.end as Idx,
+                ))
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for SubstrSplit<'_> {}
+
+/// An iterator over the parts of a [`Substr`] separated by a string, where
+/// each part includes the separator that ends it.
+///
+/// Created by [`Substr::split_inclusive_as_substrs`]. Each item is a `Substr`
+/// which shares the same parent as the original. As with [`SubstrSplit`], this
+/// is not a `DoubleEndedIterator`.
+#[derive(Clone, Debug)]
+pub struct SubstrSplitInclusive<'a> {
+    parent: ArcStr,
+    // These are the same as in `SubstrSplit`.
+    start: usize,
+    search: usize,
+    end: usize,
+    sep: &'a str,
+    finished: bool,
+}
+
+impl Iterator for SubstrSplitInclusive<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        if self.finished {
+            return None;
+        }
+        let found = self
+            .parent
+            .get(self.search..self.end)
+            .and_then(|haystack| haystack.find(self.sep));
+        match found {
+            Some(i) => {
+                let sep_end = self.search + i + self.sep.len();
+                let item = Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    sep_end as Idx,
+                );
+                self.start = sep_end;
+                self.search = sep_end;
+                if self.sep.is_empty() {
+                    self.search += self.parent[self.start..self.end]
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+                }
+                Some(item)
+            }
+            None => {
+                self.finished = true;
+                // Unlike `split`, there's no empty item after a trailing
+                // separator.
+                if self.start == self.end {
+                    return None;
+                }
+                Some(Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    self.end as Idx,
+                ))
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for SubstrSplitInclusive<'_> {}
+
+/// An iterator over the parts of a [`Substr`] separated by a string, limited
+/// to a given number of items.
+///
+/// Created by [`Substr::splitn_as_substrs`]. Each item is a `Substr` which
+/// shares the same parent as the original.
+#[derive(Clone, Debug)]
+pub struct SubstrSplitN<'a> {
+    split: SubstrSplit<'a>,
+    // The number of items we may still produce.
+    n: usize,
+}
+
+impl Iterator for SubstrSplitN<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        match self.n {
+            0 => None,
+            1 => {
+                self.n = 0;
+                let split = &mut self.split;
+                if split.finished {
+                    None
+                } else {
+                    split.finished = true;
+                    Some(Substr(
+                        ArcStr::clone(&split.parent),
+                        split.start as Idx,
+                        split.end as Idx,
+                    ))
+                }
+            }
+            _ => {
+                self.n -= 1;
+                self.split.next()
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 || self.split.finished {
+            (0, Some(0))
+        } else {
+            (1, Some(self.n))
+        }
+    }
+}
+
+impl core::iter::FusedIterator for SubstrSplitN<'_> {}
+
+/// An iterator over the parts of a [`Substr`] separated by a string, starting
+/// from the end, and limited to a given number of items.
+///
+/// Created by [`Substr::rsplitn_as_substrs`]. Each item is a `Substr` which
+/// shares the same parent as the original.
+#[derive(Clone, Debug)]
+pub struct SubstrRSplitN<'a> {
+    parent: ArcStr,
+    start: usize,
+    // End of the next item.
+    end: usize,
+    // Where the search for the next separator ends. Always `<= end`.
+    search_end: usize,
+    sep: &'a str,
+    // The number of items we may still produce.
+    n: usize,
+}
+
+impl Iterator for SubstrRSplitN<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        match self.n {
+            0 => return None,
+            1 => {
+                self.n = 0;
+                return Some(Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    self.end as Idx,
+                ));
+            }
+            _ => self.n -= 1,
+        }
+        match self.parent[self.start..self.search_end].rfind(self.sep) {
+            Some(i) => {
+                let sep_start = self.start + i;
+                let item = Substr(
+                    ArcStr::clone(&self.parent),
+                    (sep_start + self.sep.len()) as Idx,
+                    self.end as Idx,
+                );
+                self.end = sep_start;
+                self.search_end = sep_start;
+                if self.sep.is_empty() {
+                    // As in `SubstrSplit`, skip over the previous char so that
+                    // we don't match at the same position forever. If there
+                    // isn't one, the start of the string is all that's left.
+                    match self.parent[self.start..self.end].chars().next_back() {
+                        Some(c) => self.search_end -= c.len_utf8(),
+                        None => self.n = self.n.min(1),
+                    }
+                }
+                Some(item)
+            }
+            None => {
+                self.n = 0;
+                Some(Substr(
+                    ArcStr::clone(&self.parent),
+                    self.start as Idx,
+                    self.end as Idx,
+                ))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ((self.n != 0) as usize, Some(self.n))
+    }
+}
+
+impl core::iter::FusedIterator for SubstrRSplitN<'_> {}
+
+/// An iterator over the non-overlapping occurrences of a string in a
+/// [`Substr`].
+///
+/// Created by [`Substr::matches_as_substrs`]. Each item is a `Substr` which
+/// shares the same parent as the original.
+#[derive(Clone, Debug)]
+pub struct SubstrMatches<'a> {
+    parent: ArcStr,
+    // Where to search for the next match, in the parent. May be past `end`
+    // once no more matches may be found (this only happens with an empty
+    // pattern).
+    search: usize,
+    end: usize,
+    pat: &'a str,
+}
+
+impl Iterator for SubstrMatches<'_> {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let i = self.parent.get(self.search..self.end)?.find(self.pat)?;
+        let start = self.search + i;
+        let end = start + self.pat.len();
+        self.search = end;
+        if self.pat.is_empty() {
+            // Same as in `SubstrSplit`: skip the next char (or past the end),
+            // so we don't keep matching at the same position.
+            self.search += self.parent[end..self.end]
+                .chars()
+                .next()
+                .map_or(1, char::len_utf8);
+        }
+        Some(Substr(
+            ArcStr::clone(&self.parent),
+            start as Idx,
+            end as Idx,
+        ))
+    }
+}
+
+impl core::iter::FusedIterator for SubstrMatches<'_> {}
+
+/// An iterator over the lines of a [`Substr`].
+///
+/// Created by [`Substr::lines_as_substrs`]. Each item is a `Substr` which
+/// shares the same parent as the original.
+#[derive(Clone, Debug)]
+pub struct SubstrLines {
+    parent: ArcStr,
+    start: usize,
+    end: usize,
+}
+
+impl Iterator for SubstrLines {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        if self.start >= self.end {
+            return None;
+        }
+        let rest = &self.parent[self.start..self.end];
+        let (line_end, next_start) = match rest.find('\n') {
+            Some(i) => {
+                let line = &rest[..i];
+                let len = line.strip_suffix('\r').unwrap_or(line).len();
+                (self.start + len, self.start + i + 1)
+            }
+            None => (self.end, self.end),
+        };
+        let item = Substr(
+            ArcStr::clone(&self.parent),
+            self.start as Idx,
+            line_end as Idx,
+        );
+        self.start = next_start;
+        Some(item)
+    }
+}
+
+impl core::iter::FusedIterator for SubstrLines {}
+
+/// An iterator over the `char`s of a [`Substr`], as `Substr`s.
+///
+/// Created by [`Substr::chars_as_substrs`]. Each item is a `Substr` which
+/// covers a single `char`, and shares the same parent as the original.
+#[derive(Clone, Debug)]
+pub struct SubstrChars {
+    parent: ArcStr,
+    start: usize,
+    end: usize,
+}
+
+impl Iterator for SubstrChars {
+    type Item = Substr;
+    #[inline]
+    fn next(&mut self) -> Option<Substr> {
+        let c = self.parent[self.start..self.end].chars().next()?;
+        let char_end = self.start + c.len_utf8();
+        let item = Substr(
+            ArcStr::clone(&self.parent),
+            self.start as Idx,
+            char_end as Idx,
+        );
+        self.start = char_end;
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Same as `core::str::Chars`: each char is between 1 and 4 bytes.
+        let len = self.end - self.start;
+        ((len + 3) / 4, Some(len))
+    }
+}
+
+impl core::iter::FusedIterator for SubstrChars {}
+
+/// The iterator used for `iter_indices`/`iter_offsets` by the `nom` and
+/// `winnow` integrations.
+///
+/// This is like [`core::str::CharIndices`], but owns (a reference to) the
+/// string data. The indices are relative to the start of the `Substr`.
+///
+/// Not exported, as it's only nameable through those traits.
+#[cfg(any(feature = "nom", feature = "winnow"))]
+#[derive(Clone, Debug)]
+pub struct CharIndices {
+    s: Substr,
+    front: usize,
+}
+
+#[cfg(any(feature = "nom", feature = "winnow"))]
+impl CharIndices {
+    #[inline]
+    pub(crate) fn new(s: Substr) -> Self {
+        Self { s, front: 0 }
+    }
+}
+
+#[cfg(any(feature = "nom", feature = "winnow"))]
+impl Iterator for CharIndices {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, char)> {
+        let c = self.s[self.front..].chars().next()?;
+        let i = self.front;
+        self.front += c.len_utf8();
+        Some((i, c))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.s[self.front..].chars().size_hint()
+    }
+}
+
+#[cfg(any(feature = "nom", feature = "winnow"))]
+impl core::iter::FusedIterator for CharIndices {}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,118 @@
+//! `feature = "substr"`: Helpers for tests of code which produces [`Substr`]s.
+//!
+//! Checking the contents of a `Substr` (for example, one produced by a parser)
+//! isn't always enough: sometimes you also want to know where in the input it
+//! came from, or that it borrows from the input rather than being a copy. These
+//! functions make those checks, with more helpful failure messages than
+//! comparing `Substr::range` by hand.
+//!
+//! # Example
+//!
+//! ```
+//! use arcstr::{testing, ArcStr, Substr};
+//! // A (very) simple parser.
+//! fn parse_assignment(input: &ArcStr) -> Option<(Substr, Substr)> {
+//!     let (k, v) = input.split_once('=')?;
+//!     Some((input.substr_from(k.trim()), input.substr_from(v.trim())))
+//! }
+//! let input = ArcStr::from("answer = 42");
+//! let (key, value) = parse_assignment(&input).unwrap();
+//! assert_eq!(key, "answer");
+//! testing::assert_range_eq(&key, 0..6);
+//! testing::assert_range_eq(&value, 9..11);
+//! assert!(testing::shares_parent(&key, &value));
+//! ```
+use crate::{ArcStr, Substr};
+use core::ops::Range;
+
+/// Returns true if `s` occupies exactly `expected` inside its parent.
+///
+/// This is the same as `s.range() == expected`. See [`assert_range_eq`] for a
+/// version which panics with a description of the mismatch.
+///
+/// # Examples
+///
+/// ```
+/// # use arcstr::{testing, ArcStr};
+/// let parent = ArcStr::from("abc def");
+/// assert!(testing::parent_range_is(&parent.substr(4..), 4..7));
+/// assert!(!testing::parent_range_is(&parent.substr(4..), 0..3));
+/// ```
+#[inline]
+pub fn parent_range_is(s: &Substr, expected: Range<usize>) -> bool {
+    s.range() == expected
+}
+
+/// Asserts that `s` occupies exactly `expected` inside its parent.
+///
+/// # Panics
+///
+/// Panics if `s.range() != expected`, with a message showing both ranges, the
+/// contents of `s`, and what the parent holds at `expected` (if that's a valid
+/// range of it).
+///
+/// # Examples
+///
+/// ```should_panic
+/// # use arcstr::{testing, ArcStr};
+/// let input = ArcStr::from("x = y");
+/// // Panics with:
+/// // Substr range mismatch
+/// //   actual:   4..5 ("y")
+/// //   expected: 0..1 ("x")
+/// testing::assert_range_eq(&input.substr(4..), 0..1);
+/// ```
+#[track_caller]
+pub fn assert_range_eq(s: &Substr, expected: Range<usize>) {
+    let actual = s.range();
+    if actual != expected {
+        range_mismatch(s, actual, expected);
+    }
+}
+
+/// Returns true if `a` and `b` are substrings of the same parent `ArcStr` (by
+/// pointer, not by contents).
+///
+/// This is the same as `ArcStr::ptr_eq(a.parent(), b.parent())`, and is useful
+/// for checking that a parser's output borrows from its input instead of
+/// copying it.
+///
+/// # Examples
+///
+/// ```
+/// # use arcstr::{testing, ArcStr, Substr};
+/// let input = ArcStr::from("key = value");
+/// let key = input.substr(..3);
+/// let value = input.substr(6..);
+/// assert!(testing::shares_parent(&key, &value));
+///
+/// let copy = Substr::from("key");
+/// assert_eq!(copy, key);
+/// assert!(!testing::shares_parent(&key, &copy));
+/// ```
+#[inline]
+pub fn shares_parent(a: &Substr, b: &Substr) -> bool {
+    ArcStr::ptr_eq(a.parent(), b.parent())
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn range_mismatch(s: &Substr, actual: Range<usize>, expected: Range<usize>) -> ! {
+    match s.parent().get(expected.clone()) {
+        Some(at_expected) => panic!(
+            "Substr range mismatch\n  actual:   {:?} ({:?})\n  expected: {:?} ({:?})",
+            actual,
+            s.as_str(),
+            expected,
+            at_expected,
+        ),
+        None => panic!(
+            "Substr range mismatch\n  actual:   {:?} ({:?})\n  expected: {:?} (not a valid range of the parent, which has length {})",
+            actual,
+            s.as_str(),
+            expected,
+            s.parent().len(),
+        ),
+    }
+}
@@ -0,0 +1,288 @@
+use crate::ArcStr;
+use core::marker::PhantomData;
+
+/// `feature = "typed"`: An [`ArcStr`] tagged with a type, so that different
+/// kinds of string can't be mixed up.
+///
+/// `Tag` is usually an empty type which exists only to be used here. A
+/// `TypedArcStr<UserId>` and a `TypedArcStr<ProductName>` are different types,
+/// so passing one where the other is expected, or comparing one with the
+/// other, is a compile error, even though they're both just strings.
+///
+/// The tag doesn't affect the representation: this is exactly the same size as
+/// an `ArcStr`, and cloning it is just as cheap. It also doesn't matter which
+/// traits `Tag` implements: a `TypedArcStr` is always `Clone`, `Send`, `Sync`,
+/// `Eq`, `Hash` and so on, regardless.
+///
+/// The `ArcStr` API is available through `Deref`, and a `TypedArcStr` can be
+/// turned back into a plain `ArcStr` with [`TypedArcStr::into_arcstr`] (or
+/// `From`). Going the other way needs the tag to be named explicitly, with
+/// [`ArcStr::typed`] or [`TypedArcStr::new`]. Static ones can be created with
+/// [`arcstr::typed_literal!`](crate::typed_literal).
+///
+/// # Examples
+///
+/// ```
+/// use arcstr::{ArcStr, TypedArcStr};
+///
+/// enum UserId {}
+/// enum ProductName {}
+///
+/// fn lookup_user(id: &TypedArcStr<UserId>) -> Option<u32> {
+///     if id == "alice" { Some(1) } else { None }
+/// }
+///
+/// let id: TypedArcStr<UserId> = ArcStr::from("alice").typed();
+/// assert_eq!(lookup_user(&id), Some(1));
+/// // The `ArcStr` (and `str`) API is still there.
+/// assert_eq!(id.len(), 5);
+/// assert!(id.starts_with("al"));
+///
+/// const WIDGET: TypedArcStr<ProductName> = arcstr::typed_literal!("widget", ProductName);
+/// assert_eq!(WIDGET, "widget");
+/// ```
+///
+/// Mixing up the two is a compile error:
+///
+/// ```compile_fail
+/// # use arcstr::{ArcStr, TypedArcStr};
+/// # enum UserId {}
+/// # enum ProductName {}
+/// # fn lookup_user(id: &TypedArcStr<UserId>) -> Option<u32> { None }
+/// let name: TypedArcStr<ProductName> = ArcStr::from("alice").typed();
+/// lookup_user(&name);
+/// ```
+///
+/// ```compile_fail
+/// # use arcstr::{ArcStr, TypedArcStr};
+/// # enum UserId {}
+/// # enum ProductName {}
+/// let id: TypedArcStr<UserId> = ArcStr::from("x").typed();
+/// let name: TypedArcStr<ProductName> = ArcStr::from("x").typed();
+/// assert!(id != name);
+/// ```
+#[repr(transparent)]
+pub struct TypedArcStr<Tag> {
+    s: ArcStr,
+    _tag: PhantomData<Tagged<Tag>>,
+}
+
+// `fn() -> Tag` so that we're `Send`/`Sync` (and covariant) whatever `Tag` is.
+// It's wrapped in a struct because older compilers (including our MSRV) don't
+// allow function pointer types to appear in a `const fn` like `new`.
+struct Tagged<Tag>(#[allow(dead_code)] fn() -> Tag);
+
+impl<Tag> TypedArcStr<Tag> {
+    /// Tags `s` with `Tag`.
+    ///
+    /// This is a `const fn`, but [`arcstr::typed_literal!`](crate::typed_literal)
+    /// is usually more convenient for creating constants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct Sql;
+    /// let ident = TypedArcStr::<Sql>::new(ArcStr::from("users"));
+    /// assert_eq!(ident, "users");
+    /// ```
+    #[inline]
+    pub const fn new(s: ArcStr) -> Self {
+        Self {
+            s,
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns the `ArcStr` inside us.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct Sql;
+    /// let ident: TypedArcStr<Sql> = ArcStr::from("users").typed();
+    /// let s: &ArcStr = ident.as_arcstr();
+    /// assert_eq!(s, "users");
+    /// ```
+    #[inline]
+    pub fn as_arcstr(&self) -> &ArcStr {
+        &self.s
+    }
+
+    /// Extract a string slice containing our data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct Sql;
+    /// let ident: TypedArcStr<Sql> = ArcStr::from("users").typed();
+    /// assert_eq!(ident.as_str(), "users");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.s
+    }
+
+    /// Removes the tag, returning the `ArcStr` inside us.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct Sql;
+    /// let ident: TypedArcStr<Sql> = ArcStr::from("users").typed();
+    /// let s: ArcStr = ident.into_arcstr();
+    /// assert_eq!(s, "users");
+    /// ```
+    #[inline]
+    pub fn into_arcstr(self) -> ArcStr {
+        self.s
+    }
+
+    /// Returns true if the two `TypedArcStr`s point to the same allocation.
+    /// See [`ArcStr::ptr_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct Sql;
+    /// let a: TypedArcStr<Sql> = ArcStr::from("users").typed();
+    /// assert!(TypedArcStr::ptr_eq(&a, &a.clone()));
+    /// let b: TypedArcStr<Sql> = ArcStr::from("users").typed();
+    /// assert!(!TypedArcStr::ptr_eq(&a, &b));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(lhs: &Self, rhs: &Self) -> bool {
+        ArcStr::ptr_eq(&lhs.s, &rhs.s)
+    }
+}
+
+impl ArcStr {
+    /// `feature = "typed"`: Tags this string with `Tag`, producing a
+    /// [`TypedArcStr<Tag>`](TypedArcStr).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use arcstr::{ArcStr, TypedArcStr};
+    /// struct UserId;
+    /// let id = ArcStr::from("alice").typed::<UserId>();
+    /// assert_eq!(id, "alice");
+    /// ```
+    #[inline]
+    pub fn typed<Tag>(self) -> TypedArcStr<Tag> {
+        TypedArcStr::new(self)
+    }
+}
+
+impl<Tag> Clone for TypedArcStr<Tag> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.s.clone())
+    }
+}
+
+impl<Tag> Default for TypedArcStr<Tag> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(ArcStr::new())
+    }
+}
+
+impl<Tag> core::ops::Deref for TypedArcStr<Tag> {
+    type Target = ArcStr;
+    #[inline]
+    fn deref(&self) -> &ArcStr {
+        &self.s
+    }
+}
+
+impl<Tag> PartialEq for TypedArcStr<Tag> {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        self.s == o.s
+    }
+}
+
+impl<Tag> Eq for TypedArcStr<Tag> {}
+
+impl<Tag> PartialEq<str> for TypedArcStr<Tag> {
+    #[inline]
+    fn eq(&self, o: &str) -> bool {
+        self.as_str() == o
+    }
+}
+
+impl<Tag> PartialEq<&str> for TypedArcStr<Tag> {
+    #[inline]
+    fn eq(&self, o: &&str) -> bool {
+        self.as_str() == *o
+    }
+}
+
+impl<Tag> PartialOrd for TypedArcStr<Tag> {
+    #[inline]
+    fn partial_cmp(&self, o: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+impl<Tag> Ord for TypedArcStr<Tag> {
+    #[inline]
+    fn cmp(&self, o: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(o.as_str())
+    }
+}
+
+/// Hashes the same as the `str`, which is consistent with `Borrow<str>`.
+impl<Tag> core::hash::Hash for TypedArcStr<Tag> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.as_str().hash(h)
+    }
+}
+
+impl<Tag> core::fmt::Debug for TypedArcStr<Tag> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<Tag> core::fmt::Display for TypedArcStr<Tag> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<Tag> AsRef<str> for TypedArcStr<Tag> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Tag> AsRef<ArcStr> for TypedArcStr<Tag> {
+    #[inline]
+    fn as_ref(&self) -> &ArcStr {
+        &self.s
+    }
+}
+
+impl<Tag> core::borrow::Borrow<str> for TypedArcStr<Tag> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<Tag> From<TypedArcStr<Tag>> for ArcStr {
+    #[inline]
+    fn from(s: TypedArcStr<Tag>) -> Self {
+        s.s
+    }
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "bytes-type")]
+#![allow(
+    // need to test cloning, these are deliberate.
+    clippy::redundant_clone,
+    // yep, we create owned instance just for comparison, to test comparison
+    // with owned instances.
+    clippy::cmp_owned,
+)]
+
+use arcstr::{ArcBytes, ArcStr};
+
+#[test]
+fn test_basic() {
+    let b = ArcBytes::from(&b"\xff\x00\xfe"[..]);
+    assert_eq!(b.len(), 3);
+    assert!(!b.is_empty());
+    assert_eq!(b.as_bytes(), b"\xff\x00\xfe");
+    assert_eq!(&b[1..], b"\x00\xfe");
+    assert_eq!(ArcBytes::as_static(&b), None);
+    assert!(!ArcBytes::is_static(&b));
+
+    let e = ArcBytes::new();
+    assert!(e.is_empty());
+    assert_eq!(e, ArcBytes::default());
+    assert_eq!(ArcBytes::from(&[][..]), e);
+    assert_eq!(ArcBytes::as_static(&e), Some(&[][..]));
+}
+
+#[test]
+fn test_clone_drop() {
+    let a = ArcBytes::from(vec![1, 2, 3]);
+    assert_eq!(ArcBytes::strong_count(&a), Some(1));
+    let b = a.clone();
+    assert!(ArcBytes::ptr_eq(&a, &b));
+    assert_eq!(ArcBytes::strong_count(&a), Some(2));
+    drop(b);
+    assert_eq!(ArcBytes::strong_count(&a), Some(1));
+    let c = ArcBytes::from(&a);
+    assert!(ArcBytes::ptr_eq(&a, &c));
+    assert!(!ArcBytes::ptr_eq(&a, &ArcBytes::from(&[1, 2, 3])));
+}
+
+#[test]
+fn test_literal() {
+    const LIT: ArcBytes = arcstr::literal_bytes!(b"abc\x80");
+    assert_eq!(LIT, b"abc\x80");
+    assert!(ArcBytes::is_static(&LIT));
+    assert_eq!(ArcBytes::strong_count(&LIT), None);
+    assert_eq!(ArcBytes::as_static(&LIT), Some(&b"abc\x80"[..]));
+    let copy = LIT.clone();
+    assert!(ArcBytes::ptr_eq(&LIT, &copy));
+
+    const EMPTY: ArcBytes = arcstr::literal_bytes!(b"");
+    assert!(EMPTY.is_empty());
+    const FROM_SLICE: &[u8] = &[0, 1, 2];
+    assert_eq!(arcstr::literal_bytes!(FROM_SLICE), [0u8, 1, 2]);
+}
+
+#[test]
+fn test_from_arcstr() {
+    let s = ArcStr::from("shared");
+    let b = ArcBytes::from(s.clone());
+    assert_eq!(b, b"shared");
+    assert_eq!(b.as_ptr(), s.as_ptr());
+    assert_eq!(ArcStr::strong_count(&s), Some(2));
+    drop(b);
+    assert_eq!(ArcStr::strong_count(&s), Some(1));
+
+    let lit = ArcBytes::from(&arcstr::literal!("static"));
+    assert_eq!(ArcBytes::as_static(&lit), Some(&b"static"[..]));
+}
+
+#[test]
+fn test_traits() {
+    use std::collections::HashSet;
+    let a = ArcBytes::from(&b"abc"[..]);
+    let b = ArcBytes::from(&b"abd"[..]);
+    assert!(a < b);
+    assert_eq!(a, *b"abc".as_slice());
+    assert_eq!(a, &b"abc"[..]);
+    assert_eq!(&b"abc"[..], a);
+    assert_eq!(a, b"abc".to_vec());
+    assert_eq!(b"abc".to_vec(), a);
+    assert_eq!(a, Box::<[u8]>::from(&b"abc"[..]));
+    assert_eq!(a, std::borrow::Cow::Borrowed(&b"abc"[..]));
+    assert_eq!(Vec::from(a.clone()), b"abc");
+    assert_eq!(ArcBytes::from("abc"), a);
+    assert_eq!(format!("{:?}", a), format!("{:?}", b"abc".as_slice()));
+
+    let mut set = HashSet::new();
+    set.insert(a.clone());
+    assert!(set.contains(&b"abc"[..]));
+    assert!(!set.contains(&b"abd"[..]));
+    let r: &[u8] = a.as_ref();
+    assert_eq!(r, b"abc");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+    let b = ArcBytes::from(&b"\xff\x00"[..]);
+    assert_tokens(&b, &[Token::Bytes(b"\xff\x00")]);
+    assert_de_tokens(&b, &[Token::ByteBuf(b"\xff\x00")]);
+    assert_de_tokens(&b, &[Token::BorrowedBytes(b"\xff\x00")]);
+    assert_de_tokens(
+        &b,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U8(0xff),
+            Token::U8(0),
+            Token::SeqEnd,
+        ],
+    );
+    assert_de_tokens(&ArcBytes::from("abc"), &[Token::Str("abc")]);
+}
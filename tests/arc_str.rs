@@ -101,6 +101,107 @@ fn smoke_test_clone() {
     drop(vec![ArcStr::default(); count]);
 }
 
+#[test]
+fn test_try_reuse() {
+    let a = ArcStr::from("🙀abc");
+    let p = a.as_ptr();
+    let a = ArcStr::try_reuse(a, "abc🙀");
+    assert_eq!(a, "abc🙀");
+    assert_eq!(a.as_ptr(), p);
+    assert_eq!(ArcStr::strong_count(&a), Some(1));
+
+    let shared = a.clone();
+    let b = ArcStr::try_reuse(a, "xyzw!!!");
+    assert_eq!(b, "xyzw!!!");
+    assert_eq!(shared, "abc🙀");
+    assert_ne!(b.as_ptr(), p);
+    assert_eq!(ArcStr::strong_count(&shared), Some(1));
+
+    let c = ArcStr::try_reuse(shared, "short");
+    assert_eq!(c, "short");
+    assert_ne!(c.as_ptr(), p);
+
+    // Static strings are never written to.
+    let lit = arcstr::literal!("abc");
+    let d = ArcStr::try_reuse(lit.clone(), "def");
+    assert_eq!((lit.as_str(), d.as_str()), ("abc", "def"));
+    assert!(!ArcStr::is_static(&d));
+    let e = ArcStr::try_reuse(ArcStr::new(), "");
+    assert_eq!(e, "");
+}
+
+#[test]
+fn test_weak() {
+    use arcstr::WeakArcStr;
+    let a = ArcStr::from("abc");
+    let w: WeakArcStr = ArcStr::downgrade(&a);
+    let w2 = w.clone();
+    assert!(w.ptr_eq(&w2));
+    assert_eq!(w.strong_count(), Some(1));
+    assert_eq!(ArcStr::strong_count(&a), Some(1));
+
+    let b = w2.upgrade().unwrap();
+    assert!(ArcStr::ptr_eq(&a, &b));
+    assert_eq!(w.strong_count(), Some(2));
+    drop(w2);
+
+    // Weak refs prevent in-place reuse, since they could upgrade at any time.
+    drop(b);
+    let p = a.as_ptr();
+    let a = ArcStr::try_reuse(a, "def");
+    assert_ne!(a.as_ptr(), p);
+    assert_eq!(a, "def");
+    assert_eq!(w.strong_count(), Some(0));
+    assert_eq!(w.upgrade(), None);
+    assert_eq!(format!("{:?}", w), "(WeakArcStr)");
+
+    // But not once they're gone.
+    let w = ArcStr::downgrade(&a);
+    drop(w);
+    let p = a.as_ptr();
+    let a = ArcStr::try_reuse(a, "ghi");
+    assert_eq!(a.as_ptr(), p);
+
+    // The weak ref can outlive the strong ones.
+    let w = ArcStr::downgrade(&a);
+    drop(a);
+    let w3 = w.clone();
+    drop(w);
+    assert!(w3.upgrade().is_none());
+
+    // Static strings can always be upgraded.
+    let lit = arcstr::literal!("static");
+    let w = ArcStr::downgrade(&lit);
+    drop(lit);
+    assert_eq!(w.strong_count(), None);
+    assert!(ArcStr::is_static(&w.upgrade().unwrap()));
+    assert!(ArcStr::downgrade(&ArcStr::new())
+        .upgrade()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[allow(unknown_lints)]
+#[cfg_attr(asan, ignore)] // Leaks memory intentionally
+fn test_weak_leak() {
+    let a = ArcStr::from("leaky");
+    let w = ArcStr::downgrade(&a);
+    let leaked: &'static str = a.leak();
+    drop(a);
+    let up = w.upgrade().unwrap();
+    assert_eq!(up, leaked);
+    assert!(ArcStr::is_static(&up));
+    assert_eq!(w.strong_count(), None);
+    drop(w);
+
+    // Unique, so this takes the non-atomic path in `leak`.
+    let b = ArcStr::from("unique");
+    let _ = b.leak();
+    let w = ArcStr::downgrade(&b);
+    assert_eq!(w.upgrade().unwrap(), "unique");
+}
+
 #[test]
 fn test_btreemap() {
     let mut m = std::collections::BTreeMap::new();
@@ -153,6 +254,594 @@ fn test_serde() {
     }
 }
 
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_postcard() {
+    for s in ["", "abc", "test test 123 456", "🙀 unicode 🙀"] {
+        let arc = ArcStr::from(s);
+        let bytes = postcard::to_allocvec(&arc).unwrap();
+        assert_eq!(bytes, postcard::to_allocvec(s).unwrap());
+        let back: ArcStr = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(back, s);
+        // `to_slice` doesn't use anything from `std`.
+        let mut buf = [0u8; 64];
+        let used = postcard::to_slice(&arc, &mut buf).unwrap();
+        assert_eq!(used, &bytes[..]);
+    }
+    assert!(postcard::from_bytes::<ArcStr>(&[2, 0xff, 0xfe]).is_err());
+}
+
+#[cfg(feature = "proptest")]
+mod proptest_strategies {
+    use arcstr::ArcStr;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn arcstr_strategy_valid(s in arcstr::proptest::arcstr_strategy()) {
+            prop_assert!(!ArcStr::is_static(&s) || s.is_empty());
+            prop_assert_eq!(ArcStr::from(s.as_str()), s.clone());
+            prop_assert_eq!(s.len(), s.as_str().len());
+        }
+
+        #[test]
+        fn static_arcstr_strategy_valid(s in arcstr::proptest::static_arcstr_strategy()) {
+            prop_assert!(ArcStr::is_static(&s));
+            prop_assert_eq!(ArcStr::as_static(&s), Some(s.as_str()));
+        }
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[test]
+#[cfg_attr(any(miri, msan), ignore)] // Calls into libpython
+fn test_pyo3() {
+    use pyo3::exceptions::{PyTypeError, PyUnicodeDecodeError};
+    use pyo3::prelude::*;
+    use pyo3::types::{PyByteArray, PyBytes};
+    Python::initialize();
+    Python::attach(|py| {
+        let s = ArcStr::from("test test 123 456 🙀");
+        let obj = s.clone().into_pyobject(py).unwrap();
+        assert_eq!(obj.to_str().unwrap(), s.as_str());
+        assert_eq!(obj.extract::<ArcStr>().unwrap(), s);
+        let obj = (&s).into_pyobject(py).unwrap();
+        assert_eq!(obj.extract::<ArcStr>().unwrap(), s);
+        // Round trip through some Python code.
+        let upper = obj.call_method0("upper").unwrap();
+        assert_eq!(upper.extract::<ArcStr>().unwrap(), "TEST TEST 123 456 🙀");
+
+        let bytes = PyBytes::new(py, "abc 🙀".as_bytes());
+        assert_eq!(bytes.extract::<ArcStr>().unwrap(), "abc 🙀");
+        let bytearray = PyByteArray::new(py, b"def");
+        assert_eq!(bytearray.extract::<ArcStr>().unwrap(), "def");
+
+        let bad = PyBytes::new(py, b"ab\xffcd");
+        let err = bad.extract::<ArcStr>().unwrap_err();
+        assert!(err.is_instance_of::<PyUnicodeDecodeError>(py));
+        let bad = PyByteArray::new(py, b"\xff");
+        let err = bad.extract::<ArcStr>().unwrap_err();
+        assert!(err.is_instance_of::<PyUnicodeDecodeError>(py));
+        let err = 123i32
+            .into_pyobject(py)
+            .unwrap()
+            .extract::<ArcStr>()
+            .unwrap_err();
+        assert!(err.is_instance_of::<PyTypeError>(py));
+    });
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn test_quickcheck() {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+    fn roundtrips(s: ArcStr) -> bool {
+        ArcStr::from(s.as_str()) == s
+    }
+    QuickCheck::new()
+        .tests(100)
+        .quickcheck(roundtrips as fn(ArcStr) -> bool);
+
+    // Shrink a counterexample to "has fewer than 3 chars" the way quickcheck
+    // does, and make sure we get down to a minimal one.
+    let fails = |s: &ArcStr| s.chars().count() >= 3;
+    let mut g = Gen::from_size_and_seed(100, 1);
+    let mut s = ArcStr::arbitrary(&mut g);
+    while !fails(&s) {
+        s = ArcStr::arbitrary(&mut g);
+    }
+    while let Some(smaller) = s.shrink().find(fails) {
+        assert!(smaller.len() <= s.len());
+        s = smaller;
+    }
+    assert_eq!(s.chars().count(), 3);
+}
+
+#[cfg(all(feature = "sqlx", not(any(target_arch = "wasm32", loom, msrv))))]
+#[test]
+#[cfg_attr(any(miri, msan), ignore)] // Calls into SQLite
+fn test_sqlx() {
+    use sqlx::{Connection, Row};
+    futures_executor::block_on(async {
+        let mut conn = sqlx::SqliteConnection::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE t (s TEXT NOT NULL)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        let values = [
+            ArcStr::from("test test 123 456"),
+            arcstr::literal!("abc"),
+            ArcStr::new(),
+            ArcStr::from("🙀"),
+        ];
+        for v in &values {
+            sqlx::query("INSERT INTO t (s) VALUES (?)")
+                .bind(v.clone())
+                .execute(&mut conn)
+                .await
+                .unwrap();
+        }
+        let rows = sqlx::query("SELECT s FROM t ORDER BY rowid")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        let got: Vec<ArcStr> = rows.iter().map(|r| r.get::<ArcStr, _>(0)).collect();
+        assert_eq!(got, values);
+        let (s,): (ArcStr,) = sqlx::query_as("SELECT 'from a tuple'")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(s, "from a tuple");
+        // Decoding checks compatibility with the column type.
+        assert!(sqlx::query_scalar::<_, ArcStr>("SELECT 123")
+            .fetch_one(&mut conn)
+            .await
+            .is_err());
+    });
+}
+
+// Actually producing `defmt` output needs a `#[global_logger]` and a target
+// set up for `defmt-test`, neither of which we have when testing on the host,
+// so this only checks that the impls exist.
+#[cfg(feature = "defmt")]
+#[test]
+fn test_defmt() {
+    fn assert_format<T: defmt::Format>(_: &T) {}
+    assert_format(&ArcStr::from("abc"));
+    assert_format(&arcstr::literal!("abc"));
+    #[cfg(feature = "substr")]
+    assert_format(&ArcStr::from("abc").substr(1..));
+}
+
+#[cfg(all(feature = "axum", not(msrv)))]
+#[test]
+fn test_axum() {
+    use axum::response::IntoResponse;
+    fn check(res: axum::response::Response, body: &str) {
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+        let bytes =
+            futures_executor::block_on(axum::body::to_bytes(res.into_body(), usize::MAX)).unwrap();
+        assert_eq!(bytes, body.as_bytes());
+    }
+    check(ArcStr::from("hello axum").into_response(), "hello axum");
+    check(arcstr::literal!("static").into_response(), "static");
+    check(ArcStr::new().into_response(), "");
+    #[cfg(feature = "substr")]
+    {
+        let parent = ArcStr::from("[hello axum]");
+        check(parent.substr(1..6).into_response(), "hello");
+        check(
+            arcstr::literal!("[static]").substr(1..7).into_response(),
+            "static",
+        );
+    }
+    // With `bytes`, the body holds onto the string rather than a copy.
+    #[cfg(feature = "bytes")]
+    {
+        let s = ArcStr::from("shared");
+        let res = s.clone().into_response();
+        assert_eq!(ArcStr::strong_count(&s), Some(2));
+        drop(res);
+        assert_eq!(ArcStr::strong_count(&s), Some(1));
+    }
+    // Same headers as `String` produces.
+    let expected = String::from("x").into_response();
+    let ours = ArcStr::from("x").into_response();
+    assert_eq!(expected.headers(), ours.headers());
+}
+
+#[cfg(all(feature = "actix-web", not(msrv)))]
+#[test]
+fn test_actix_web() {
+    use actix_web::Responder;
+    let req = actix_web::test::TestRequest::default().to_http_request();
+    let check = |res: actix_web::HttpResponse, body: &str| {
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/plain; charset=utf-8"
+        );
+        let bytes = futures_executor::block_on(actix_web::body::to_bytes(res.into_body())).unwrap();
+        assert_eq!(bytes, body.as_bytes());
+    };
+    check(ArcStr::from("hello actix").respond_to(&req), "hello actix");
+    check(arcstr::literal!("static").respond_to(&req), "static");
+    check(ArcStr::new().respond_to(&req), "");
+    #[cfg(feature = "substr")]
+    {
+        let parent = ArcStr::from("[hello actix]");
+        check(parent.substr(1..6).respond_to(&req), "hello");
+        check(
+            arcstr::literal!("[static]").substr(1..7).respond_to(&req),
+            "static",
+        );
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_http() {
+    use http::HeaderValue;
+    use std::convert::TryFrom;
+    for v in ["text/plain; charset=utf-8", "12345", "", "gzip, deflate"] {
+        let hv = HeaderValue::from_str(v).unwrap();
+        let s = ArcStr::try_from(&hv).unwrap();
+        assert_eq!(s, v);
+        assert_eq!(ArcStr::try_from(hv.clone()).unwrap(), v);
+        let back = HeaderValue::try_from(s).unwrap();
+        assert_eq!(back, hv);
+        assert_eq!(back.to_str().unwrap(), v);
+    }
+    assert_eq!(
+        HeaderValue::try_from(arcstr::literal!("static")).unwrap(),
+        "static"
+    );
+
+    // Not visible ASCII: allowed in a `HeaderValue`, but not by `to_str`.
+    let hv = HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap();
+    assert!(ArcStr::try_from(&hv).is_err());
+    assert!(ArcStr::try_from(hv).is_err());
+    // Not allowed in a header value at all.
+    assert!(HeaderValue::try_from(ArcStr::from("line\nbreak")).is_err());
+    assert!(HeaderValue::try_from(ArcStr::from("nul\0")).is_err());
+
+    #[cfg(feature = "substr")]
+    {
+        let parent = ArcStr::from("Content-Length: 42\r\n");
+        let value = parent.substr(16..18);
+        let hv = HeaderValue::try_from(value.clone()).unwrap();
+        assert_eq!(hv, "42");
+        assert_eq!(arcstr::Substr::try_from(&hv).unwrap(), value);
+        assert_eq!(arcstr::Substr::try_from(hv).unwrap(), "42");
+        assert!(HeaderValue::try_from(parent.substr(16..)).is_err());
+        let bad = HeaderValue::from_bytes(b"\xff").unwrap();
+        assert!(arcstr::Substr::try_from(bad).is_err());
+    }
+}
+
+#[cfg(feature = "smol-str")]
+#[test]
+fn test_smol_str() {
+    use smol_str::SmolStr;
+    for v in [
+        "",
+        "short",
+        "a string which is too long to be stored inline",
+        "🙀é",
+    ] {
+        let a = ArcStr::from(SmolStr::new(v));
+        assert_eq!(a, v);
+        assert_eq!(ArcStr::from(&SmolStr::new(v)), v);
+        assert_eq!(SmolStr::from(a), v);
+        #[cfg(feature = "substr")]
+        {
+            let sub = arcstr::Substr::from(SmolStr::new(v));
+            assert_eq!(sub, v);
+            assert_eq!(SmolStr::from(sub), v);
+        }
+    }
+    let lit = arcstr::literal!("a static string which is too long to be stored inline");
+    assert_eq!(SmolStr::from(lit.clone()).as_ptr(), lit.as_ptr());
+}
+
+#[cfg(feature = "compact-str")]
+#[test]
+fn test_compact_str() {
+    use compact_str::CompactString;
+    for v in [
+        "",
+        "short",
+        "a string which is too long to be stored inline",
+        "🙀é",
+    ] {
+        let a = ArcStr::from(CompactString::new(v));
+        assert_eq!(a, v);
+        assert_eq!(ArcStr::from(&CompactString::new(v)), v);
+        assert_eq!(CompactString::from(a), v);
+        #[cfg(feature = "substr")]
+        {
+            let sub = arcstr::Substr::from(CompactString::new(v));
+            assert_eq!(sub, v);
+            assert_eq!(CompactString::from(sub), v);
+        }
+    }
+    let lit = arcstr::literal!("a static string which is too long to be stored inline");
+    assert_eq!(CompactString::from(lit.clone()).as_ptr(), lit.as_ptr());
+}
+
+#[cfg(feature = "kstring")]
+#[test]
+fn test_kstring() {
+    use kstring::KString;
+    for v in [
+        "",
+        "short",
+        "a string which is too long to be stored inline",
+        "🙀é",
+    ] {
+        let a = ArcStr::from(KString::from_ref(v));
+        assert_eq!(a, v);
+        assert_eq!(ArcStr::from(&KString::from_ref(v)), v);
+        assert_eq!(KString::from(a), v);
+        #[cfg(feature = "substr")]
+        {
+            let sub = arcstr::Substr::from(KString::from_ref(v));
+            assert_eq!(sub, v);
+            assert_eq!(KString::from(sub), v);
+        }
+    }
+    let lit = arcstr::literal!("a static string which is too long to be stored inline");
+    assert_eq!(KString::from(lit.clone()).as_ptr(), lit.as_ptr());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_bytes() {
+    use bytes::Bytes;
+    use std::convert::TryFrom;
+    // Static data is referenced directly.
+    let lit = arcstr::literal!("static data");
+    let b = Bytes::from(lit.clone());
+    assert_eq!(b, "static data".as_bytes());
+    assert_eq!(b.as_ptr(), lit.as_ptr());
+    assert_eq!(b.as_ptr(), ArcStr::as_static(&lit).unwrap().as_ptr());
+
+    // Dynamic data keeps the `ArcStr` alive until the last `Bytes` is gone.
+    let dynamic = ArcStr::from("dynamic data");
+    let b = Bytes::from(dynamic.clone());
+    assert_eq!(b, "dynamic data".as_bytes());
+    assert_eq!(b.as_ptr(), dynamic.as_ptr());
+    assert_eq!(ArcStr::strong_count(&dynamic), Some(2));
+    let b2 = b.slice(8..);
+    assert_eq!(b2, "data".as_bytes());
+    drop(b);
+    assert_eq!(ArcStr::strong_count(&dynamic), Some(2));
+    drop(b2);
+    assert_eq!(ArcStr::strong_count(&dynamic), Some(1));
+
+    assert_eq!(ArcStr::try_from(Bytes::from("abc 🙀")).unwrap(), "abc 🙀");
+    assert_eq!(ArcStr::try_from(Bytes::new()).unwrap(), "");
+    let err = ArcStr::try_from(Bytes::from_static(b"ab\xffcd")).unwrap_err();
+    assert_eq!(err.valid_up_to(), 2);
+}
+
+#[cfg(all(feature = "diesel", not(any(target_arch = "wasm32", loom, msrv))))]
+#[test]
+#[cfg_attr(any(miri, msan), ignore)] // Calls into SQLite
+fn test_diesel() {
+    use diesel::prelude::*;
+    diesel::table! {
+        names (id) {
+            id -> Integer,
+            name -> Text,
+            nickname -> Nullable<Text>,
+        }
+    }
+    #[derive(Queryable, Insertable, Debug, PartialEq)]
+    #[diesel(table_name = names)]
+    struct Name {
+        id: i32,
+        name: ArcStr,
+        nickname: Option<ArcStr>,
+    }
+
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query(
+        "CREATE TABLE names (id INTEGER PRIMARY KEY, name TEXT NOT NULL, nickname TEXT)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    let rows = vec![
+        Name {
+            id: 1,
+            name: ArcStr::from("test test 123 456"),
+            nickname: Some(arcstr::literal!("test")),
+        },
+        Name {
+            id: 2,
+            name: ArcStr::from("🙀"),
+            nickname: None,
+        },
+    ];
+    diesel::insert_into(names::table)
+        .values(&rows)
+        .execute(&mut conn)
+        .unwrap();
+    let back: Vec<Name> = names::table.order(names::id).load(&mut conn).unwrap();
+    assert_eq!(back, rows);
+
+    let needle = ArcStr::from("🙀");
+    let found: Vec<ArcStr> = names::table
+        .filter(names::name.eq(&needle))
+        .select(names::name)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(found, [needle]);
+}
+
+#[cfg(all(feature = "regex", feature = "substr"))]
+#[test]
+fn test_regex() {
+    let re = regex::Regex::new("([a-z]+)(?:=([0-9]*))?").unwrap();
+    let text = ArcStr::from("🙀 a=1, bc=, def");
+    let m = text.find_regex(&re).unwrap();
+    assert_eq!(m, "a=1");
+    assert_eq!(m.range(), 5..8);
+    assert!(ArcStr::ptr_eq(m.parent(), &text));
+
+    let all: Vec<_> = text.find_all_regex(&re).collect();
+    assert_eq!(all, ["a=1", "bc=", "def"]);
+    for m in &all {
+        assert!(ArcStr::ptr_eq(m.parent(), &text));
+        assert_eq!(&text[m.range()], m.as_str());
+    }
+
+    let caps = text.captures_as_substrs(&re).unwrap();
+    assert_eq!(
+        caps,
+        [Some("a=1".into()), Some("a".into()), Some("1".into())]
+    );
+    let caps = ArcStr::from("def").captures_as_substrs(&re).unwrap();
+    assert_eq!(caps, [Some("def".into()), Some("def".into()), None]);
+    let caps = ArcStr::from("bc=").captures_as_substrs(&re).unwrap();
+    assert_eq!(caps[2].as_ref().unwrap().range(), 3..3);
+    for c in caps.iter().flatten() {
+        assert_eq!(c.parent(), "bc=");
+    }
+
+    let empty = ArcStr::from("123");
+    assert_eq!(empty.find_regex(&re), None);
+    assert_eq!(empty.find_all_regex(&re).count(), 0);
+    assert_eq!(empty.captures_as_substrs(&re), None);
+    // Empty matches behave the same as with `Regex::find_iter`.
+    let re = regex::Regex::new("[0-9]*").unwrap();
+    let ranges: Vec<_> = ArcStr::from("a12b")
+        .find_all_regex(&re)
+        .map(|m| m.range())
+        .collect();
+    let expected: Vec<_> = re.find_iter("a12b").map(|m| m.range()).collect();
+    assert_eq!(ranges, expected);
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn test_redis() {
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+    let s = ArcStr::from("test test 123 456");
+    assert_eq!(s.to_redis_args(), vec![b"test test 123 456".to_vec()]);
+    assert_eq!(s.to_redis_args(), String::from(s.as_str()).to_redis_args());
+
+    let bulk = Value::BulkString(b"test test 123 456".to_vec());
+    assert_eq!(ArcStr::from_redis_value_ref(&bulk).unwrap(), s);
+    assert_eq!(ArcStr::from_redis_value(bulk).unwrap(), s);
+    let simple = Value::SimpleString("simple".into());
+    assert_eq!(ArcStr::from_redis_value(simple).unwrap(), "simple");
+    assert_eq!(ArcStr::from_redis_value(Value::Okay).unwrap(), "OK");
+    assert_eq!(ArcStr::from_redis_value(Value::Int(123)).unwrap(), "123");
+    assert!(ArcStr::from_redis_value(Value::Nil).is_err());
+    assert!(ArcStr::from_redis_value(Value::BulkString(vec![0xff])).is_err());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    // Cheap deterministic pseudo-random bytes.
+    // Mostly-valid UTF-8, so that we don't just generate empty strings.
+    let chars = ['a', 'b', ' ', '\n', 'é', '🙀', '\u{10ffff}'];
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut data = String::new();
+    for _ in 0..2048 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        data.push(chars[state as usize % chars.len()]);
+    }
+    let data = data.into_bytes();
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let s = ArcStr::arbitrary(&mut u).unwrap();
+        assert!(core::str::from_utf8(s.as_bytes()).is_ok());
+        assert_eq!(s.len(), s.as_str().len());
+        assert_eq!(s, ArcStr::from(s.as_str()));
+    }
+    let s = ArcStr::arbitrary_take_rest(Unstructured::new(b"abc")).unwrap();
+    assert_eq!(s, "abc");
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_schemars() {
+    let schema = schemars::schema_for!(ArcStr);
+    assert_eq!(schema.as_value()["type"], "string");
+    assert_eq!(schema, schemars::schema_for!(String));
+    assert_eq!(
+        <ArcStr as schemars::JsonSchema>::schema_name(),
+        <String as schemars::JsonSchema>::schema_name(),
+    );
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn test_speedy() {
+    use speedy::{BigEndian, LittleEndian, Readable, Writable};
+    for s in ["", "abc", "test test 123 456", "🙀 unicode 🙀"].map(ArcStr::from) {
+        let bytes = s.write_to_vec_with_ctx(LittleEndian::default()).unwrap();
+        let expected = String::from(s.as_str())
+            .write_to_vec_with_ctx(LittleEndian::default())
+            .unwrap();
+        assert_eq!(bytes, expected);
+        let back = ArcStr::read_from_buffer_with_ctx(LittleEndian::default(), &bytes).unwrap();
+        assert_eq!(back, s);
+        let back = ArcStr::read_from_buffer_copying_data_with_ctx(LittleEndian::default(), &bytes)
+            .unwrap();
+        assert_eq!(back, s);
+        let be = s.write_to_vec_with_ctx(BigEndian::default()).unwrap();
+        let back = ArcStr::read_from_buffer_with_ctx(BigEndian::default(), &be).unwrap();
+        assert_eq!(back, s);
+    }
+    let bad = [2u8, 0, 0, 0, 0xff, 0xfe];
+    assert!(ArcStr::read_from_buffer_with_ctx(LittleEndian::default(), &bad).is_err());
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn test_borsh() {
+    for s in ["", "abc", "test test 123 456", "🙀 unicode 🙀"] {
+        let arc = ArcStr::from(s);
+        let bytes = borsh::to_vec(&arc).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&String::from(s)).unwrap());
+        let back: ArcStr = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(back, s);
+    }
+    assert_eq!(
+        borsh::to_vec(&arcstr::literal!("lit")).unwrap(),
+        borsh::to_vec("lit").unwrap(),
+    );
+    let bad = borsh::to_vec(&[0xffu8, 0xfe][..]).unwrap();
+    assert!(borsh::from_slice::<ArcStr>(&bad).is_err());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_rkyv() {
+    use rkyv::Deserialize;
+    for s in [
+        ArcStr::from("test test 123 456"),
+        ArcStr::new(),
+        arcstr::literal!("abc"),
+    ] {
+        let bytes = rkyv::to_bytes::<_, 256>(&s).unwrap();
+        let archived = unsafe { rkyv::archived_root::<ArcStr>(&bytes) };
+        assert_eq!(archived.as_str(), s);
+        let back: ArcStr = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(back, s);
+    }
+}
+
 #[test]
 fn test_loose_ends() {
     assert_eq!(ArcStr::default(), "");
@@ -223,6 +912,30 @@ fn test_ptr_eq() {
     assert!(ArcStr::ptr_eq(&strange_new_foobar, &wild_blue_foobar));
 }
 
+#[test]
+fn test_contains_arcstr() {
+    let hay = ArcStr::from("the quick brown fox");
+    assert!(ArcStr::contains_arcstr(&hay, &hay));
+    assert!(ArcStr::contains_arcstr(&hay, &hay.clone()));
+    assert!(ArcStr::contains_arcstr(
+        &hay,
+        &ArcStr::from("the quick brown fox")
+    ));
+    assert!(ArcStr::contains_arcstr(&hay, &ArcStr::from("brown")));
+    assert!(ArcStr::contains_arcstr(&hay, &arcstr::literal!("fox")));
+    assert!(!ArcStr::contains_arcstr(
+        &hay,
+        &ArcStr::from("the quick brown fox!")
+    ));
+    assert!(!ArcStr::contains_arcstr(&ArcStr::from("brown"), &hay));
+    assert!(!ArcStr::contains_arcstr(&hay, &ArcStr::from("Brown")));
+    assert!(ArcStr::contains_arcstr(&hay, &ArcStr::new()));
+    assert!(ArcStr::contains_arcstr(&ArcStr::new(), &ArcStr::new()));
+    assert!(!ArcStr::contains_arcstr(&ArcStr::new(), &hay));
+    let lit = arcstr::literal!("static");
+    assert!(ArcStr::contains_arcstr(&lit, &lit.clone()));
+}
+
 #[test]
 fn test_statics() {
     const STATIC: ArcStr = arcstr::literal!("Electricity!");
@@ -368,3 +1081,877 @@ fn test_leaking() {
     assert!(ArcStr::is_static(&s));
     assert_eq!(ArcStr::as_static(&s), Some("foobar"));
 }
+
+#[test]
+fn test_char_count() {
+    assert_eq!(ArcStr::from("abc").char_count(), 3);
+    assert_eq!(ArcStr::from("ééé").char_count(), 3);
+    assert_eq!(ArcStr::from("文字").char_count(), 2);
+    assert_eq!(ArcStr::from("🙀").char_count(), 1);
+    assert_eq!(ArcStr::from("🏳️‍🌈").char_count(), 4);
+    assert_eq!(arcstr::literal!("a🙀é").char_count(), 3);
+    assert_eq!(ArcStr::new().char_count(), 0);
+}
+
+#[test]
+fn test_encode_utf16_utf32() {
+    let s = ArcStr::from("hello world");
+    assert_eq!(
+        s.encode_utf16_to_vec(),
+        "hello world".encode_utf16().collect::<Vec<_>>()
+    );
+    let s = ArcStr::from("a🙀é");
+    assert_eq!(s.encode_utf16_to_vec(), [0x61, 0xd83d, 0xde40, 0xe9]);
+    assert_eq!(s.encode_utf32_to_vec(), [0x61, 0x1f640, 0xe9]);
+    assert_eq!(String::from_utf16(&s.encode_utf16_to_vec()).unwrap(), s);
+
+    let mut buf = vec![1, 2];
+    assert_eq!(
+        s.encode_utf16_to_buf(&mut buf),
+        [0x61, 0xd83d, 0xde40, 0xe9]
+    );
+    assert_eq!(buf, [1, 2, 0x61, 0xd83d, 0xde40, 0xe9]);
+    assert!(ArcStr::new().encode_utf16_to_buf(&mut buf).is_empty());
+    assert_eq!(buf.len(), 6);
+
+    assert!(ArcStr::new().encode_utf16_to_vec().is_empty());
+    assert!(ArcStr::new().encode_utf32_to_vec().is_empty());
+}
+
+#[test]
+fn test_char_boundary_indices() {
+    for src in ["", "abc", "ééé", "文字", "🙀", "🏳️‍🌈", "a🙀é"] {
+        let s = ArcStr::from(src);
+        let bounds: Vec<usize> = s.char_boundary_indices().collect();
+        assert_eq!(bounds.len(), s.char_count() + 1);
+        assert_eq!(bounds.first(), Some(&0));
+        assert_eq!(bounds.last(), Some(&s.len()));
+        assert!(bounds.iter().all(|&i| s.is_char_boundary(i)));
+        assert!(bounds.windows(2).all(|w| w[0] < w[1]));
+        for w in bounds.windows(2) {
+            assert_eq!(s[w[0]..w[1]].chars().count(), 1);
+        }
+        let mut rev: Vec<usize> = s.char_boundary_indices().rev().collect();
+        rev.reverse();
+        assert_eq!(rev, bounds);
+        assert_eq!(s.char_boundary_indices().count(), bounds.len());
+    }
+    let s = ArcStr::from("aé🙀");
+    let mut it = s.char_boundary_indices();
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.next_back(), Some(7));
+    assert_eq!(it.next_back(), Some(3));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+}
+
+#[test]
+#[cfg(not(msrv))] // implicit captures need Rust 1.58
+fn test_format_implicit_capture() {
+    let s = "local";
+    let n = 3;
+    assert_eq!(arcstr::format!("{s}"), "local");
+    assert_eq!(arcstr::format!("{s}-{n}"), "local-3");
+    assert_eq!(arcstr::format!("{s:>7}|{}", n), "  local|3");
+    let width = 4;
+    assert_eq!(arcstr::format!("{n:0width$}"), "0003");
+}
+
+#[test]
+fn test_from_display_debug() {
+    assert_eq!(ArcStr::from_display(&42u8), "42");
+    assert_eq!(ArcStr::from_display(&-7i64), "-7");
+    assert_eq!(ArcStr::from_display(&u128::MAX), u128::MAX.to_string());
+    assert_eq!(ArcStr::from_display(&0.25f64), "0.25");
+    assert_eq!(ArcStr::from_display(&'ß'), "ß");
+    assert_eq!(ArcStr::from_display("unsized"), "unsized");
+
+    struct Point(i32, i32);
+    impl core::fmt::Display for Point {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "({}, {})", self.0, self.1)
+        }
+    }
+    assert_eq!(ArcStr::from_display(&Point(1, -2)), "(1, -2)");
+
+    struct Nothing;
+    impl core::fmt::Display for Nothing {
+        fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Ok(())
+        }
+    }
+    let empty = ArcStr::from_display(&Nothing);
+    assert!(empty.is_empty());
+    assert!(ArcStr::is_static(&empty));
+
+    assert_eq!(ArcStr::from_debug(&[1, 2]), "[1, 2]");
+    assert_eq!(ArcStr::from_debug("tab\t"), "\"tab\\t\"");
+    assert_eq!(ArcStr::from_debug(&None::<u8>), "None");
+    assert_eq!(ArcStr::from_debug(&ArcStr::from("x")), "\"x\"");
+}
+
+#[deny(
+    clippy::declare_interior_mutable_const,
+    clippy::borrow_interior_mutable_const
+)]
+mod interior_mutable_const_lints {
+    use arcstr::ArcStr;
+    pub const LINT_CONST: ArcStr = arcstr::literal!("hello");
+    #[test]
+    fn test_literal_lints() {
+        const LOCAL: ArcStr = arcstr::literal!("local");
+        assert_eq!(LINT_CONST, "hello");
+        assert_eq!(LOCAL.len(), 5);
+        assert!(ArcStr::is_static(&LOCAL));
+        assert_eq!(arcstr::literal!("expr"), "expr");
+        #[cfg(feature = "substr")]
+        {
+            const SUB: arcstr::Substr = arcstr::literal_substr!("sub");
+            assert_eq!(SUB, "sub");
+        }
+    }
+}
+
+#[test]
+fn test_concat() {
+    const TWO: ArcStr = arcstr::concat!("foo", "bar");
+    assert_eq!(TWO, "foobar");
+    assert!(ArcStr::is_static(&TWO));
+    const THREE: ArcStr = arcstr::concat!("foo", "bar", "baz");
+    assert_eq!(THREE, "foobarbaz");
+    assert_eq!(ArcStr::as_static(&THREE), Some("foobarbaz"));
+
+    let mixed = arcstr::concat!("x", 1, '-', 2.5, true, stringify!(ident));
+    assert_eq!(mixed, "x1-2.5trueident");
+    assert!(ArcStr::is_static(&mixed));
+    assert!(ArcStr::is_static(&mixed.clone()));
+
+    const WITH_FILE: ArcStr = arcstr::concat!("<", include_str!("../LICENSE-MIT"), ">");
+    assert!(WITH_FILE.starts_with("<Copyright (c) 2020 Thom Chiovoloni"));
+    assert!(WITH_FILE.ends_with(">"));
+    assert!(ArcStr::is_static(&WITH_FILE));
+
+    assert_eq!(arcstr::concat!(), "");
+    assert_eq!(arcstr::concat!("trailing", "comma",), "trailingcomma");
+}
+
+#[test]
+fn test_join() {
+    const ONE: ArcStr = arcstr::join!(", ", "only");
+    assert_eq!(ONE, "only");
+    assert!(ArcStr::is_static(&ONE));
+    const THREE: ArcStr = arcstr::join!(", ", "a", "b", "c");
+    assert_eq!(THREE, "a, b, c");
+    assert!(ArcStr::is_static(&THREE));
+
+    let ws = arcstr::join!(" ", "hello", "world");
+    assert_eq!(ws, "hello world");
+    assert!(ArcStr::is_static(&ws));
+    assert_eq!(arcstr::join!("\n", "line 1", "line 2",), "line 1\nline 2");
+    assert_eq!(arcstr::join!("", "x", "y", "z"), "xyz");
+    assert_eq!(arcstr::join!('-', 1, 2, 3), "1-2-3");
+}
+
+#[test]
+fn test_concat_with_separator() {
+    let owned = [String::from("a"), String::from("bc"), String::from("")];
+    assert_eq!(ArcStr::concat_with_separator("--", &owned), "a--bc--");
+    let arcs = [ArcStr::from("x"), arcstr::literal!("y")];
+    assert_eq!(ArcStr::concat_with_separator("", &arcs), "xy");
+    assert_eq!(ArcStr::concat_with_separator("→", &["ü", "ß"]), "ü→ß");
+
+    let one = ArcStr::concat_with_separator(", ", &["only"]);
+    assert_eq!(one, "only");
+    let none = ArcStr::concat_with_separator::<&str>(", ", &[]);
+    assert_eq!(none, "");
+    assert!(ArcStr::is_static(&none));
+    let blank = ArcStr::concat_with_separator("", &["", ""]);
+    assert!(ArcStr::is_static(&blank));
+    assert_eq!(ArcStr::concat_with_separator(",", &["", ""]), ",");
+
+    // An `AsRef` impl which lies about its contents mustn't cause UB.
+    struct Liar(std::cell::Cell<bool>);
+    impl AsRef<str> for Liar {
+        fn as_ref(&self) -> &str {
+            if self.0.replace(true) {
+                "much longer the second time"
+            } else {
+                "short"
+            }
+        }
+    }
+    let r = std::panic::catch_unwind(|| {
+        ArcStr::concat_with_separator(" ", &[Liar(Default::default())])
+    });
+    assert!(r.is_err());
+}
+
+#[test]
+fn test_join_display() {
+    assert_eq!(ArcStr::join_display(", ", [1, 2, 3]), "1, 2, 3");
+    assert_eq!(ArcStr::join_display("", ["a", "b"]), "ab");
+    assert_eq!(ArcStr::join_display(" | ", [1.5f32].iter()), "1.5");
+    let empty = ArcStr::join_display(", ", core::iter::empty::<u8>());
+    assert_eq!(empty, "");
+    assert!(ArcStr::is_static(&empty));
+    let words = vec![ArcStr::from("x"), ArcStr::from("y")];
+    assert_eq!(
+        ArcStr::join_display('/'.to_string().as_str(), &words),
+        "x/y"
+    );
+}
+
+#[test]
+fn test_from_chars_iter_with_hint() {
+    // Mixed widths, and long enough to cross a few allocation size classes.
+    let inputs = ["", "a", "héllo", "abc❤️d🦀", &"xyz🦀é".repeat(40)];
+    for text in inputs {
+        let exact = text.len();
+        for hint in [exact, 0, 1, exact / 2, exact + 1, exact * 3 + 7] {
+            let s = ArcStr::from_chars_iter_with_hint(text.chars(), hint);
+            assert_eq!(s, text, "hint: {}", hint);
+            assert_eq!(s.len(), text.len(), "hint: {}", hint);
+            assert_eq!(ArcStr::is_static(&s), text.is_empty());
+            // It's a normal `ArcStr` in every way.
+            let c = s.clone();
+            assert_eq!(ArcStr::strong_count(&s), (!text.is_empty()).then(|| 2));
+            drop(s);
+            assert_eq!(c, text);
+        }
+    }
+    let mapped = ArcStr::from_chars_iter_with_hint("a b c".chars().filter(|c| *c != ' '), 5);
+    assert_eq!(mapped, "abc");
+}
+
+#[test]
+// Miri (and the sanitizers, unless told otherwise) treat a failed huge
+// allocation as fatal, rather than returning null.
+#[cfg_attr(miri, ignore)]
+fn test_from_chars_iter_with_hint_huge() {
+    // Too large to allocate, or to even compute a layout for. These fall back
+    // to growing from nothing.
+    for hint in [usize::MAX / 4, isize::MAX as usize, usize::MAX] {
+        let s = ArcStr::from_chars_iter_with_hint("abc".chars(), hint);
+        assert_eq!(s, "abc");
+        assert_eq!(s.len(), 3);
+        let s = ArcStr::from_chars_iter_with_hint("".chars(), hint);
+        assert!(ArcStr::is_static(&s));
+    }
+}
+
+#[test]
+fn test_from_chars_iter_with_hint_panic() {
+    // The partially built string is freed (Miri and the sanitizers catch it if
+    // not) when the iterator panics.
+    let r = std::panic::catch_unwind(|| {
+        let it = "abcdef"
+            .chars()
+            .map(|c| if c == 'e' { panic!("oops") } else { c });
+        ArcStr::from_chars_iter_with_hint(it, 2)
+    });
+    assert!(r.is_err());
+}
+
+#[test]
+fn test_literal_generic_contexts() {
+    fn generic<T: Default>() -> (T, ArcStr) {
+        (T::default(), arcstr::literal!("generic"))
+    }
+    let (n, s) = generic::<u32>();
+    assert_eq!(n, 0);
+    assert_eq!(s, "generic");
+    assert!(ArcStr::is_static(&s));
+    assert_eq!(generic::<String>().1, "generic");
+
+    struct Holder<T>(T);
+    impl<T> Holder<T>
+    where
+        T: AsRef<str>,
+    {
+        const ASSOC: ArcStr = arcstr::literal!("associated");
+        fn describe(&self) -> (ArcStr, ArcStr) {
+            (arcstr::literal!("holder"), ArcStr::from(self.0.as_ref()))
+        }
+    }
+    let h = Holder("inner");
+    assert_eq!(h.describe(), ("holder".into(), "inner".into()));
+    assert_eq!(Holder::<&str>::ASSOC, "associated");
+    assert!(ArcStr::is_static(&Holder::<String>::ASSOC));
+
+    let captured = 5;
+    let closure = |n: usize| (arcstr::literal!("closure"), n + captured);
+    let (s, n) = closure(1);
+    assert_eq!(s, "closure");
+    assert_eq!(n, 6);
+    assert!(ArcStr::is_static(&s));
+}
+
+#[deny(dead_code, unused)]
+mod dead_code_lints {
+    use arcstr::ArcStr;
+    const _: ArcStr = arcstr::literal!("unused");
+    #[allow(dead_code)]
+    const ALLOWED_UNUSED: ArcStr = arcstr::literal!("allowed");
+    #[cfg(feature = "substr")]
+    const _: arcstr::Substr = arcstr::literal_substr!("unused substr");
+
+    #[test]
+    fn test_literal_dead_code() {
+        assert_eq!(arcstr::literal!("used"), "used");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lazy() {
+    use arcstr::LazyArcStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static LAZY: LazyArcStr = LazyArcStr::new(|| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        // Give the other threads a chance to pile up.
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        arcstr::format!("computed {}", 123)
+    });
+    assert_eq!(LazyArcStr::get(&LAZY), None);
+    assert_eq!(format!("{:?}", LAZY), "LazyArcStr(<uninit>)");
+
+    let handles = (0..8)
+        .map(|_| std::thread::spawn(|| LAZY.clone()))
+        .collect::<Vec<_>>();
+    let results = handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    for r in &results {
+        assert!(ArcStr::ptr_eq(r, &LAZY));
+    }
+    assert_eq!(LazyArcStr::get(&LAZY).unwrap(), "computed 123");
+    assert_eq!(format!("{:?}", LAZY), "LazyArcStr(\"computed 123\")");
+
+    // Non-static, with a capturing initializer, which gets dropped.
+    let prefix = ArcStr::from("pre");
+    let lazy = LazyArcStr::new(move || arcstr::format!("{}fix", prefix));
+    assert_eq!(*LazyArcStr::force(&lazy), "prefix");
+    let kept = lazy.clone();
+    drop(lazy);
+    assert_eq!(ArcStr::strong_count(&kept), Some(1));
+    drop(LazyArcStr::new(|| ArcStr::from("never computed")));
+}
+
+#[test]
+fn test_from_iter_char() {
+    let s: ArcStr = "abc".chars().collect();
+    assert_eq!(s, "abc");
+    let s: ArcStr = "héllo"
+        .chars()
+        .map(|c| c.to_uppercase().next().unwrap())
+        .collect();
+    assert_eq!(s, "HÉLLO");
+    let empty: ArcStr = "".chars().collect();
+    assert!(ArcStr::is_static(&empty));
+
+    // An iterator which doesn't know how long it is.
+    struct NoHint(u32);
+    impl Iterator for NoHint {
+        type Item = char;
+        fn next(&mut self) -> Option<char> {
+            self.0 += 1;
+            core::char::from_digit(self.0, 10).filter(|_| self.0 < 6)
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (0, None)
+        }
+    }
+    assert_eq!(NoHint(0).collect::<ArcStr>(), "12345");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_from_env() {
+    use std::env::VarError;
+    // Tests run in parallel, so these names are only used here.
+    std::env::set_var("ARCSTR_TEST_FROM_ENV", "value 🦀");
+    let v = ArcStr::from_env("ARCSTR_TEST_FROM_ENV").unwrap();
+    assert_eq!(v, "value 🦀");
+    assert!(!ArcStr::is_static(&v));
+    let fallback = arcstr::literal!("fallback");
+    assert_eq!(
+        ArcStr::from_env_or("ARCSTR_TEST_FROM_ENV", fallback.clone()),
+        "value 🦀"
+    );
+
+    std::env::remove_var("ARCSTR_TEST_FROM_ENV");
+    assert_eq!(
+        ArcStr::from_env("ARCSTR_TEST_FROM_ENV"),
+        Err(VarError::NotPresent)
+    );
+    let d = ArcStr::from_env_or("ARCSTR_TEST_FROM_ENV", fallback.clone());
+    assert!(ArcStr::ptr_eq(&d, &fallback));
+
+    std::env::set_var("ARCSTR_TEST_FROM_ENV_EMPTY", "");
+    assert_eq!(ArcStr::from_env("ARCSTR_TEST_FROM_ENV_EMPTY").unwrap(), "");
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        let bad = OsStr::from_bytes(b"not \xff utf8");
+        std::env::set_var("ARCSTR_TEST_FROM_ENV_BAD", bad);
+        assert_eq!(
+            ArcStr::from_env("ARCSTR_TEST_FROM_ENV_BAD"),
+            Err(VarError::NotUnicode(bad.to_owned()))
+        );
+        assert_eq!(
+            ArcStr::from_env_or("ARCSTR_TEST_FROM_ENV_BAD", fallback.clone()),
+            "fallback"
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_to() {
+    use std::io::{Cursor, Write};
+    let s = ArcStr::from("héllo wörld\n");
+    let mut v = Vec::new();
+    s.write_to(&mut v).unwrap();
+    s.write_to(&mut v).unwrap();
+    assert_eq!(v, [s.as_bytes(), s.as_bytes()].concat());
+
+    let mut c = Cursor::new(Vec::new());
+    c.write_all(b"> ").unwrap();
+    s.write_to(&mut c).unwrap();
+    assert_eq!(c.position() as usize, 2 + s.len());
+    assert_eq!(&c.get_ref()[2..], s.as_bytes());
+    let w: &mut dyn Write = &mut c;
+    arcstr::literal!("!").write_to(w).unwrap();
+    assert_eq!(c.into_inner().last(), Some(&b'!'));
+
+    // Errors from the writer are passed through.
+    let mut full = [0u8; 4];
+    let err = s.write_to(&mut &mut full[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+
+    #[cfg(feature = "substr")]
+    {
+        let mut v = Vec::new();
+        s.substr(7..).write_to(&mut v).unwrap();
+        assert_eq!(v, "wörld\n".as_bytes());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reader() {
+    use arcstr::ArcStrReader;
+    use std::io::{BufRead, Read};
+    let s = ArcStr::from("ab🦀cdé\nfg");
+    let mut r = ArcStrReader::new(s.clone());
+    // Chunks don't need to respect char boundaries.
+    let mut chunks = Vec::new();
+    let mut buf = [0u8; 4];
+    loop {
+        let n = r.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        chunks.push(buf[..n].to_vec());
+    }
+    assert_eq!(chunks.len(), (s.len() + 3) / 4);
+    assert!(chunks[..chunks.len() - 1].iter().all(|c| c.len() == 4));
+    assert_eq!(chunks.concat(), s.as_bytes());
+    assert_eq!(r.position(), s.len());
+    assert_eq!(r.read(&mut buf).unwrap(), 0);
+    assert!(r.fill_buf().unwrap().is_empty());
+    assert!(ArcStr::ptr_eq(r.get_ref(), &s));
+    assert!(ArcStr::ptr_eq(&r.into_inner(), &s));
+
+    let mut r = ArcStrReader::from(s.clone());
+    assert_eq!(r.fill_buf().unwrap(), s.as_bytes());
+    r.consume(2);
+    assert_eq!(r.fill_buf().unwrap(), &s.as_bytes()[2..]);
+    let lines: Vec<String> = r.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["🦀cdé", "fg"]);
+
+    let mut r = ArcStrReader::new(s.clone());
+    r.consume(usize::MAX);
+    assert_eq!(r.position(), s.len());
+    let mut out = Vec::new();
+    assert_eq!(ArcStrReader::default().read_to_end(&mut out).unwrap(), 0);
+
+    #[cfg(feature = "substr")]
+    {
+        let mut r = arcstr::SubstrReader::from(s.substr(2..));
+        let mut out = String::new();
+        r.read_to_string(&mut out).unwrap();
+        assert_eq!(out, &s[2..]);
+        assert_eq!(r.position(), s.len() - 2);
+        let mut r = arcstr::SubstrReader::new(s.substr(2..6));
+        assert_eq!(r.read(&mut [0; 8]).unwrap(), 4);
+        assert_eq!(r.read(&mut [0; 8]).unwrap(), 0);
+    }
+}
+
+#[test]
+fn test_write_to_fmt() {
+    let s = ArcStr::from("abc");
+    let mut out = String::new();
+    s.write_to_fmt(&mut out).unwrap();
+    ArcStr::new().write_to_fmt(&mut out).unwrap();
+    s.write_to_fmt(&mut out as &mut dyn core::fmt::Write)
+        .unwrap();
+    assert_eq!(out, "abcabc");
+    #[cfg(feature = "substr")]
+    {
+        s.substr(1..).write_to_fmt(&mut out).unwrap();
+        assert_eq!(out, "abcabcbc");
+    }
+}
+
+#[test]
+fn test_ascii_titlecase() {
+    assert_eq!(ArcStr::from("SHOUTING").to_ascii_titlecase(), "Shouting");
+    assert_eq!(ArcStr::from("quiet").to_ascii_titlecase(), "Quiet");
+    assert_eq!(
+        ArcStr::from("mIxEd CaSe").to_ascii_titlecase(),
+        "Mixed case"
+    );
+    assert_eq!(ArcStr::from("Already").to_ascii_titlecase(), "Already");
+    // It's the first letter that's capitalized, not the first character.
+    assert_eq!(
+        ArcStr::from("42nd STREET").to_ascii_titlecase(),
+        "42Nd street"
+    );
+    assert_eq!(
+        ArcStr::from("...and THEN").to_ascii_titlecase(),
+        "...And then"
+    );
+    assert_eq!(ArcStr::from("¡hOLA!").to_ascii_titlecase(), "¡Hola!");
+    assert_eq!(ArcStr::from("ÉCOLE").to_ascii_titlecase(), "ÉCole");
+    assert_eq!(ArcStr::from("123 !?").to_ascii_titlecase(), "123 !?");
+    let empty = ArcStr::new().to_ascii_titlecase();
+    assert_eq!(empty, "");
+    assert!(ArcStr::is_static(&empty));
+
+    #[cfg(feature = "substr")]
+    {
+        let s = ArcStr::from("xxHELLOxx");
+        assert_eq!(s.substr(2..7).to_ascii_titlecase(), "Hello");
+        assert_eq!(s.substr(2..2).to_ascii_titlecase(), "");
+    }
+}
+
+#[test]
+fn test_ascii_case() {
+    let s = ArcStr::from("Hello, World 123!");
+    assert_eq!(s.to_ascii_uppercase(), "HELLO, WORLD 123!");
+    assert_eq!(s.to_ascii_lowercase(), "hello, world 123!");
+    // Non-ASCII chars are unchanged, even ones which have case.
+    let s = ArcStr::from("Ärger über ǅ, ΣΊΣΥΦΟΣ, ß 🦀");
+    assert_eq!(s.to_ascii_uppercase(), "ÄRGER üBER ǅ, ΣΊΣΥΦΟΣ, ß 🦀");
+    assert_eq!(s.to_ascii_lowercase(), "Ärger über ǅ, ΣΊΣΥΦΟΣ, ß 🦀");
+    assert_eq!(s.to_ascii_lowercase(), s.as_str().to_ascii_lowercase());
+    // Already in the target case.
+    let s = ArcStr::from("LOUD");
+    let up = s.to_ascii_uppercase();
+    assert_eq!(up, s);
+    assert!(!ArcStr::ptr_eq(&up, &s));
+    assert_eq!(ArcStr::from("quiet").to_ascii_lowercase(), "quiet");
+    // Static strings produce a new string too.
+    let lit = arcstr::literal!("Static");
+    assert!(!ArcStr::is_static(&lit.to_ascii_lowercase()));
+    assert_eq!(lit.to_ascii_lowercase(), "static");
+
+    let empty = ArcStr::new();
+    assert_eq!(empty.to_ascii_uppercase(), "");
+    assert_eq!(ArcStr::from("").to_ascii_lowercase(), "");
+
+    #[cfg(feature = "substr")]
+    {
+        let s = ArcStr::from("(MiXeD ÇaSe)");
+        assert_eq!(s.substr(1..12).to_ascii_uppercase(), "MIXED ÇASE");
+        assert_eq!(s.substr(1..12).to_ascii_lowercase(), "mixed Çase");
+        assert_eq!(s.substr(1..1).to_ascii_lowercase(), "");
+    }
+}
+
+#[test]
+fn test_add() {
+    let foo = ArcStr::from("foo");
+    let bar = ArcStr::from("bar");
+    let s = foo.clone() + "bar";
+    assert_eq!(s, "foobar");
+    assert_eq!(&foo + "x" + "y" + bar.as_str(), "fooxybar");
+    assert_eq!(&foo + bar.clone(), "foobar");
+    assert_eq!(foo.clone() + bar.clone() + foo.clone(), "foobarfoo");
+    assert_eq!(ArcStr::new() + "ünicode" + "→", "ünicode→");
+
+    // An empty side doesn't need a new allocation.
+    assert!(ArcStr::ptr_eq(&(foo.clone() + ""), &foo));
+    assert!(ArcStr::ptr_eq(&(ArcStr::new() + bar.clone()), &bar));
+    assert!(ArcStr::is_static(&(ArcStr::new() + "")));
+}
+
+#[test]
+fn test_try_from_bytes() {
+    use core::convert::TryFrom;
+    assert_eq!(ArcStr::try_from(&b"ascii"[..]).unwrap(), "ascii");
+    assert_eq!(ArcStr::try_from("ü→😀".as_bytes()).unwrap(), "ü→😀");
+    assert_eq!(
+        ArcStr::try_from("ü→😀".as_bytes().to_vec()).unwrap(),
+        "ü→😀"
+    );
+    assert!(ArcStr::is_static(&ArcStr::try_from(&b""[..]).unwrap()));
+    assert!(ArcStr::is_static(&ArcStr::try_from(vec![]).unwrap()));
+
+    let err = ArcStr::try_from(&b"ab\xffcd"[..]).unwrap_err();
+    assert_eq!(err.valid_up_to(), 2);
+    let err = ArcStr::try_from(b"abc\xe2\x86".to_vec()).unwrap_err();
+    assert_eq!(err.valid_up_to(), 3);
+    assert_eq!(err.error_len(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[cfg_attr(miri, ignore)] // Uses the file system
+fn test_path() {
+    use std::ffi::{OsStr, OsString};
+    use std::path::{Path, PathBuf};
+    let path = ArcStr::from(file!());
+    let contents = std::fs::read(&path).unwrap();
+    assert!(contents.starts_with(b"#![allow("));
+    assert!(std::fs::metadata(path.clone()).unwrap().is_file());
+
+    let p: &Path = path.as_ref();
+    assert_eq!(p, Path::new(file!()));
+    let o: &OsStr = path.as_ref();
+    assert_eq!(o, OsStr::new(file!()));
+    assert_eq!(PathBuf::from(path.clone()), PathBuf::from(file!()));
+    assert_eq!(OsString::from(path), OsString::from(file!()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_try_from_ffi() {
+    use core::convert::TryFrom;
+    use std::ffi::{CStr, CString, OsStr};
+    assert_eq!(ArcStr::try_from(OsStr::new("ü→")).unwrap(), "ü→");
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let err = ArcStr::try_from(OsStr::from_bytes(b"a\xffb")).unwrap_err();
+        assert_eq!(err.to_string(), "string is not valid unicode");
+    }
+
+    let c = CStr::from_bytes_with_nul("ü→\0".as_bytes()).unwrap();
+    assert_eq!(ArcStr::try_from(c).unwrap(), "ü→");
+    let bad = CStr::from_bytes_with_nul(b"ab\xffc\0").unwrap();
+    assert_eq!(ArcStr::try_from(bad).unwrap_err().valid_up_to(), 2);
+
+    let c = CString::try_from(ArcStr::from("abc")).unwrap();
+    assert_eq!(c.as_bytes_with_nul(), b"abc\0");
+    let err = CString::try_from(ArcStr::from("ab\0c")).unwrap_err();
+    assert_eq!(err.nul_position(), 2);
+}
+
+#[cfg(feature = "substr")]
+#[test]
+fn test_substr_from_out_of_range_message() {
+    let a = ArcStr::from("  abcdefg  ");
+    let other = "abcdefg";
+    let r = std::panic::catch_unwind(|| a.substr_from(other));
+    let msg = *r.unwrap_err().downcast::<String>().unwrap();
+    let expected = format!(
+        "ArcStr over ({:p}..{:p}) does not contain substr over ({:p}..{:p})",
+        a.as_ptr(),
+        a.as_ptr().wrapping_add(a.len()),
+        other.as_ptr(),
+        other.as_ptr().wrapping_add(other.len()),
+    );
+    assert_eq!(msg, expected);
+}
+
+#[test]
+fn test_from_utf8_owned() {
+    let mut exact = Vec::with_capacity(5);
+    exact.extend_from_slice("ü→".as_bytes());
+    assert_eq!(ArcStr::from_utf8_owned(exact).unwrap(), "ü→");
+    let mut spare = Vec::with_capacity(100);
+    spare.extend_from_slice(b"spare");
+    assert_eq!(ArcStr::from_utf8_owned(spare).unwrap(), "spare");
+    assert!(ArcStr::is_static(&ArcStr::from_utf8_owned(vec![]).unwrap()));
+
+    let err = ArcStr::from_utf8_owned(b"ok\xe2\x86".to_vec()).unwrap_err();
+    assert_eq!(err.utf8_error().valid_up_to(), 2);
+    assert_eq!(err.into_bytes(), b"ok\xe2\x86");
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn test_unicode_normalization() {
+    // Precomposed vs decomposed, with a second combining mark that has to be
+    // reordered.
+    let composed = ArcStr::from("Am\u{e9}lie \u{1e69}");
+    let decomposed = ArcStr::from("Ame\u{301}lie s\u{323}\u{307}");
+    let unordered = ArcStr::from("Ame\u{301}lie s\u{307}\u{323}");
+    assert_eq!(composed.to_nfd(), decomposed);
+    assert_eq!(unordered.to_nfd(), decomposed);
+    assert_eq!(decomposed.to_nfc(), "Am\u{e9}lie \u{1e69}");
+    assert_eq!(unordered.to_nfc(), decomposed.to_nfc());
+    assert_eq!(composed.to_nfc().to_nfd(), decomposed);
+
+    // Compatibility forms.
+    let compat = ArcStr::from("\u{2460} \u{ff21} x\u{b2}");
+    assert_eq!(compat.to_nfkc(), "1 A x2");
+    assert_eq!(compat.to_nfkd(), "1 A x2");
+    assert!(ArcStr::ptr_eq(&compat.to_nfc(), &compat));
+    assert!(ArcStr::ptr_eq(&compat.to_nfd(), &compat));
+
+    // Already normalized strings aren't copied, including static ones.
+    let ascii = arcstr::literal!("plain ascii");
+    for n in [
+        ascii.to_nfc(),
+        ascii.to_nfd(),
+        ascii.to_nfkc(),
+        ascii.to_nfkd(),
+    ] {
+        assert!(ArcStr::ptr_eq(&n, &ascii));
+    }
+    let nfc = ArcStr::from("Am\u{e9}lie");
+    assert!(ArcStr::ptr_eq(&nfc.to_nfc(), &nfc));
+    assert!(ArcStr::ptr_eq(&nfc.to_nfkc(), &nfc));
+    assert!(ArcStr::ptr_eq(&decomposed.to_nfd(), &decomposed));
+    assert!(ArcStr::ptr_eq(&decomposed.to_nfkd(), &decomposed));
+    assert_eq!(ArcStr::new().to_nfc(), "");
+
+    #[cfg(feature = "substr")]
+    {
+        let s = composed.substr(8..);
+        assert_eq!(s.to_nfd(), "s\u{323}\u{307}");
+        assert!(!ArcStr::ptr_eq(s.to_nfd().parent(), &composed));
+        let s = composed.substr(..2);
+        assert!(arcstr::Substr::shallow_eq(&s.to_nfd(), &s));
+        assert!(arcstr::Substr::shallow_eq(&s.to_nfkc(), &s));
+        assert_eq!(compat.substr(4..).to_nfkc(), "A x2");
+    }
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn test_encoding() {
+    use encoding_rs::{UTF_16LE, WINDOWS_1252};
+    // Every byte in 0xa0..=0xff is the Latin-1 char with that code point.
+    let latin1: Vec<u8> = (0xa0..=0xffu8).collect();
+    let (s, replaced) = ArcStr::decode(&latin1, WINDOWS_1252);
+    assert!(!replaced);
+    assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+    assert_eq!(s.chars().count(), latin1.len());
+    assert!(s.chars().zip(&latin1).all(|(c, &b)| c as u32 == b as u32));
+    assert_eq!(s.encode_to(WINDOWS_1252), latin1);
+    // Windows-1252 differs from Latin-1 in 0x80..0xa0.
+    let (s, _) = ArcStr::decode(b"\x93quoted\x94 \x85", WINDOWS_1252);
+    assert_eq!(s, "\u{201c}quoted\u{201d} \u{2026}");
+    assert_eq!(s.encode_to(WINDOWS_1252), b"\x93quoted\x94 \x85");
+
+    // BOM handling.
+    let (s, replaced) = ArcStr::decode(b"\xff\xfeh\0i\0", WINDOWS_1252);
+    assert_eq!((s.as_str(), replaced), ("hi", false));
+    let (s, _) = ArcStr::decode_without_bom_handling(b"\xff\xfeh\0i\0", WINDOWS_1252);
+    assert_eq!(s, "ÿþh\0i\0");
+    let (s, _) = ArcStr::decode_without_bom_handling(b"h\0i\0", UTF_16LE);
+    assert_eq!(s, "hi");
+
+    // Malformed input.
+    let (s, replaced) = ArcStr::decode(b"h\0\0\xd8", UTF_16LE);
+    assert_eq!((s.as_str(), replaced), ("h\u{fffd}", true));
+    let (s, replaced) = ArcStr::decode(b"", UTF_16LE);
+    assert_eq!((s.as_str(), replaced), ("", false));
+
+    // Unmappable chars, and encodings which can't be encoded into.
+    assert_eq!(ArcStr::from("a→b").encode_to(WINDOWS_1252), b"a&#8594;b");
+    assert_eq!(ArcStr::from("é").encode_to(UTF_16LE), "é".as_bytes());
+}
+
+#[cfg(feature = "pool-alloc")]
+#[test]
+fn test_pool_alloc() {
+    // Sizes on either side of each block size, and of the largest one.
+    let sizes = [0, 1, 7, 8, 15, 16, 24, 39, 40, 41, 48, 63, 64, 65, 200];
+    for round in 0..3 {
+        let strs: Vec<ArcStr> = sizes
+            .iter()
+            .map(|&n| ArcStr::from("x".repeat(n) + &round.to_string()))
+            .collect();
+        for (s, &n) in strs.iter().zip(sizes.iter()) {
+            assert_eq!(s.len(), n + 1);
+            assert!(s[..n].bytes().all(|b| b == b'x'));
+            assert!(s.ends_with(&round.to_string()));
+        }
+        // Reused blocks must come back zeroed when asked for.
+        for &n in &sizes[1..] {
+            let z = ArcStr::init_with(n, |_| {}).unwrap();
+            assert!(z.bytes().all(|b| b == 0));
+        }
+        // Dropping via a weak reference takes the other dealloc path.
+        let weak = ArcStr::downgrade(&strs[3]);
+        drop(strs);
+        assert!(weak.upgrade().is_none());
+    }
+
+    // Churn from a few threads at once, so the free lists get contended.
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            std::thread::spawn(move || {
+                let mut keep = Vec::new();
+                for i in 0..2000 {
+                    let s = ArcStr::from(format!("{}:{}", t, i));
+                    if i % 3 == 0 {
+                        keep.push(s);
+                    } else {
+                        assert_eq!(s, format!("{}:{}", t, i));
+                    }
+                }
+                for (j, s) in keep.iter().enumerate() {
+                    assert_eq!(*s, format!("{}:{}", t, j * 3));
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+}
+
+#[test]
+fn test_ptr_range() {
+    let s = ArcStr::from("héllo world");
+    let r = s.ptr_range();
+    assert_eq!(r.start, s.as_ptr());
+    assert_eq!(r.end as usize - r.start as usize, s.len());
+    for (i, _) in s.char_indices() {
+        let p = s[i..].as_ptr();
+        assert!(s.contains_ptr(p));
+        assert_eq!(s.ptr_offset(p), Some(i));
+    }
+    // Not on a char boundary, but still ours.
+    assert_eq!(s.ptr_offset(s.as_ptr().wrapping_add(2)), Some(2));
+    // One past the end, and before the start.
+    assert!(!s.contains_ptr(r.end));
+    assert_eq!(s.ptr_offset(r.end), None);
+    assert_eq!(s.ptr_offset(r.start.wrapping_sub(1)), None);
+    // Same contents, somewhere else.
+    let other = s.to_string();
+    assert!(!s.contains_ptr(other.as_ptr()));
+    assert_eq!(s.ptr_offset(other.as_ptr()), None);
+    assert!(!s.contains_ptr(core::ptr::null()));
+
+    let lit = arcstr::literal!("static");
+    assert_eq!(lit.ptr_offset(lit[3..].as_ptr()), Some(3));
+    let empty = ArcStr::new();
+    assert!(empty.ptr_range().is_empty());
+    assert!(!empty.contains_ptr(empty.as_ptr()));
+    assert_eq!(empty.ptr_offset(empty.as_ptr()), None);
+}
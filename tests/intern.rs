@@ -0,0 +1,103 @@
+#![cfg(feature = "intern")]
+#![allow(
+    // need to test cloning, these are deliberate.
+    clippy::redundant_clone,
+)]
+
+use arcstr::{ArcStr, InternedArcStr};
+
+// Note: the pool is global, and tests run in parallel, so each test uses
+// strings which no other test does.
+
+#[test]
+fn test_dedup() {
+    let a = InternedArcStr::new("dedup-a");
+    let b = InternedArcStr::new(&String::from("dedup-a"));
+    let c = InternedArcStr::from(&ArcStr::from("dedup-a"));
+    assert!(InternedArcStr::ptr_eq(&a, &b));
+    assert!(InternedArcStr::ptr_eq(&a, &c));
+    assert_eq!(a, b);
+    assert_eq!(ArcStr::strong_count(a.as_arcstr()), Some(3));
+
+    let d = InternedArcStr::new("dedup-b");
+    assert_ne!(a, d);
+    assert!(a < d);
+    assert_eq!(a, "dedup-a");
+    assert_eq!(&*d, "dedup-b");
+    assert_eq!(format!("{} {:?}", a, d), "dedup-a \"dedup-b\"");
+}
+
+#[test]
+fn test_cleanup() {
+    let a = InternedArcStr::new("cleanup");
+    let weak = ArcStr::downgrade(a.as_arcstr());
+    let b = a.clone();
+    drop(a);
+    assert_eq!(InternedArcStr::get("cleanup"), Some(b.clone()));
+    drop(b);
+    assert_eq!(weak.upgrade(), None);
+    assert_eq!(InternedArcStr::get("cleanup"), None);
+
+    let again = InternedArcStr::new("cleanup");
+    assert_eq!(again, "cleanup");
+    assert_eq!(ArcStr::strong_count(again.as_arcstr()), Some(1));
+}
+
+#[test]
+fn test_escaped_arcstr() {
+    let a = InternedArcStr::new("escaped");
+    let s = ArcStr::from(&a);
+    drop(a);
+    // The `ArcStr` keeps the string alive, so it's still interned.
+    let b = InternedArcStr::new("escaped");
+    assert!(ArcStr::ptr_eq(&s, b.as_arcstr()));
+    drop(b);
+    drop(s);
+    assert_eq!(InternedArcStr::get("escaped"), None);
+    let c = InternedArcStr::new("escaped");
+    assert_eq!(ArcStr::strong_count(c.as_arcstr()), Some(1));
+}
+
+#[test]
+fn test_empty() {
+    let a = InternedArcStr::new("");
+    assert!(a.is_empty());
+    assert_eq!(a, InternedArcStr::new(""));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // Too slow
+fn test_threads() {
+    const THREADS: usize = 8;
+    const STRINGS: usize = 100;
+    let handles = (0..THREADS)
+        .map(|t| {
+            std::thread::spawn(move || {
+                let mut kept = vec![];
+                for round in 0..20 {
+                    for i in 0..STRINGS {
+                        let s = InternedArcStr::new(&format!("threads-{}", i));
+                        // Drop some of them right away, to race with the
+                        // others being created.
+                        if (i + t + round) % 3 != 0 {
+                            kept.push(s);
+                        }
+                    }
+                }
+                kept
+            })
+        })
+        .collect::<Vec<_>>();
+    let all = handles
+        .into_iter()
+        .flat_map(|h| h.join().unwrap())
+        .collect::<Vec<_>>();
+    for s in &all {
+        let expected = InternedArcStr::new(s);
+        assert!(InternedArcStr::ptr_eq(s, &expected), "{:?}", s);
+    }
+    drop(all);
+    for i in 0..STRINGS {
+        assert_eq!(InternedArcStr::get(&format!("threads-{}", i)), None);
+    }
+}
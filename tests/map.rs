@@ -0,0 +1,89 @@
+#![cfg(feature = "map")]
+
+use arcstr::{ArcStr, LazyArcStrMap};
+use std::collections::HashMap;
+
+// Checks `m` against a `HashMap` with the same contents.
+fn check_same(m: &LazyArcStrMap<usize>, expected: &HashMap<String, usize>, keys: &[ArcStr]) {
+    assert_eq!(m.len(), expected.len());
+    assert_eq!(m.is_empty(), expected.is_empty());
+    for k in keys {
+        let want = expected.get(k.as_str());
+        assert_eq!(m.get(k), want, "{:?}", k);
+        // A copy, so it can only be found by contents.
+        assert_eq!(m.get(&ArcStr::from(k.as_str())), want, "{:?}", k);
+        assert_eq!(m.get_str(k), want, "{:?}", k);
+        assert_eq!(m.contains_key(k), want.is_some());
+    }
+    let mut seen: Vec<(String, usize)> = m.iter().map(|(k, v)| (k.to_string(), *v)).collect();
+    let mut want: Vec<(String, usize)> = expected.iter().map(|(k, v)| (k.clone(), *v)).collect();
+    seen.sort();
+    want.sort();
+    assert_eq!(seen, want);
+}
+
+#[test]
+fn test_matches_hashmap() {
+    // Enough keys to cross the small-map threshold in both directions.
+    let keys: Vec<ArcStr> = (0..40).map(|i| ArcStr::from(format!("key{}", i))).collect();
+    let mut m = LazyArcStrMap::new();
+    let mut expected = HashMap::new();
+    for (i, k) in keys.iter().enumerate() {
+        assert_eq!(m.insert(k.clone(), i), None);
+        expected.insert(k.to_string(), i);
+        check_same(&m, &expected, &keys);
+    }
+    // Replacing a value, via a different pointer.
+    assert_eq!(m.insert(ArcStr::from("key3"), 300), Some(3));
+    expected.insert("key3".into(), 300);
+    *m.get_mut(&keys[4]).unwrap() = 400;
+    expected.insert("key4".into(), 400);
+    check_same(&m, &expected, &keys);
+
+    // Remove from the middle, the end, and by a copied key, back down past
+    // the threshold.
+    for (n, i) in [
+        0, 39, 20, 5, 17, 1, 2, 38, 30, 31, 32, 33, 34, 35, 36, 37, 6, 7, 8, 9, 10, 11, 12, 13, 14,
+    ]
+    .iter()
+    .enumerate()
+    {
+        let k = if n % 2 == 0 {
+            keys[*i].clone()
+        } else {
+            ArcStr::from(keys[*i].as_str())
+        };
+        assert_eq!(m.remove(&k), expected.remove(k.as_str()));
+        assert_eq!(m.remove(&k), None);
+        check_same(&m, &expected, &keys);
+    }
+    // And back up again.
+    for (i, k) in keys.iter().enumerate() {
+        m.insert(k.clone(), i + 1000);
+        expected.insert(k.to_string(), i + 1000);
+    }
+    check_same(&m, &expected, &keys);
+    m.clear();
+    expected.clear();
+    check_same(&m, &expected, &keys);
+}
+
+#[test]
+fn test_static_keys() {
+    static A: ArcStr = arcstr::literal!("a");
+    static B: ArcStr = arcstr::literal!("b");
+    let mut m: LazyArcStrMap<&str> = [(A.clone(), "first"), (B.clone(), "second")]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(m.get(&A), Some(&"first"));
+    assert_eq!(m.get(&arcstr::literal!("b")), Some(&"second"));
+    m.extend([(ArcStr::from("c"), "third")]);
+    assert_eq!(m.len(), 3);
+    assert_eq!(
+        format!("{:?}", m),
+        r#"{"a": "first", "b": "second", "c": "third"}"#
+    );
+    assert!(LazyArcStrMap::<()>::default().is_empty());
+    assert!(LazyArcStrMap::<()>::with_capacity(10).is_empty());
+}
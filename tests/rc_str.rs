@@ -0,0 +1,106 @@
+#![cfg(feature = "rc-str")]
+#![allow(
+    // need to test cloning, these are deliberate.
+    clippy::redundant_clone,
+    // yep, we create owned instance just for comparison, to test comparison
+    // with owned instances.
+    clippy::cmp_owned,
+)]
+
+use arcstr::{ArcStr, RcStr};
+
+// Compiles only if `$t` does *not* implement `$tr`: otherwise, the call to
+// `check` is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($t:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn check() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$t as AmbiguousIfImpl<_>>::check();
+    }};
+}
+
+#[test]
+fn test_not_send_sync() {
+    assert_not_impl!(RcStr: Send);
+    assert_not_impl!(RcStr: Sync);
+}
+
+#[test]
+fn test_clone_drop() {
+    let a = RcStr::from("foobar");
+    assert_eq!(RcStr::strong_count(&a), Some(1));
+    let b = a.clone();
+    assert!(RcStr::ptr_eq(&a, &b));
+    assert_eq!(RcStr::strong_count(&a), Some(2));
+    let c = RcStr::from(&b);
+    assert_eq!(RcStr::strong_count(&a), Some(3));
+    drop(b);
+    drop(a);
+    assert_eq!(RcStr::strong_count(&c), Some(1));
+    assert_eq!(c, "foobar");
+
+    let v: Vec<RcStr> = (0..10).map(|_| c.clone()).collect();
+    assert_eq!(RcStr::strong_count(&c), Some(11));
+    drop(v);
+    assert_eq!(RcStr::strong_count(&c), Some(1));
+
+    let d = RcStr::from("foobar");
+    assert!(!RcStr::ptr_eq(&c, &d));
+    assert_eq!(c, d);
+}
+
+#[test]
+fn test_static() {
+    const LIT: RcStr = arcstr::rc_literal!("abc");
+    let a = LIT;
+    let b = a.clone();
+    assert!(RcStr::is_static(&b));
+    assert_eq!(RcStr::strong_count(&b), None);
+    assert_eq!(RcStr::as_static(&b), Some("abc"));
+    assert_eq!(RcStr::as_static(&RcStr::from("abc")), None);
+    assert!(RcStr::is_static(&RcStr::new()));
+    assert!(RcStr::is_static(&RcStr::from("")));
+    assert_eq!(RcStr::default(), "");
+    assert_eq!(arcstr::rc_literal!("🙀"), "🙀");
+}
+
+#[test]
+fn test_conversions() {
+    let a = ArcStr::from("shared");
+    let r = RcStr::from(a.clone());
+    assert_eq!(r, a);
+    assert_eq!(a, r);
+    assert_ne!(r.as_ptr(), a.as_ptr());
+    let back = ArcStr::from(r.clone());
+    assert_eq!(back, "shared");
+    assert_eq!(RcStr::from(arcstr::literal!("lit")), "lit");
+
+    assert_eq!(RcStr::from(String::from("s")), "s");
+    assert_eq!(RcStr::from(&String::from("s")), "s");
+    assert_eq!(RcStr::from(Box::<str>::from("b")), "b");
+    assert_eq!(RcStr::from(std::borrow::Cow::Borrowed("c")), "c");
+    assert_eq!("x".parse::<RcStr>().unwrap(), "x");
+    assert_eq!(RcStr::try_alloc("y").unwrap(), "y");
+}
+
+#[test]
+fn test_traits() {
+    let s = RcStr::from("héllo");
+    assert_eq!(s.len(), 6);
+    assert_eq!(s.char_count(), 5);
+    assert_eq!(&s[1..3], "é");
+    assert_eq!(s.as_bytes(), "héllo".as_bytes());
+    assert_eq!(format!("{} {:?}", s, s), "héllo \"héllo\"");
+    assert_eq!(s.to_string(), "héllo");
+    assert!(RcStr::from("a") < RcStr::from("b"));
+
+    let mut set = std::collections::HashSet::new();
+    set.insert(s.clone());
+    assert!(set.contains("héllo"));
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(s, 1);
+    assert_eq!(map.get("héllo"), Some(&1));
+}
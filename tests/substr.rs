@@ -6,6 +6,8 @@
     clippy::cmp_owned,
     // Deliberate
     clippy::redundant_slicing,
+    // for `cfg(msrv)`
+    unexpected_cfgs,
 )]
 #![cfg(feature = "substr")]
 use arcstr::{ArcStr, Substr};
@@ -130,6 +132,9 @@ fn test_various_partial_eq() {
     check_partial_eq!(@eq; substr(":o"), std::rc::Rc::<str>::from(":o"));
     check_partial_eq!(@eq; substr("!!!"), std::sync::Arc::<str>::from("!!!"));
     check_partial_eq!(@eq; substr("examples"), ArcStr::from("examples"));
+    check_partial_eq!(@eq; substr("boxed"), Box::<str>::from("boxed"));
+    check_partial_eq!(@eq; substr("rc"), std::rc::Rc::new(String::from("rc")));
+    check_partial_eq!(@eq; substr("arc"), std::sync::Arc::new(String::from("arc")));
 
     check_partial_eq!(@eq; substr(""), "");
     check_partial_eq!(@eq; substr(""), ArcStr::from("abc").substr(3..));
@@ -149,6 +154,17 @@ fn test_various_partial_eq() {
     check_partial_eq!(@ne; substr("put"), std::rc::Rc::<str>::from("⛳️"));
     check_partial_eq!(@ne; substr("pots"), std::sync::Arc::<str>::from("🍲"));
     check_partial_eq!(@ne; substr("lots"), ArcStr::from("auctions"));
+    check_partial_eq!(@ne; substr("box"), Box::<str>::from("fox"));
+    check_partial_eq!(@ne; substr("rc"), std::rc::Rc::new(String::from("RC")));
+    check_partial_eq!(@ne; substr("arc"), std::sync::Arc::new(String::from("ark")));
+
+    // Spelled out, since these are the ones people actually write.
+    let s = substr("literal");
+    assert!(s == "literal");
+    assert!("literal" == s);
+    assert!(*"literal" == s);
+    assert!(s == *"literal");
+    assert!("other" != s);
 }
 
 #[test]
@@ -191,6 +207,70 @@ fn test_ord() {
     assert_eq!(&arr, &["bar", "baz", "foo"]);
 }
 
+#[test]
+fn test_partial_ord_mixed() {
+    let a = ArcStr::from("banana");
+    let sub = ArcStr::from("xxapple").substr(2..);
+    assert!(sub < a);
+    assert!(a > sub);
+    assert_eq!(
+        a.partial_cmp(&a.substr(..)),
+        Some(core::cmp::Ordering::Equal)
+    );
+    assert_eq!(
+        a.substr(..).partial_cmp(&a),
+        Some(core::cmp::Ordering::Equal)
+    );
+
+    assert!(sub < *"apples");
+    assert!(*"b" > sub);
+    assert!(sub > "aardvark");
+    assert!("zebra" > sub);
+    assert!(sub <= String::from("apple"));
+    assert!(String::from("apple") >= sub);
+    assert!(a > *"apple");
+    assert!("apple" < a);
+    assert!(a < String::from("cherry"));
+    assert!(String::from("cherry") > a);
+
+    // Sort a mix of `ArcStr`s and `Substr`s by comparing across types.
+    enum Either {
+        A(ArcStr),
+        S(Substr),
+    }
+    impl Either {
+        fn cmp(&self, o: &Self) -> core::cmp::Ordering {
+            match (self, o) {
+                (Either::A(a), Either::A(b)) => a.partial_cmp(b),
+                (Either::A(a), Either::S(b)) => a.partial_cmp(b),
+                (Either::S(a), Either::A(b)) => a.partial_cmp(b),
+                (Either::S(a), Either::S(b)) => a.partial_cmp(b),
+            }
+            .unwrap()
+        }
+        fn as_str(&self) -> &str {
+            match self {
+                Either::A(a) => a,
+                Either::S(s) => s,
+            }
+        }
+    }
+    let mut v = [
+        Either::A(ArcStr::from("pear")),
+        Either::S(sub.clone()),
+        Either::A(a.clone()),
+        Either::S(ArcStr::from("fig.").substr(..3)),
+        Either::S(a.substr(..3)),
+    ];
+    v.sort_by(Either::cmp);
+    let sorted: Vec<&str> = v.iter().map(Either::as_str).collect();
+    assert_eq!(sorted, ["apple", "ban", "banana", "fig", "pear"]);
+
+    let mut subs = [a.substr(2..), sub.clone(), a.substr(..2)];
+    subs.sort_by(|x, y| x.partial_cmp(y.as_str()).unwrap());
+    assert_eq!(subs, ["apple", "ba", "nana"]);
+}
+
 #[test]
 fn test_btreemap() {
     let mut m = std::collections::BTreeMap::new();
@@ -220,6 +300,43 @@ fn test_hashmap() {
     }
 }
 
+#[test]
+fn test_hash_matches_arcstr() {
+    use std::borrow::Borrow;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+    fn hash_of<T: Hash + ?Sized>(v: &T) -> u64 {
+        let mut h = DefaultHasher::new();
+        v.hash(&mut h);
+        h.finish()
+    }
+    let parent = ArcStr::from("xxabcxx");
+    for (a, sub) in [
+        (ArcStr::from("abc"), parent.substr(2..5)),
+        (arcstr::literal!("abc"), Substr::from("abc")),
+        (ArcStr::new(), parent.substr(3..3)),
+        (parent.clone(), parent.substr(..)),
+    ] {
+        assert_eq!(a, sub);
+        assert_eq!(hash_of(&a), hash_of(&sub));
+        assert_eq!(hash_of(&a), hash_of(sub.as_str()));
+    }
+
+    // `ArcStr` keys, looked up with a `Substr` (through `Borrow<str>`).
+    let mut m: HashMap<ArcStr, i32> = HashMap::new();
+    m.insert(ArcStr::from("abc"), 1);
+    m.insert(arcstr::literal!("xx"), 2);
+    let sub = parent.substr(2..5);
+    let key: &str = sub.borrow();
+    assert_eq!(m.get(key), Some(&1));
+    assert_eq!(m.get::<str>(parent.substr(..2).borrow()), Some(&2));
+    assert_eq!(m.get::<str>(parent.substr(1..3).borrow()), None);
+    // And the other way around.
+    let m: HashMap<Substr, i32> = [(parent.substr(2..5), 3)].into_iter().collect();
+    assert_eq!(m.get(ArcStr::from("abc").as_str()), Some(&3));
+}
+
 #[cfg(feature = "serde")]
 #[test]
 fn test_serde() {
@@ -243,10 +360,338 @@ fn test_serde() {
     }
 }
 
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_postcard() {
+    let parent = ArcStr::from("  test test 123 456");
+    for s in [parent.substr(2..), parent.substr(..), parent.substr(5..5)] {
+        let bytes = postcard::to_allocvec(&s).unwrap();
+        assert_eq!(bytes, postcard::to_allocvec(s.as_str()).unwrap());
+        let back: Substr = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(back, s);
+        let mut buf = [0u8; 64];
+        let used = postcard::to_slice(&s, &mut buf).unwrap();
+        assert_eq!(used, &bytes[..]);
+    }
+    assert!(postcard::from_bytes::<Substr>(&[2, 0xff, 0xfe]).is_err());
+}
+
+#[cfg(all(feature = "winnow", not(msrv)))]
+#[test]
+fn test_winnow() {
+    use winnow::ascii::{line_ending, space0};
+    use winnow::combinator::{separated, separated_pair};
+    use winnow::stream::{LocatingSlice, Location};
+    use winnow::token::take_while;
+    use winnow::{ModalResult, Parser};
+
+    fn key(i: &mut Substr) -> ModalResult<Substr> {
+        take_while(1.., |c: char| c.is_alphanumeric() || c == '_').parse_next(i)
+    }
+    fn value(i: &mut Substr) -> ModalResult<Substr> {
+        take_while(0.., |c: char| c != '\n' && c != '\r').parse_next(i)
+    }
+    fn pair(i: &mut Substr) -> ModalResult<(Substr, Substr)> {
+        separated_pair(key, (space0, '=', space0), value).parse_next(i)
+    }
+    fn pairs(i: &mut Substr) -> ModalResult<Vec<(Substr, Substr)>> {
+        separated(0.., pair, line_ending).parse_next(i)
+    }
+
+    let input = ArcStr::from("name = alice\nemoji=🙀\r\nempty=");
+    let mut stream = Substr::full(input.clone());
+    let parsed = pairs.parse_next(&mut stream).unwrap();
+    assert!(stream.is_empty());
+    assert_eq!(stream.current_token_start(), input.len());
+    let expected = [("name", "alice"), ("emoji", "🙀"), ("empty", "")];
+    assert_eq!(parsed.len(), expected.len());
+    for ((k, v), (want_k, want_v)) in parsed.iter().zip(&expected) {
+        assert_eq!(k, want_k);
+        assert_eq!(v, want_v);
+        for tok in [k, v] {
+            assert!(ArcStr::ptr_eq(tok.parent(), &input));
+            assert_eq!(&input[tok.range()], tok.as_str());
+        }
+    }
+    assert_eq!(parsed[0].1.range(), 7..12);
+    assert_eq!(parsed[1].0.range(), 13..18);
+    assert_eq!(parsed[2].1.range(), 31..31);
+
+    // Parsing a `Substr` which doesn't start at the beginning of its parent.
+    let mut tail = input.substr(13..);
+    let (k, v) = pair.parse_next(&mut tail).unwrap();
+    assert_eq!((k.range(), v.range()), (13..18, 19..23));
+    assert_eq!(tail.current_token_start(), 23);
+    assert!(pair.parse_next(&mut Substr::from("=oops")).is_err());
+    let all = pairs.parse(input.substr(13..23)).unwrap();
+    assert_eq!(all[0].1, "🙀");
+
+    // Locations (and so spans) are relative to the parent, even when parsing
+    // started partway through it. `LocatingSlice` makes them relative to
+    // where parsing started instead, without changing the slices produced.
+    let (_, span) = key.with_span().parse_next(&mut input.substr(13..)).unwrap();
+    assert_eq!(span, 13..18);
+    let mut located = LocatingSlice::new(input.substr(13..));
+    let r: ModalResult<(Substr, core::ops::Range<usize>)> = take_while(1.., char::is_alphanumeric)
+        .with_span()
+        .parse_next(&mut located);
+    let (k, span) = r.unwrap();
+    assert_eq!((k.range(), span), (13..18, 0..5));
+    assert_eq!(located.current_token_start(), 5);
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn test_nom() {
+    use nom::bytes::complete::{escaped_transform, tag, take_while, take_while1};
+    use nom::character::complete::{char, digit1, line_ending, none_of, space0};
+    use nom::combinator::{all_consuming, map_res, opt, value};
+    use nom::multi::separated_list0;
+    use nom::sequence::{delimited, preceded};
+    use nom::{IResult, Parser};
+
+    fn field(i: Substr) -> IResult<Substr, Substr> {
+        take_while(|c: char| c != ',' && c != '\n' && c != '\r')(i)
+    }
+    fn record(i: Substr) -> IResult<Substr, Vec<Substr>> {
+        separated_list0(char(','), field).parse(i)
+    }
+    fn csv(i: Substr) -> IResult<Substr, Vec<Vec<Substr>>> {
+        all_consuming(separated_list0(line_ending, record)).parse(i)
+    }
+
+    let input = ArcStr::from("name,age,emoji\nalice,30,🙀\r\nbob,,∆");
+    let whole = Substr::full(input.clone());
+    let (rest, rows) = csv(whole).unwrap();
+    assert!(rest.is_empty());
+    let expected = [
+        ["name", "age", "emoji"],
+        ["alice", "30", "🙀"],
+        ["bob", "", "∆"],
+    ];
+    assert_eq!(rows.len(), expected.len());
+    for (row, want) in rows.iter().zip(&expected) {
+        assert_eq!(row, want);
+        for tok in row {
+            assert!(ArcStr::ptr_eq(tok.parent(), &input));
+            assert_eq!(&input[tok.range()], tok.as_str());
+        }
+    }
+    assert_eq!(rows[1][1].range(), 21..23);
+
+    // A few more combinators.
+    fn ident(i: Substr) -> IResult<Substr, Substr> {
+        preceded(
+            space0,
+            take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+        )
+        .parse(i)
+    }
+    fn number(i: Substr) -> IResult<Substr, u32> {
+        map_res(digit1, |d: Substr| d.parse::<u32>()).parse(i)
+    }
+    let (rest, id) = ident(Substr::from("  foo_bar1 = 3")).unwrap();
+    assert_eq!(id, "foo_bar1");
+    assert_eq!(id.range(), 2..10);
+    assert_eq!(rest, " = 3");
+    let eq: IResult<Substr, Substr> = delimited(space0, tag("="), space0).parse(rest);
+    let (rest, _) = eq.unwrap();
+    assert_eq!(number(rest).unwrap().1, 3);
+    assert!(ident(Substr::from("  !")).is_err());
+    assert!(opt(tag::<_, _, nom::error::Error<_>>("x"))
+        .parse(Substr::from("y"))
+        .unwrap()
+        .1
+        .is_none());
+    let unescaped: IResult<Substr, String> =
+        escaped_transform(none_of("\\"), '\\', value("\n", char('n')))(Substr::from("a\\nb"));
+    assert_eq!(unescaped.unwrap().1, "a\nb");
+}
+
+#[cfg(feature = "proptest")]
+mod proptest_strategies {
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn substr_strategy_valid(s in arcstr::proptest::substr_strategy()) {
+            let parent = s.parent();
+            let range = s.range();
+            prop_assert!(range.start <= range.end && range.end <= parent.len());
+            prop_assert!(parent.is_char_boundary(range.start));
+            prop_assert!(parent.is_char_boundary(range.end));
+            prop_assert_eq!(s.as_str(), &parent[range]);
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn test_quickcheck() {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+    fn valid(s: Substr) -> bool {
+        let (parent, range) = (s.parent(), s.range());
+        range.end <= parent.len()
+            && parent.is_char_boundary(range.start)
+            && parent.is_char_boundary(range.end)
+            && s.as_str() == &parent[range]
+    }
+    QuickCheck::new()
+        .tests(100)
+        .quickcheck(valid as fn(Substr) -> bool);
+
+    let fails = |s: &Substr| s.chars().count() >= 3;
+    let mut g = Gen::from_size_and_seed(100, 1);
+    let mut s = Substr::arbitrary(&mut g);
+    while !fails(&s) || s.is_full() {
+        s = Substr::arbitrary(&mut g);
+    }
+    while let Some(smaller) = s.shrink().find(fails) {
+        assert!(valid(smaller.clone()));
+        assert!(smaller.parent().len() <= s.parent().len());
+        s = smaller;
+    }
+    assert_eq!(s.chars().count(), 3);
+    assert!(s.is_full());
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn test_redis() {
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+    let s = ArcStr::from("  test test 123 456").substr(2..);
+    assert_eq!(s.to_redis_args(), vec![b"test test 123 456".to_vec()]);
+    assert_eq!(s.to_redis_args(), String::from(s.as_str()).to_redis_args());
+
+    let bulk = Value::BulkString(b"test test 123 456".to_vec());
+    assert_eq!(Substr::from_redis_value_ref(&bulk).unwrap(), s);
+    assert_eq!(Substr::from_redis_value(bulk).unwrap(), s);
+    let simple = Value::SimpleString("simple".into());
+    assert_eq!(Substr::from_redis_value(simple).unwrap(), "simple");
+    assert_eq!(Substr::from_redis_value(Value::Okay).unwrap(), "OK");
+    assert_eq!(Substr::from_redis_value(Value::Int(123)).unwrap(), "123");
+    assert!(Substr::from_redis_value(Value::Nil).is_err());
+    assert!(Substr::from_redis_value(Value::BulkString(vec![0xff])).is_err());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    // Mostly-valid UTF-8, so that we don't just generate empty strings.
+    let chars = ['a', 'b', ' ', '\n', 'é', '🙀', '\u{10ffff}'];
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut data = String::new();
+    for _ in 0..2048 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        data.push(chars[state as usize % chars.len()]);
+    }
+    let data = data.into_bytes();
+    let mut saw_partial = false;
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let s = Substr::arbitrary(&mut u).unwrap();
+        let parent = s.parent();
+        let range = s.range();
+        assert!(range.start <= range.end && range.end <= parent.len());
+        assert!(parent.is_char_boundary(range.start));
+        assert!(parent.is_char_boundary(range.end));
+        assert_eq!(s.as_str(), &parent[range.clone()]);
+        saw_partial |= range.len() != parent.len();
+    }
+    assert!(saw_partial);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_schemars() {
+    let schema = schemars::schema_for!(Substr);
+    assert_eq!(schema.as_value()["type"], "string");
+    assert_eq!(schema, schemars::schema_for!(String));
+    assert_eq!(
+        <Substr as schemars::JsonSchema>::schema_name(),
+        <String as schemars::JsonSchema>::schema_name(),
+    );
+}
+
+#[cfg(feature = "speedy")]
+#[test]
+fn test_speedy() {
+    use speedy::{BigEndian, LittleEndian, Readable, Writable};
+    let parent = ArcStr::from("  test test 123 456");
+    for s in [parent.substr(2..), parent.substr(..), parent.substr(5..5)] {
+        let bytes = s.write_to_vec_with_ctx(LittleEndian::default()).unwrap();
+        let expected = String::from(s.as_str())
+            .write_to_vec_with_ctx(LittleEndian::default())
+            .unwrap();
+        assert_eq!(bytes, expected);
+        let back = Substr::read_from_buffer_with_ctx(LittleEndian::default(), &bytes).unwrap();
+        assert_eq!(back, s);
+        assert_eq!(back.parent(), s.as_str());
+        let back = Substr::read_from_buffer_copying_data_with_ctx(LittleEndian::default(), &bytes)
+            .unwrap();
+        assert_eq!(back, s);
+        let be = s.write_to_vec_with_ctx(BigEndian::default()).unwrap();
+        let back = Substr::read_from_buffer_with_ctx(BigEndian::default(), &be).unwrap();
+        assert_eq!(back, s);
+    }
+    let bad = [2u8, 0, 0, 0, 0xff, 0xfe];
+    assert!(Substr::read_from_buffer_with_ctx(LittleEndian::default(), &bad).is_err());
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn test_borsh() {
+    let parent = ArcStr::from("  test test 123 456");
+    for s in [parent.substr(2..), parent.substr(..), parent.substr(5..5)] {
+        let bytes = borsh::to_vec(&s).unwrap();
+        assert_eq!(bytes, borsh::to_vec(&String::from(s.as_str())).unwrap());
+        let back: Substr = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(back, s);
+        assert_eq!(back.parent(), s.as_str());
+    }
+    let bad = borsh::to_vec(&[0xffu8, 0xfe][..]).unwrap();
+    assert!(borsh::from_slice::<Substr>(&bad).is_err());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn test_rkyv() {
+    use rkyv::Deserialize;
+    let parent = ArcStr::from("  test test 123 456");
+    for s in [parent.substr(2..), parent.substr(..), parent.substr(5..5)] {
+        let bytes = rkyv::to_bytes::<_, 256>(&s).unwrap();
+        let archived = unsafe { rkyv::archived_root::<Substr>(&bytes) };
+        assert_eq!(archived.0.as_str(), parent);
+        let back: Substr = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(back, s);
+        assert_eq!(back.range(), s.range());
+        assert_eq!(back.parent(), &parent);
+    }
+    // A `(String, u32, u32)` archives the same way as a `Substr`, so we can
+    // use one to build archives with bad ranges.
+    for (start, end) in [(1, 2), (2, 1), (0, 100)] {
+        let bytes = rkyv::to_bytes::<_, 256>(&(String::from("é!"), start, end)).unwrap();
+        let archived = unsafe { rkyv::archived_root::<Substr>(&bytes) };
+        let back: Substr = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(back, "é!");
+        assert_eq!(back.range(), 0..3);
+    }
+}
+
 #[test]
 fn test_loose_ends() {
     assert_eq!(Substr::default(), "");
     assert_eq!("abc".parse::<Substr>().unwrap(), "abc");
+    let parsed: Substr = "parsed".parse().unwrap();
+    assert_eq!(parsed.as_str(), "parsed");
+    assert_eq!(parsed.range(), 0..6);
+    assert_eq!(Substr::from("from").as_str(), "from");
+    // Only the macro can produce a static `Substr`.
+    assert!(!ArcStr::is_static(parsed.parent()));
+    assert!(ArcStr::is_static(arcstr::literal_substr!("lit").parent()));
     let abc_sub = Substr::from(" abc ").substr(1..4);
     let abc_str: &str = abc_sub.as_ref();
     let abc_bytes: &[u8] = abc_sub.as_ref();
@@ -429,3 +874,977 @@ fn test_try_substr_from_substr() {
     assert_eq!(ss.as_deref(), Some("abcdefg"));
     assert!(Substr::shallow_eq(&ss.unwrap(), &subs.substr(2..9)));
 }
+
+#[test]
+fn test_split_at() {
+    let parent = ArcStr::from("__foo🙀bar__");
+    let s = parent.substr(2..parent.len() - 2);
+    let (l, r) = s.split_at(3);
+    assert_eq!(l, "foo");
+    assert_eq!(r, "🙀bar");
+    assert!(ArcStr::ptr_eq(l.parent(), &parent));
+    assert!(ArcStr::ptr_eq(r.parent(), &parent));
+    assert_eq!(l.range(), 2..5);
+    assert_eq!(r.range(), 5..12);
+
+    let (l, r) = s.split_at(0);
+    assert_eq!(l, "");
+    assert!(Substr::shallow_eq(&r, &s));
+    let (l, r) = s.split_at(s.len());
+    assert!(Substr::shallow_eq(&l, &s));
+    assert_eq!(r, "");
+    assert_eq!(r.range(), 12..12);
+
+    assert!(s.try_split_at(4).is_none());
+    assert!(s.try_split_at(s.len() + 1).is_none());
+    assert!(s.try_split_at(7).is_some());
+
+    let (l, r) = Substr::new().split_at(0);
+    assert_eq!((l.as_str(), r.as_str()), ("", ""));
+}
+
+#[test]
+#[should_panic = "mid 3 is out of bounds of substr of length 2"]
+fn test_split_at_panic_oob() {
+    let s = ArcStr::from("abcdef").substr(1..3);
+    let _ = s.split_at(3);
+}
+
+#[test]
+#[should_panic = "is out of bounds of substr of length 2"]
+fn test_split_at_panic_overflow() {
+    // `start + mid` would wrap around to a valid index.
+    let s = ArcStr::from("abc").substr(1..3);
+    let _ = s.split_at(usize::MAX);
+}
+
+#[test]
+#[should_panic = "mid 1 is not a char boundary of substr of length 4"]
+fn test_split_at_panic_char_boundary() {
+    let s = ArcStr::from(" 🙀").substr(1..);
+    let _ = s.split_at(1);
+}
+
+#[test]
+fn test_union_intersection() {
+    let parent = ArcStr::from("foo bar baz");
+    let foo = parent.substr(0..3);
+    let space_bar = parent.substr(3..7);
+    let oo_b = parent.substr(1..5);
+    let baz = parent.substr(8..);
+
+    // adjacent
+    let u = Substr::union(&foo, &space_bar).unwrap();
+    assert!(Substr::shallow_eq(&u, &parent.substr(0..7)));
+    assert!(Substr::shallow_eq(
+        &u,
+        &Substr::union(&space_bar, &foo).unwrap()
+    ));
+    assert!(Substr::intersection(&foo, &space_bar).is_none());
+    assert!(Substr::intersection(&space_bar, &foo).is_none());
+
+    // overlapping
+    let u = Substr::union(&oo_b, &space_bar).unwrap();
+    assert!(Substr::shallow_eq(&u, &parent.substr(1..7)));
+    let i = Substr::intersection(&oo_b, &space_bar).unwrap();
+    assert!(Substr::shallow_eq(&i, &parent.substr(3..5)));
+    assert_eq!(i, " b");
+    let i = Substr::intersection(&space_bar, &oo_b).unwrap();
+    assert!(Substr::shallow_eq(&i, &parent.substr(3..5)));
+
+    // contained
+    let i = Substr::intersection(&parent.substr(..), &oo_b).unwrap();
+    assert!(Substr::shallow_eq(&i, &oo_b));
+    let u = Substr::union(&parent.substr(..), &oo_b).unwrap();
+    assert!(Substr::shallow_eq(&u, &parent.substr(..)));
+
+    // non-overlapping
+    let u = Substr::union(&foo, &baz).unwrap();
+    assert_eq!(u, "foo bar baz");
+    assert!(Substr::intersection(&foo, &baz).is_none());
+    assert!(Substr::intersection(&baz, &foo).is_none());
+
+    // different parents
+    let other = ArcStr::from("foo bar baz");
+    assert!(Substr::union(&foo, &other.substr(0..3)).is_none());
+    assert!(Substr::intersection(&foo, &other.substr(0..3)).is_none());
+    assert!(Substr::union(&foo, &Substr::new()).is_none());
+}
+
+#[test]
+fn test_adjacent_merge() {
+    let parent = ArcStr::from("foo bar baz");
+    let foo = parent.substr(0..3);
+    let space = parent.substr(3..4);
+    let bar = parent.substr(4..7);
+    let oo_b = parent.substr(1..5);
+
+    // forward
+    assert!(foo.is_adjacent_to(&space));
+    let merged = foo.clone().try_merge_adjacent(space.clone()).unwrap();
+    assert!(Substr::shallow_eq(&merged, &parent.substr(0..4)));
+    // reverse
+    assert!(bar.is_adjacent_to(&space));
+    let merged = bar.clone().try_merge_adjacent(space.clone()).unwrap();
+    assert!(Substr::shallow_eq(&merged, &parent.substr(3..7)));
+
+    // gap
+    assert!(!foo.is_adjacent_to(&bar));
+    assert!(!bar.is_adjacent_to(&foo));
+    let (a, b) = foo.clone().try_merge_adjacent(bar.clone()).unwrap_err();
+    assert!(Substr::shallow_eq(&a, &foo));
+    assert!(Substr::shallow_eq(&b, &bar));
+
+    // overlap
+    assert!(!oo_b.is_adjacent_to(&space));
+    assert!(!oo_b.is_adjacent_to(&foo));
+    assert!(oo_b.clone().try_merge_adjacent(space.clone()).is_err());
+
+    // different parents
+    let other = ArcStr::from("foo bar baz");
+    assert!(!foo.is_adjacent_to(&other.substr(3..4)));
+    let (a, b) = foo
+        .clone()
+        .try_merge_adjacent(other.substr(3..4))
+        .unwrap_err();
+    assert!(Substr::shallow_eq(&a, &foo));
+    assert!(ArcStr::ptr_eq(b.parent(), &other));
+}
+
+#[test]
+fn test_into_arcstr() {
+    let parent = ArcStr::from("abc def");
+    // full: no copy
+    let full = Substr::full(parent.clone());
+    assert!(ArcStr::ptr_eq(&full.to_arcstr(), &parent));
+    assert!(ArcStr::ptr_eq(&full.into_arcstr(), &parent));
+    assert!(ArcStr::ptr_eq(&parent.substr(..).into_arcstr(), &parent));
+
+    // partial: copied into a new allocation
+    let def = parent.substr(4..);
+    let a = def.to_arcstr();
+    assert_eq!(a, "def");
+    assert!(!ArcStr::ptr_eq(&a, &parent));
+    assert_eq!(ArcStr::strong_count(&a), Some(1));
+    let a = def.into_arcstr();
+    assert_eq!(a, "def");
+    assert_eq!(ArcStr::strong_count(&a), Some(1));
+    assert_eq!(ArcStr::strong_count(&parent), Some(1));
+
+    // empty
+    assert_eq!(Substr::new().into_arcstr(), "");
+    assert_eq!(parent.substr(3..3).to_arcstr(), "");
+    assert_eq!(parent.substr(3..3).into_arcstr(), "");
+
+    // Always copied, and never keeps the parent alive.
+    let def = parent.substr(4..);
+    let full = Substr::full(parent.clone());
+    let owned = full.to_owned_arcstr();
+    assert_eq!(owned, parent);
+    assert!(!ArcStr::ptr_eq(&owned, &parent));
+    let owned = def.to_owned_arcstr();
+    assert_eq!(owned, "def");
+    assert_eq!(ArcStr::strong_count(&owned), Some(1));
+    assert_eq!(Substr::new().to_owned_arcstr(), "");
+
+    // The whole parent, no copy.
+    let p = def.clone_parent();
+    assert_eq!(p, "abc def");
+    assert!(ArcStr::ptr_eq(&p, &parent));
+    assert_eq!(ArcStr::strong_count(&parent), Some(4));
+    drop((full, p, def));
+    assert_eq!(ArcStr::strong_count(&parent), Some(1));
+
+    // static parent
+    let lit = arcstr::literal!("static literal");
+    let full = lit.substr(..).into_arcstr();
+    assert!(ArcStr::is_static(&full));
+    assert_eq!(full, "static literal");
+    let part = lit.substr(7..).into_arcstr();
+    assert!(!ArcStr::is_static(&part));
+    assert_eq!(part, "literal");
+}
+
+#[test]
+fn test_is_full() {
+    let empty = Substr::new();
+    assert!(empty.is_full());
+    assert!(ArcStr::ptr_eq(
+        empty.as_arcstr_if_full().unwrap(),
+        empty.parent()
+    ));
+    assert!(Substr::default().is_full());
+    assert!(Substr::from(ArcStr::new()).is_full());
+    assert!(Substr::from("").is_full());
+
+    let parent = ArcStr::from("abc def");
+    let full = Substr::full(parent.clone());
+    assert!(full.is_full());
+    assert!(ArcStr::ptr_eq(full.as_arcstr_if_full().unwrap(), &parent));
+    assert!(parent.substr(..).is_full());
+    assert!(parent.substr(0..7).is_full());
+    assert!(full.substr(..).is_full());
+
+    for sub in [
+        parent.substr(1..),
+        parent.substr(..6),
+        parent.substr(1..6),
+        parent.substr(0..0),
+        parent.substr(7..),
+    ] {
+        assert!(!sub.is_full());
+        assert!(sub.as_arcstr_if_full().is_none());
+    }
+
+    let lit = arcstr::literal_substr!("literal");
+    assert!(lit.is_full());
+    assert_eq!(lit.as_arcstr_if_full().unwrap(), "literal");
+    assert!(!lit.substr(1..).is_full());
+}
+
+#[test]
+fn test_split_once_as_substrs() {
+    let parent = ArcStr::from("=a=b🙀c=");
+    let s = parent.substr(1..parent.len() - 1);
+    assert_eq!(s, "a=b🙀c");
+
+    // middle
+    let (l, r) = s.split_once_as_substrs("=").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("a", "b🙀c"));
+    assert!(Substr::shallow_eq(&l, &parent.substr(1..2)));
+    assert!(Substr::shallow_eq(&r, &parent.substr(3..9)));
+    let (l, r) = s.rsplit_once_as_substrs("=").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("a", "b🙀c"));
+
+    // multi-byte
+    let (l, r) = s.split_once_as_substrs("🙀").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("a=b", "c"));
+    assert!(ArcStr::ptr_eq(l.parent(), &parent));
+    assert!(ArcStr::ptr_eq(r.parent(), &parent));
+    let (l, r) = s.rsplit_once_as_substrs("🙀").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("a=b", "c"));
+
+    // start and end
+    let (l, r) = s.split_once_as_substrs("a").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("", "=b🙀c"));
+    assert_eq!(l.range(), 1..1);
+    let (l, r) = s.rsplit_once_as_substrs("c").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("a=b🙀", ""));
+    assert_eq!(r.range(), 9..9);
+
+    // first vs last
+    let s2 = Substr::from("1,2,3");
+    let (l, r) = s2.split_once_as_substrs(",").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("1", "2,3"));
+    let (l, r) = s2.rsplit_once_as_substrs(",").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("1,2", "3"));
+
+    // not found
+    assert!(s.split_once_as_substrs("x").is_none());
+    assert!(s.rsplit_once_as_substrs("x").is_none());
+
+    // separators which only occur in the parent outside our range, or which
+    // straddle our boundary, aren't found.
+    let s3 = parent.substr(2..8);
+    assert_eq!(s3, "=b🙀");
+    assert!(s3.split_once_as_substrs("a=").is_none());
+    assert!(s3.rsplit_once_as_substrs("🙀c").is_none());
+    let (l, r) = s3.rsplit_once_as_substrs("=").unwrap();
+    assert_eq!((l.as_str(), r.as_str()), ("", "b🙀"));
+}
+
+#[test]
+fn test_find_as_substr() {
+    let parent = ArcStr::from("ab🙀ab🙀ab");
+    let s = parent.substr(2..parent.len() - 2);
+    assert_eq!(s, "🙀ab🙀");
+
+    // start
+    let f = s.find_as_substr("🙀").unwrap();
+    assert!(Substr::shallow_eq(&f, &parent.substr(2..6)));
+    // end
+    let f = s.rfind_as_substr("🙀").unwrap();
+    assert!(Substr::shallow_eq(&f, &parent.substr(8..12)));
+    // middle
+    let f = s.find_as_substr("ab").unwrap();
+    assert!(Substr::shallow_eq(&f, &parent.substr(6..8)));
+    let f = s.rfind_as_substr("ab").unwrap();
+    assert!(Substr::shallow_eq(&f, &parent.substr(6..8)));
+    // not found
+    assert!(s.find_as_substr("x").is_none());
+    assert!(s.rfind_as_substr("x").is_none());
+    // only present in the parent outside of our range
+    assert!(s.find_as_substr("ab🙀ab").is_none());
+    assert!(s.rfind_as_substr("🙀ab🙀ab").is_none());
+    assert!(parent.substr(2..7).rfind_as_substr("ab").is_none());
+    // empty pattern
+    let f = s.find_as_substr("").unwrap();
+    assert_eq!(f, "");
+    assert_eq!(f.range(), 2..2);
+    let f = s.rfind_as_substr("").unwrap();
+    assert_eq!(f, "");
+    assert_eq!(f.range(), 12..12);
+}
+
+#[test]
+fn test_repeat() {
+    let s = arcstr::literal!("_ab🙀_").substr(1..7);
+    assert_eq!(s.repeat(0), "");
+    let one = s.repeat(1);
+    assert_eq!(one, "ab🙀");
+    assert!(!ArcStr::is_static(&one));
+    assert!(!ArcStr::ptr_eq(&one, s.parent()));
+    assert_eq!(s.repeat(5), "ab🙀".repeat(5));
+    assert_eq!(Substr::new().repeat(100), "");
+}
+
+#[test]
+#[should_panic = "capacity overflow"]
+fn test_repeat_overflow() {
+    let _ = Substr::from("AAA").repeat(usize::MAX / 2);
+}
+
+#[test]
+fn test_char_count() {
+    let parent = ArcStr::from("xxabcééé文字🙀🏳️‍🌈xx");
+    assert_eq!(parent.substr(2..5).char_count(), 3);
+    assert_eq!(parent.substr(5..11).char_count(), 3);
+    assert_eq!(parent.substr(11..17).char_count(), 2);
+    assert_eq!(parent.substr(17..21).char_count(), 1);
+    let s = parent.substr(2..parent.len() - 2);
+    assert_eq!(s.char_count(), s.chars().count());
+    assert_eq!(parent.substr(2..2).char_count(), 0);
+    assert_eq!(Substr::new().char_count(), 0);
+}
+
+#[test]
+fn test_encode_utf16_utf32() {
+    let parent = ArcStr::from("<hello world 🙀>");
+    let s = parent.substr(1..parent.len() - 1);
+    assert_eq!(
+        s.encode_utf16_to_vec(),
+        "hello world 🙀".encode_utf16().collect::<Vec<_>>()
+    );
+    assert_eq!(&s.encode_utf16_to_vec()[12..], [0xd83d, 0xde40]);
+    assert_eq!(
+        s.encode_utf32_to_vec(),
+        "hello world 🙀".chars().map(u32::from).collect::<Vec<_>>()
+    );
+    let mut buf = vec![0];
+    assert_eq!(
+        s.substr(12..).encode_utf16_to_buf(&mut buf),
+        [0xd83d, 0xde40]
+    );
+    assert_eq!(buf, [0, 0xd83d, 0xde40]);
+}
+
+#[test]
+fn test_char_boundary_indices() {
+    let parent = ArcStr::from("xxabcééé文字🙀🏳️‍🌈xx");
+    let s = parent.substr(2..parent.len() - 2);
+    let bounds: Vec<usize> = s.char_boundary_indices().collect();
+    assert_eq!(bounds.len(), s.char_count() + 1);
+    assert_eq!(
+        bounds,
+        s.as_str()
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([s.len()])
+            .collect::<Vec<_>>()
+    );
+    assert!(bounds.iter().all(|&i| s.is_char_boundary(i)));
+    for w in bounds.windows(2) {
+        let c = s.substr(w[0]..w[1]);
+        assert_eq!(c.char_count(), 1);
+        assert!(ArcStr::ptr_eq(c.parent(), &parent));
+        assert_eq!(c.range(), w[0] + 2..w[1] + 2);
+    }
+    let e = parent.substr(5..5);
+    assert_eq!(e.char_boundary_indices().collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn test_literal_substr_range() {
+    const HELLO: Substr = arcstr::literal_substr!("hello world", 0..5);
+    assert_eq!(HELLO, "hello");
+    assert_eq!(HELLO.range(), 0..5);
+    assert_eq!(HELLO.parent(), "hello world");
+    assert!(ArcStr::is_static(HELLO.parent()));
+
+    const WORLD: Substr = arcstr::literal_substr!("hello world", 6..11);
+    assert_eq!(WORLD, "world");
+    const START: usize = 2;
+    let cat = arcstr::literal_substr!("ab🙀cd", START..START + 4);
+    assert_eq!(cat, "🙀");
+    assert_eq!(arcstr::literal_substr!("abc", 3..3), "");
+    assert_eq!(arcstr::literal_substr!("abc", 0..3), "abc");
+    assert_eq!(arcstr::literal_substr!("", 0..0), "");
+    assert_eq!(arcstr::literal_substr!("abc", 1..2,), "b");
+}
+
+#[test]
+fn test_split_as_substrs() {
+    fn check(full: &str, range: std::ops::Range<usize>, sep: &str) {
+        let parent = ArcStr::from(full);
+        let s = parent.substr(range);
+        let expected: Vec<&str> = s.as_str().split(sep).collect();
+        let got: Vec<Substr> = s.split_as_substrs(sep).collect();
+        assert_eq!(got, expected, "{:?}.split({:?})", s, sep);
+        for part in &got {
+            assert!(ArcStr::ptr_eq(part.parent(), &parent));
+            assert!(part.range().start >= s.range().start);
+            assert!(part.range().end <= s.range().end);
+            assert_eq!(&parent[part.range()], part.as_str());
+        }
+        let mut it = s.split_as_substrs(sep);
+        for _ in &mut it {}
+        assert!(it.next().is_none());
+        assert!(it.next().is_none());
+    }
+    // single-char sep
+    check("a,b,c", 0..5, ",");
+    check("xa,b,cx", 1..6, ",");
+    // multi-char sep
+    check("a::b::c", 0..7, "::");
+    check("a🙀b🙀🙀c", 0..14, "🙀");
+    check("aaaa", 0..4, "aa");
+    check("aaa", 0..3, "aa");
+    // not found
+    check("abc", 0..3, ",");
+    check(",abc,", 1..4, ",");
+    // empty sep
+    check("abc", 0..3, "");
+    check("a🙀c", 0..6, "");
+    check("xa🙀cx", 1..7, "");
+    check("", 0..0, "");
+    check("abc", 1..1, "");
+    // leading/trailing
+    check(",a,b,", 0..5, ",");
+    check(",,", 0..2, ",");
+    check(",", 0..1, ",");
+    check("", 0..0, ",");
+
+    let parent = ArcStr::from("a,b");
+    let mut split = parent.substr(..).split_as_substrs(",");
+    let a = split.next().unwrap();
+    assert_eq!(a.range(), 0..1);
+    let b = split.next().unwrap();
+    assert_eq!(b.range(), 2..3);
+    assert_eq!(split.next(), None);
+}
+
+#[test]
+fn test_matches_as_substrs() {
+    fn check(full: &str, range: std::ops::Range<usize>, pat: &str) {
+        let parent = ArcStr::from(full);
+        let s = parent.substr(range);
+        let expected: Vec<(usize, &str)> = s.as_str().match_indices(pat).collect();
+        let got: Vec<Substr> = s.matches_as_substrs(pat).collect();
+        assert_eq!(got.len(), expected.len(), "{:?}.matches({:?})", s, pat);
+        for (part, (i, text)) in got.iter().zip(expected) {
+            assert!(ArcStr::ptr_eq(part.parent(), &parent));
+            assert_eq!(part, text);
+            assert_eq!(part.range().start, s.range().start + i);
+            assert!(part.range().end <= s.range().end);
+        }
+        let mut it = s.matches_as_substrs(pat);
+        for _ in &mut it {}
+        assert!(it.next().is_none());
+        assert!(it.next().is_none());
+    }
+    check("abcabc", 0..6, "bc");
+    check("aaaa", 0..4, "aa");
+    check("aaa", 0..3, "aa");
+    check("a🙀b🙀🙀c", 0..14, "🙀");
+    check("abc", 0..3, "x");
+    // Matches in the parent which cross our boundaries are ignored.
+    check("xyzxy", 1..4, "xy");
+    check("xyzxy", 1..5, "xy");
+    check("abab", 1..3, "ab");
+    check("abab", 1..3, "ba");
+    // Pattern equal to the whole substr, or longer than it.
+    check("[abc]", 1..4, "abc");
+    check("[abc]", 1..4, "[abc]");
+    check("", 0..0, "a");
+    // Empty pattern.
+    check("abc", 0..3, "");
+    check("xa🙀cx", 1..7, "");
+    check("abc", 1..1, "");
+    check("", 0..0, "");
+
+    let parent = ArcStr::from("--abc--");
+    let s = parent.substr(2..5);
+    let all: Vec<Substr> = s.matches_as_substrs("abc").collect();
+    assert_eq!(all, ["abc"]);
+    assert_eq!(all[0].range(), 2..5);
+    assert_eq!(s.matches_as_substrs("-").count(), 0);
+}
+
+#[test]
+fn test_lines_as_substrs() {
+    fn check(full: &str, range: std::ops::Range<usize>, expected: &[&str]) {
+        let parent = ArcStr::from(full);
+        let s = parent.substr(range);
+        let got: Vec<Substr> = s.lines_as_substrs().collect();
+        assert_eq!(got, expected, "{:?}.lines()", s);
+        for line in &got {
+            assert!(ArcStr::ptr_eq(line.parent(), &parent));
+            assert!(line.range().start >= s.range().start);
+            assert!(line.range().end <= s.range().end);
+        }
+        let mut it = s.lines_as_substrs();
+        for _ in &mut it {}
+        assert!(it.next().is_none());
+    }
+    // \n
+    check("a\nb\nc", 0..5, &["a", "b", "c"]);
+    check("a\nb\n", 0..4, &["a", "b"]);
+    check("a\n\nb", 0..4, &["a", "", "b"]);
+    check("\n", 0..1, &[""]);
+    check("\n\n", 0..2, &["", ""]);
+    // \r\n
+    check("a\r\nb\r\nc", 0..7, &["a", "b", "c"]);
+    check("a\r\nb\r\n", 0..6, &["a", "b"]);
+    check("\r\n", 0..2, &[""]);
+    // bare \r is not a line ending
+    check("a\rb\nc", 0..5, &["a\rb", "c"]);
+    check("a\r\rb", 0..4, &["a\r\rb"]);
+    // mixed
+    check("a\nb\r\nc\rd\n\r\ne", 0..12, &["a", "b", "c\rd", "", "e"]);
+    // empty
+    check("", 0..0, &[]);
+    check("abc", 1..1, &[]);
+    // no newline
+    check("abc", 0..3, &["abc"]);
+    check("🙀🙀", 0..8, &["🙀🙀"]);
+    // starting mid-line of the parent
+    check("abc\ndef\nghi", 2..9, &["c", "def", "g"]);
+    // a `\r\n` split by our end
+    check("abc\r\ndef", 0..4, &["abc\r"]);
+    check("abc\r\ndef", 4..8, &["", "def"]);
+
+    let parent = ArcStr::from("abc\r\ndef");
+    let lines: Vec<_> = parent.substr(..).lines_as_substrs().collect();
+    assert_eq!(lines[0].range(), 0..3);
+    assert_eq!(lines[1].range(), 5..8);
+}
+
+#[test]
+fn test_chars_as_substrs() {
+    let parent = ArcStr::from("_aé文🙀b🏳️‍🌈_");
+    let s = parent.substr(1..parent.len() - 1);
+    let expected: Vec<String> = s.chars().map(String::from).collect();
+    let got: Vec<Substr> = s.chars_as_substrs().collect();
+    assert_eq!(got, expected);
+    assert_eq!(got.len(), s.char_count());
+    assert_eq!(got[0], "a");
+    assert_eq!(got[1], "é");
+    assert_eq!(got[2], "文");
+    assert_eq!(got[3], "🙀");
+    assert_eq!(got[4], "b");
+    let mut pos = s.range().start;
+    for c in &got {
+        assert!(ArcStr::ptr_eq(c.parent(), &parent));
+        assert_eq!(c.range().start, pos);
+        assert_eq!(c.chars().count(), 1);
+        pos = c.range().end;
+    }
+    assert_eq!(pos, s.range().end);
+
+    let mut it = s.chars_as_substrs();
+    let (lo, hi) = it.size_hint();
+    assert!(lo <= got.len() && hi.unwrap() >= got.len());
+    for _ in &mut it {}
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert!(it.next().is_none());
+    assert!(it.next().is_none());
+
+    assert_eq!(Substr::new().chars_as_substrs().count(), 0);
+}
+
+#[test]
+fn test_try_substr_from_char_boundary() {
+    let orig = ArcStr::from("aé→b");
+    let sub = orig.substr(..);
+    // Safety (ish): these `&str`s start or end inside a char, which code that
+    // misuses `unsafe` could produce. They're never read as `str`.
+    let (mid_start, mid_end) = unsafe { (orig.get_unchecked(2..4), orig.get_unchecked(1..4)) };
+    assert_eq!(orig.try_substr_from(mid_start), None);
+    assert_eq!(orig.try_substr_from(mid_end), None);
+    assert_eq!(sub.try_substr_from(mid_start), None);
+    assert_eq!(sub.try_substr_from(mid_end), None);
+    assert_eq!(sub.try_substr_from(&orig[1..3]).unwrap(), "é");
+
+    let r = std::panic::catch_unwind(|| sub.substr_from(mid_start));
+    let msg = *r.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        msg,
+        "Substr::substr_from: substr over 2..4 does not start and end on char boundaries"
+    );
+    let r = std::panic::catch_unwind(|| sub.substr_from("elsewhere"));
+    let msg = *r.unwrap_err().downcast::<&str>().unwrap();
+    assert_eq!(msg, "non-substring passed to Substr::substr_from");
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_bad_substr_idx_message() {
+    fn panic_message(f: impl FnOnce() -> Substr + std::panic::UnwindSafe) -> String {
+        *std::panic::catch_unwind(f)
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap()
+    }
+    let short = ArcStr::from("hello world!!!!");
+    assert_eq!(
+        panic_message(|| short.substr(5..20)),
+        "Bad substr range 5..20 for string of length 15 \
+         (end must be <= string length/index max size): \"hello world!!!!\"",
+    );
+    let long = ArcStr::from("0123456789012345678901234567890é and more");
+    assert_eq!(
+        panic_message(|| long.substr(32..31)),
+        "Bad substr range 32..31 for string of length 42 (start must be <= end): \
+         \"0123456789012345678901234567890\"...",
+    );
+    assert_eq!(
+        panic_message(|| long.substr(32..33)),
+        "Bad substr range 32..33 for string of length 42 \
+         (start and end must be on char boundaries): \"0123456789012345678901234567890\"...",
+    );
+    let sub = long.substr(1..);
+    assert_eq!(
+        panic_message(|| sub.substr(..50)),
+        "Bad substr range 1..51 for string of length 42 \
+         (end must be <= string length/index max size): \"0123456789012345678901234567890\"...",
+    );
+}
+
+#[cfg(feature = "verify")]
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_verify_invariants() {
+    let parent = ArcStr::from("aé→b");
+    ArcStr::verify_invariants(&parent);
+    ArcStr::verify_invariants(&arcstr::literal!("static"));
+    ArcStr::verify_invariants(&ArcStr::new());
+    parent.substr(1..6).verify_invariants();
+    parent.substr(..0).verify_invariants();
+
+    fn panic_message(s: Substr) -> String {
+        let r = std::panic::catch_unwind(|| s.verify_invariants());
+        *r.unwrap_err().downcast::<String>().unwrap()
+    }
+    let bad =
+        |r: std::ops::Range<usize>| unsafe { Substr::from_parts_unchecked(parent.clone(), r) };
+    assert_eq!(
+        panic_message(bad(3..1)),
+        "Substr invariant violated: start 3 is greater than end 1"
+    );
+    assert_eq!(
+        panic_message(bad(1..9)),
+        "Substr invariant violated: end 9 is past the end of the parent (length 7)"
+    );
+    assert_eq!(
+        panic_message(bad(2..3)),
+        "Substr invariant violated: range 2..3 is not on char boundaries"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_deref_checks_invariants() {
+    let parent = ArcStr::from("aé→b");
+    // These break `from_parts_unchecked`'s contract, but with debug assertions
+    // we panic before the bad range is used for anything.
+    let bad =
+        |r: std::ops::Range<usize>| unsafe { Substr::from_parts_unchecked(parent.clone(), r) };
+    for (r, msg) in [
+        (3..1, "start 3 is greater than end 1"),
+        (1..9, "end 9 is past the end of the parent (length 7)"),
+        (2..3, "range 2..3 is not on char boundaries"),
+    ] {
+        let s = bad(r);
+        let err = std::panic::catch_unwind(|| s.as_str().len()).unwrap_err();
+        let err = *err.downcast::<String>().unwrap();
+        assert_eq!(err, format!("Substr invariant violated: {}", msg));
+    }
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn test_unicode_segmentation() {
+    // Combining marks, CJK, a ZWJ emoji sequence, and a flag.
+    let text = ArcStr::from("ke\u{301}o 漢字 👨‍👩‍👧 🇯🇵. Ĉu vi? 是的。");
+    let gs: Vec<Substr> = text.grapheme_clusters(true).collect();
+    assert_eq!(gs.concat(), text.as_str());
+    assert!(gs.iter().all(|g| ArcStr::ptr_eq(g.parent(), &text)));
+    assert_eq!(gs[1], "e\u{301}");
+    assert_eq!(gs[4], "漢");
+    assert_eq!(gs[7], "👨‍👩‍👧");
+    assert_eq!(gs[9], "🇯🇵");
+    for g in &gs {
+        assert_eq!(&text[g.range()], g.as_str());
+    }
+    let back: Vec<Substr> = text.grapheme_clusters(true).rev().collect();
+    assert_eq!(back.into_iter().rev().collect::<Vec<_>>(), gs);
+    // Legacy clusters don't keep spacing marks with their base.
+    let deva = ArcStr::from("कि");
+    assert_eq!(deva.grapheme_clusters(true).count(), 1);
+    assert_eq!(deva.grapheme_clusters(false).count(), 2);
+
+    let words: Vec<Substr> = text.unicode_words().collect();
+    assert_eq!(words, ["ke\u{301}o", "漢", "字", "Ĉu", "vi", "是", "的"]);
+    assert_eq!(text.unicode_words().next_back().unwrap(), "的");
+
+    let sentences: Vec<Substr> = text.unicode_sentences().collect();
+    assert_eq!(sentences.concat(), text.as_str());
+    assert_eq!(sentences.last().unwrap(), "是的。");
+
+    // On a `Substr`, the ranges are still relative to the parent.
+    let sub = text.substr_from(&text[text.find('漢').unwrap()..]);
+    let words: Vec<Substr> = sub.unicode_words().collect();
+    assert_eq!(words[0].range(), sub.range().start..sub.range().start + 3);
+    assert_eq!(words, ["漢", "字", "Ĉu", "vi", "是", "的"]);
+    let gs: Vec<Substr> = sub.grapheme_clusters(false).collect();
+    assert_eq!(gs.concat(), sub.as_str());
+    assert!(gs.iter().all(|g| &text[g.range()] == g.as_str()));
+    let sentences: Vec<Substr> = sub.unicode_sentences().collect();
+    assert_eq!(sentences.concat(), sub.as_str());
+    assert!(sentences.iter().all(|s| &text[s.range()] == s.as_str()));
+
+    assert_eq!(Substr::new().grapheme_clusters(true).count(), 0);
+    assert_eq!(ArcStr::new().unicode_words().count(), 0);
+}
+
+#[test]
+fn test_splitn_rsplitn() {
+    let texts = [
+        "",
+        "a",
+        "key=value=extra",
+        "==",
+        "=a=",
+        "aaa",
+        "a,b,,c",
+        "ünï=cödé=",
+        "no separator",
+    ];
+    let seps = ["=", ",", "", "aa", "a", "xyz"];
+    for &text in &texts {
+        // With some padding, so that the ranges are relative to the parent.
+        let parent = ArcStr::from(format!("[{}]", text));
+        let sub = parent.substr(1..parent.len() - 1);
+        for &sep in &seps {
+            for n in 0..6 {
+                let ours: Vec<Substr> = sub.splitn_as_substrs(n, sep).collect();
+                let std: Vec<&str> = text.splitn(n, sep).collect();
+                assert_eq!(ours, std, "{:?}.splitn({}, {:?})", text, n, sep);
+                assert!(ours.iter().all(|s| &parent[s.range()] == s.as_str()));
+
+                let ours: Vec<Substr> = sub.rsplitn_as_substrs(n, sep).collect();
+                let std: Vec<&str> = text.rsplitn(n, sep).collect();
+                assert_eq!(ours, std, "{:?}.rsplitn({}, {:?})", text, n, sep);
+                assert!(ours.iter().all(|s| &parent[s.range()] == s.as_str()));
+
+                let whole = ArcStr::from(text);
+                let ours: Vec<Substr> = whole.splitn_as_substrs(n, sep).collect();
+                assert_eq!(ours, text.splitn(n, sep).collect::<Vec<_>>());
+                let ours: Vec<Substr> = whole.rsplitn_as_substrs(n, sep).collect();
+                assert_eq!(ours, text.rsplitn(n, sep).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    let s = Substr::from("k=v=x");
+    assert_eq!(s.splitn_as_substrs(0, "=").count(), 0);
+    assert_eq!(s.splitn_as_substrs(1, "=").collect::<Vec<_>>(), ["k=v=x"]);
+    assert_eq!(
+        s.splitn_as_substrs(2, "=").collect::<Vec<_>>(),
+        ["k", "v=x"]
+    );
+    assert_eq!(
+        s.splitn_as_substrs(10, "=").collect::<Vec<_>>(),
+        ["k", "v", "x"]
+    );
+    assert_eq!(s.rsplitn_as_substrs(0, "=").count(), 0);
+    assert_eq!(s.rsplitn_as_substrs(1, "=").collect::<Vec<_>>(), ["k=v=x"]);
+    assert_eq!(
+        s.rsplitn_as_substrs(2, "=").collect::<Vec<_>>(),
+        ["x", "k=v"]
+    );
+    assert_eq!(
+        s.rsplitn_as_substrs(10, "=").collect::<Vec<_>>(),
+        ["x", "v", "k"]
+    );
+    assert_eq!(
+        s.rsplitn_as_substrs(3, "").collect::<Vec<_>>(),
+        ["", "x", "k=v="]
+    );
+    // Fused.
+    let mut it = s.splitn_as_substrs(2, "=");
+    assert_eq!(it.by_ref().count(), 2);
+    assert_eq!(it.next(), None);
+    let mut it = s.rsplitn_as_substrs(2, "=");
+    assert_eq!(it.by_ref().count(), 2);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_split_inclusive() {
+    let texts = [
+        "",
+        ".",
+        "..",
+        ".a.b.",
+        "a..b",
+        "no separator",
+        "Hi. Bye. ",
+        "日本.語",
+    ];
+    for &text in &texts {
+        let parent = ArcStr::from(format!("<{}>", text));
+        let sub = parent.substr(1..parent.len() - 1);
+        for &sep in &[".", ". ", "", "..", "Hi. Bye. "] {
+            let ours: Vec<Substr> = sub.split_inclusive_as_substrs(sep).collect();
+            let std: Vec<&str> = text.split_inclusive(sep).collect();
+            assert_eq!(ours, std, "{:?}.split_inclusive({:?})", text, sep);
+            assert!(ours.iter().all(|s| &parent[s.range()] == s.as_str()));
+            let whole = ArcStr::from(text);
+            let ours: Vec<Substr> = whole.split_inclusive_as_substrs(sep).collect();
+            assert_eq!(ours, std);
+        }
+    }
+
+    let s = ArcStr::from(".start, middle.. end.");
+    let parts: Vec<Substr> = s.split_inclusive_as_substrs(".").collect();
+    assert_eq!(parts, [".", "start, middle.", ".", " end."]);
+    assert!(parts.iter().all(|p| p.ends_with('.')));
+    let whole: Vec<Substr> = s.split_inclusive_as_substrs(s.as_str()).collect();
+    assert_eq!(whole, [s.as_str()]);
+    assert_eq!(whole[0].range(), 0..s.len());
+
+    let mut it = s.split_inclusive_as_substrs(".");
+    assert_eq!(it.by_ref().count(), 4);
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_range_helpers() {
+    use arcstr::testing;
+    let input = ArcStr::from("name: héllo");
+    let name = input.substr(..4);
+    let value = input.substr(6..);
+    assert!(testing::parent_range_is(&name, 0..4));
+    assert!(!testing::parent_range_is(&name, 0..5));
+    testing::assert_range_eq(&value, 6..12);
+    assert!(testing::shares_parent(&name, &value));
+    assert!(!testing::shares_parent(&name, &Substr::from("name")));
+
+    let msg = std::panic::catch_unwind(|| testing::assert_range_eq(&value, 0..4))
+        .unwrap_err()
+        .downcast::<String>()
+        .unwrap();
+    assert!(msg.contains("6..12"), "{}", msg);
+    assert!(msg.contains("\"héllo\""), "{}", msg);
+    assert!(msg.contains("\"name\""), "{}", msg);
+    // Not a valid range of the parent (past the end, and not on a char
+    // boundary), but we still produce a message rather than panicking inside
+    // the panic.
+    for bad in [0..100, 8..9] {
+        let msg = std::panic::catch_unwind(|| testing::assert_range_eq(&value, bad))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert!(msg.contains("not a valid range"), "{}", msg);
+    }
+}
+
+#[test]
+fn test_arc_str_vec() {
+    use arcstr::ArcStrVec;
+    let input = ArcStr::from("fn main() { println!(\"héllo\"); }");
+    let mut v = ArcStrVec::with_capacity(input.clone(), 8);
+    assert!(v.is_empty());
+    assert_eq!(v.get(0), None);
+    let words: Vec<Substr> = input.substr(..).split_as_substrs(" ").collect();
+    for w in &words {
+        v.push(w.clone()).unwrap();
+    }
+    // Each element doesn't keep its own reference to the parent.
+    drop(words);
+    assert_eq!(ArcStr::strong_count(&input), Some(2));
+
+    assert_eq!(v.len(), 5);
+    assert!(ArcStr::ptr_eq(v.parent(), &input));
+    let fourth = v.get(3).unwrap();
+    assert_eq!(fourth, "println!(\"héllo\");");
+    assert!(ArcStr::ptr_eq(fourth.parent(), &input));
+    assert_eq!(fourth.range(), 12..31);
+    assert_eq!(v.get_str(1), Some("main()"));
+    assert_eq!(v.get_str(5), None);
+
+    let all: Vec<Substr> = v.iter().collect();
+    assert_eq!(all, ["fn", "main()", "{", "println!(\"héllo\");", "}"]);
+    assert_eq!(v.iter().len(), 5);
+    assert_eq!(v.iter().next_back().unwrap(), "}");
+    assert_eq!((&v).into_iter().count(), 5);
+    assert_eq!(
+        format!("{:?}", v),
+        r#"["fn", "main()", "{", "println!(\"héllo\");", "}"]"#
+    );
+
+    let err = v.push(Substr::from("fn")).unwrap_err();
+    assert_eq!(err, "fn");
+    let other = ArcStr::from(input.as_str());
+    assert!(v.push(other.substr(..2)).is_err());
+    assert_eq!(v.len(), 5);
+
+    assert_eq!(v.pop().unwrap(), "}");
+    let back: Vec<Substr> = v.clone().into();
+    assert_eq!(back.len(), 4);
+    v.clear();
+    assert!(v.is_empty());
+    assert_eq!(ArcStrVec::default().len(), 0);
+}
+
+#[test]
+fn test_arc_str_vec_empty_tokens() {
+    use arcstr::ArcStrVec;
+    let p = ArcStr::from("ab cd  ef");
+    let mut v = ArcStrVec::new(p.clone());
+    // The empty token between the two spaces comes back as `Substr::new()`,
+    // whose parent isn't `p`.
+    for t in p.split(' ') {
+        v.push(p.substr_from(t)).unwrap();
+    }
+    let got: Vec<Substr> = v.iter().collect();
+    assert_eq!(got, ["ab", "cd", "", "ef"]);
+    assert_eq!(v.get_str(2), Some(""));
+    assert!(ArcStr::ptr_eq(v.get(2).unwrap().parent(), &p));
+    // Empty ones with our parent keep their position.
+    v.push(p.substr(5..5)).unwrap();
+    assert_eq!(v.get(4).unwrap().range(), 5..5);
+    v.push(Substr::from("")).unwrap();
+    v.push(ArcStr::from("xyz").substr(3..)).unwrap();
+    assert_eq!(v.get(6).unwrap().range(), 0..0);
+    assert_eq!(v.len(), 7);
+}
+
+#[test]
+fn test_as_bytes() {
+    let parent = ArcStr::from("a🦀bé");
+    for (start, _) in parent.char_indices() {
+        for end in (start..=parent.len()).filter(|&e| parent.is_char_boundary(e)) {
+            let s = parent.substr(start..end);
+            assert_eq!(s.as_bytes(), s.as_str().as_bytes());
+            assert_eq!(s.as_bytes(), &parent.as_bytes()[start..end]);
+            assert_eq!(s.as_bytes().as_ptr(), s.as_ptr());
+        }
+    }
+    assert_eq!(Substr::new().as_bytes(), b"");
+    // Same API as `ArcStr`.
+    let whole = parent.substr(..);
+    assert_eq!(whole.as_bytes(), parent.as_bytes());
+}
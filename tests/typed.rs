@@ -0,0 +1,104 @@
+#![cfg(feature = "typed")]
+#![allow(
+    // need to test cloning, these are deliberate.
+    clippy::redundant_clone,
+)]
+
+use arcstr::{ArcStr, TypedArcStr};
+
+enum FooTag {}
+enum BarTag {}
+// Deliberately implements nothing.
+struct NotCloneTag;
+
+// Compiles only if `$t` does *not* implement `$tr`: otherwise, the call to
+// `check` is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($t:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn check() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$t as AmbiguousIfImpl<_>>::check();
+    }};
+}
+
+#[test]
+fn test_tags_dont_mix() {
+    assert_not_impl!(TypedArcStr<FooTag>: PartialEq<TypedArcStr<BarTag>>);
+    assert_not_impl!(TypedArcStr<BarTag>: PartialEq<TypedArcStr<FooTag>>);
+    assert_not_impl!(TypedArcStr<FooTag>: From<TypedArcStr<BarTag>>);
+    assert_not_impl!(TypedArcStr<FooTag>: From<ArcStr>);
+
+    let foo: TypedArcStr<FooTag> = ArcStr::from("same").typed();
+    let bar: TypedArcStr<BarTag> = ArcStr::from("same").typed();
+    // Same content, and each compares equal to it, but they can only be
+    // compared with each other by removing the tags.
+    assert_eq!(foo, "same");
+    assert_eq!(bar, "same");
+    assert_eq!(foo.as_arcstr(), bar.as_arcstr());
+}
+
+#[test]
+fn test_basics() {
+    let a: TypedArcStr<FooTag> = ArcStr::from("hello").typed();
+    let b = a.clone();
+    assert!(TypedArcStr::ptr_eq(&a, &b));
+    assert_eq!(ArcStr::strong_count(&a), Some(2));
+    assert_eq!(a, b);
+    assert_eq!(a.as_str(), "hello");
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.to_uppercase(), "HELLO");
+    assert_eq!(format!("{} {:?}", a, a), "hello \"hello\"");
+    assert_eq!(TypedArcStr::<FooTag>::default(), "");
+    assert!(a < ArcStr::from("world").typed::<FooTag>());
+
+    let s: ArcStr = a.into();
+    assert!(ArcStr::ptr_eq(&s, b.as_arcstr()));
+    assert!(ArcStr::ptr_eq(&s, &b.into_arcstr()));
+
+    // The tag's own traits don't matter.
+    let n: TypedArcStr<NotCloneTag> = ArcStr::from("n").typed();
+    assert_eq!(n.clone(), n);
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TypedArcStr<NotCloneTag>>();
+    assert_send_sync::<TypedArcStr<*const u8>>();
+    assert_eq!(
+        std::mem::size_of::<TypedArcStr<NotCloneTag>>(),
+        std::mem::size_of::<ArcStr>()
+    );
+}
+
+#[test]
+fn test_typed_literal() {
+    const FOO: TypedArcStr<FooTag> = arcstr::typed_literal!("foo", FooTag);
+    assert_eq!(FOO, "foo");
+    assert!(ArcStr::is_static(&FOO));
+    let bar = arcstr::typed_literal!("bar", BarTag);
+    assert_eq!(bar, "bar");
+}
+
+#[test]
+fn test_hash_map() {
+    use std::collections::HashMap;
+    let mut m: HashMap<TypedArcStr<FooTag>, u32> = HashMap::new();
+    m.insert(ArcStr::from("one").typed(), 1);
+    m.insert(arcstr::typed_literal!("two", FooTag), 2);
+    // `Borrow<str>`, so lookups don't need a `TypedArcStr`.
+    assert_eq!(m.get("one"), Some(&1));
+    assert_eq!(m.get("two"), Some(&2));
+    assert_eq!(m.get("three"), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_test::{assert_tokens, Token};
+    let a: TypedArcStr<FooTag> = ArcStr::from("serde").typed();
+    assert_tokens(&a, &[Token::Str("serde")]);
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    assert_eq!(bytes, postcard::to_allocvec("serde").unwrap());
+    let back: TypedArcStr<FooTag> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(back, a);
+}
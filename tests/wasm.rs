@@ -0,0 +1,50 @@
+//! Run with `wasm-pack test --node -- --features wasm-bindgen`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+
+use arcstr::ArcStr;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(inline_js = r#"
+export function call_with(f, s) { return f(s); }
+export function echo(s) { return s; }
+export function echo_opt(s) { return s; }
+"#)]
+extern "C" {
+    fn call_with(f: &js_sys_function::Function, s: ArcStr) -> ArcStr;
+    fn echo(s: ArcStr) -> ArcStr;
+    fn echo_opt(s: Option<ArcStr>) -> Option<ArcStr>;
+}
+
+mod js_sys_function {
+    use wasm_bindgen::prelude::*;
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = Function)]
+        pub type Function;
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_roundtrip() {
+    let s = ArcStr::from("test test 123 456 🙀");
+    assert_eq!(echo(s.clone()), s);
+    assert_eq!(echo(arcstr::literal!("lit")), "lit");
+    assert_eq!(echo(ArcStr::new()), "");
+    assert_eq!(echo_opt(Some(s.clone())), Some(s));
+    assert_eq!(echo_opt(None), None);
+}
+
+#[wasm_bindgen_test]
+fn test_js_callback() {
+    let received = std::rc::Rc::new(std::cell::RefCell::new(None));
+    let r2 = received.clone();
+    let cb = Closure::<dyn FnMut(ArcStr) -> ArcStr>::new(move |s: ArcStr| {
+        *r2.borrow_mut() = Some(s.clone());
+        arcstr::format!("{}!", s)
+    });
+    let f: &js_sys_function::Function = cb.as_ref().unchecked_ref();
+    let out = call_with(f, ArcStr::from("hello"));
+    assert_eq!(out, "hello!");
+    assert_eq!(received.borrow().as_deref(), Some("hello"));
+}